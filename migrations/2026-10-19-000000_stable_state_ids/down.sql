@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE saved_states DROP COLUMN state_id;
+ALTER TABLE saved_states DROP COLUMN name;
+ALTER TABLE saved_connections DROP COLUMN start_state_id;
+ALTER TABLE saved_connections DROP COLUMN end_state_id;
@@ -0,0 +1,8 @@
+-- States used to be identified by their position, so existing states keep it as their id
+ALTER TABLE saved_states ADD COLUMN state_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE saved_states ADD COLUMN name TEXT;
+UPDATE saved_states SET state_id = position;
+
+ALTER TABLE saved_connections ADD COLUMN start_state_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE saved_connections ADD COLUMN end_state_id TEXT NOT NULL DEFAULT '';
+UPDATE saved_connections SET start_state_id = start_point, end_state_id = end_point;
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+UPDATE saved_workspaces SET alphabet = (
+  SELECT COALESCE(GROUP_CONCAT(value, ','), '') FROM json_each(saved_workspaces.alphabet)
+);
+
+UPDATE saved_connections SET connection_character = REPLACE(REPLACE(connection_character, '\,', ','), '\\', '\');
+
+ALTER TABLE saved_workspaces DROP COLUMN input_separator;
@@ -0,0 +1,21 @@
+-- Alphabets used to be joined with commas, which made a comma impossible as a symbol, so they are now stored as JSON arrays.
+-- The old alphabet is split at its commas and the symbols handed to json_group_array, which escapes any quote,
+-- backslash or control character a symbol holds
+UPDATE saved_workspaces SET alphabet = (
+  WITH RECURSIVE symbols(symbol, rest, position) AS (
+    SELECT NULL, saved_workspaces.alphabet || ',', 0
+    UNION ALL
+    SELECT substr(rest, 1, instr(rest, ',') - 1), substr(rest, instr(rest, ',') + 1), position + 1
+    FROM symbols
+    WHERE rest <> ''
+  )
+  SELECT json_group_array(symbol) FROM (
+    SELECT symbol FROM symbols WHERE symbol <> '' ORDER BY position
+  )
+);
+
+-- Connection labels now escape commas and backslashes within their characters. Every label saved before this held a
+-- single character, so the whole label is escaped
+UPDATE saved_connections SET connection_character = REPLACE(REPLACE(connection_character, '\', '\\'), ',', '\,');
+
+ALTER TABLE saved_workspaces ADD COLUMN input_separator TEXT;
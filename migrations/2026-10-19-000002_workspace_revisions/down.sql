@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE saved_workspaces DROP COLUMN max_revisions;
+
+DROP TABLE workspace_revisions;
@@ -0,0 +1,11 @@
+-- Every save also stores a snapshot of the workspace in the same JSON layout workspaces are exported in,
+-- keeping the most recent max_revisions of them
+CREATE TABLE workspace_revisions (
+  id INTEGER PRIMARY KEY NOT NULL UNIQUE,
+  workspace_id INTEGER NOT NULL,
+  date_of_revision TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  snapshot TEXT NOT NULL,
+  FOREIGN KEY (workspace_id) REFERENCES saved_workspaces(id)
+);
+
+ALTER TABLE saved_workspaces ADD COLUMN max_revisions INTEGER NOT NULL DEFAULT 50;
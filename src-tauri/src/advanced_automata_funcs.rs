@@ -1,9 +1,13 @@
-use std::{cell::RefCell, collections::{HashMap, HashSet}};
-use app::{create_connections_from_state_positions, create_unique_state_coordinates, models::WorkspaceData, remove_all_epsilon_transitions};
+use std::{cell::RefCell, collections::{BTreeMap, BTreeSet, HashMap, HashSet}};
+use app::{create_connections_from_state_positions, create_state_names, create_state_numbers, create_unique_state_coordinates,
+models::WorkspaceData, remove_all_epsilon_transitions};
 
-use app::models::{Connection, Coordinate, State};
+use app::labels::{split_labels_into_minterms, TransitionLabel, OTHERWISE_LABEL};
+use app::layout::layout_state_positions;
+use app::models::{Connection, Coordinate, State, TypeOfAutomata};
 
-use crate::saving_automata_funcs::{retrieve_workspace_data, save_workspace};
+use crate::saving_automata_funcs::{retrieve_workspace_data, save_automata_to_target, WorkspaceTarget};
+mod tests;
 
 fn mark_unequivalent_states_in_dfa(
   state_positions: &HashMap<String, State>, 
@@ -130,6 +134,7 @@ fn remove_redundant_state_connections(
 
 fn remove_redundant_connections(
   connections: Vec<Connection>,
+  state_positions: &HashMap<String, State>,
   equivalent_state_keys: &HashMap<&String, &String>,
   state_keys_to_be_ignored: &HashSet<&String>
 ) -> Vec<Connection> {
@@ -138,8 +143,8 @@ fn remove_redundant_connections(
 
   for mut connection in connections {
 
-    let start_state_key: String = connection.curve.start_point.into();
-    let end_state_key: String = connection.curve.end_point.into();
+    let start_state_key = connection.get_start_state_key(state_positions);
+    let end_state_key = connection.get_end_state_key(state_positions);
     
     if !state_keys_to_be_ignored.contains(&start_state_key) 
       && !state_keys_to_be_ignored.contains(&end_state_key) {
@@ -149,14 +154,10 @@ fn remove_redundant_connections(
     } else if !state_keys_to_be_ignored.contains(&start_state_key) {
 
       if let Some(equivalent_state_key) = equivalent_state_keys.get(&end_state_key) {
-        
-        // Looks ugly, fix in future
-        let equivalent_state_key: Coordinate = equivalent_state_key
-          .to_owned()
-          .try_into()
-          .expect("There was an error parsing the coordinate to a string");
 
-        connection.curve.end_point = equivalent_state_key;
+        connection.curve.end_point = state_positions[*equivalent_state_key].get_position();
+        connection.start_state_id = start_state_key;
+        connection.end_state_id = equivalent_state_key.to_string();
 
         updated_connections.push(connection);
 
@@ -172,18 +173,42 @@ fn remove_redundant_connections(
 
 #[tauri::command]
 pub fn minimize_dfa(
-  mut state_positions: HashMap<String, State>,
+  state_positions: HashMap<String, State>,
   connections: Vec<Connection>,
   input_alphabet: Vec<String>,
   email: &str,
-  workspace_name: &str
+  workspace_name: &str,
+  target: WorkspaceTarget
 ) -> WorkspaceData {
 
+  let (minimized_state_positions, connections) = minimize_state_positions(state_positions, connections, &input_alphabet);
+
+  save_automata_to_target(workspace_name, email, target, minimized_state_positions, connections, None, None)
+
+}
+
+// Merges every class of equivalent states into one, named after the states it stands for as in [q1,q3], and keeps only
+// the connections leaving the states which are kept, redirected to the states kept in place of the ones they led to
+pub fn minimize_state_positions(
+  mut state_positions: HashMap<String, State>,
+  connections: Vec<Connection>,
+  input_alphabet: &[String]
+) -> (HashMap<String, State>, Vec<Connection>) {
+
   // DFA's are typically required to have a connection for each state for each character in the input alphabet.
   // This however, is very unuser friendly as it is very easy to infer that an unspecified connection results in automatic unacceptance.
   // To prevent mandatory and explicit unaccepted transitions, what we will do is create a special "vortex" state, that under the hood
   // All unspecified connections are sent to and cannot escape. Doing this allows us to continue using the same algorithm
   // For DFA minimization while maintaining good user experience. The following code reflects that.
+  // Names are worked out before the vortex state is added, so it is left out of the names given to the minimized states
+  let state_names = create_state_names(&state_positions);
+  let state_numbers = create_state_numbers(&state_positions);
+
+  // Connections are compared by their labels, so classes are first split up so that no two labels overlap. Only the
+  // check for equivalent states uses the split labels, as the connections kept are the ones the user drew
+  split_labels_into_minterms(&mut state_positions);
+  let input_alphabet = get_minterm_alphabet(&state_positions, input_alphabet);
+
   let vortex_state_coords = create_unique_state_coordinates(&state_positions.keys().cloned().collect());
   let mut vortex_state = State::new(vortex_state_coords, false, false);
   
@@ -243,20 +268,54 @@ pub fn minimize_dfa(
   // If a state does not need to be ignored, we will still need to change all references made to an ignored state to its equivalent
   // Which we chose not to ignore
   // This must be done after the final list of all states to be ignored has been made
+  // Every state is named after the class of equivalent states it stands for, as in [q1,q3]
   for state in minimized_states.iter_mut() {
     *state = remove_redundant_state_connections(state.to_owned(), &equivalent_state_keys, &state_keys_to_be_ignored);
-    minimized_state_positions.insert(state.get_position_as_string(), state.to_owned());
+
+    let state_key = state.get_id().to_owned();
+    let equivalence_class: Vec<&String> = equivalent_state_keys
+      .iter()
+      .filter(|(_, equivalent_state_key)| ***equivalent_state_key == state_key)
+      .map(|(equivalent_state_key, _)| *equivalent_state_key)
+      .chain([&state_key])
+      .collect();
+    state.set_name(Some(create_merged_state_name(&state_names, &state_numbers, &equivalence_class, ('[', ']'))));
+
+    minimized_state_positions.insert(state_key, state.to_owned());
   }
 
-  let connections = remove_redundant_connections(connections, &equivalent_state_keys , &state_keys_to_be_ignored);
+  let connections = remove_redundant_connections(connections, &state_positions, &equivalent_state_keys , &state_keys_to_be_ignored);
 
   // Removing temporary implicit vortex state
   minimized_state_positions.remove::<String>(&vortex_state_coords.into());
 
-  save_workspace(workspace_name, minimized_state_positions, email, connections);
-  let workspace_data = retrieve_workspace_data(workspace_name, email);
+  (minimized_state_positions, connections)
+
+}
+
+// Once labels have been split into minterms, they act as the symbols of the alphabet. Symbols of the alphabet which
+// no label matches are kept as they are, unless a [*] label stands for them
+fn get_minterm_alphabet(state_positions: &HashMap<String, State>, input_alphabet: &[String]) -> Vec<String> {
 
-  return workspace_data;
+  let labels: BTreeSet<&String> = state_positions
+    .values()
+    .flat_map(|state| state.get_all_connections().keys())
+    .filter(|label| TransitionLabel::parse(label) != TransitionLabel::Epsilon)
+    .collect();
+
+  let parsed_labels: Vec<TransitionLabel> = labels.iter().map(|label| TransitionLabel::parse(label)).collect();
+  let has_otherwise_label = labels.contains(&OTHERWISE_LABEL.to_owned());
+
+  let unmatched_symbols = input_alphabet
+    .iter()
+    .filter(|symbol| !has_otherwise_label && !parsed_labels.iter().any(|label| label.matches(symbol)));
+
+  labels
+    .iter()
+    .copied()
+    .chain(unmatched_symbols)
+    .cloned()
+    .collect()
 
 }
 
@@ -289,29 +348,68 @@ fn specify_implicit_state_connections(input_alphabet: &Vec<String>, state_positi
 
 #[tauri::command]
 pub fn convert_nfa_to_dfa (
-  mut state_positions: HashMap<String, State>,
+  state_positions: HashMap<String, State>,
   start_state_position: &str,
   email: &str,
-  workspace_name: &str
+  workspace_name: &str,
+  target: WorkspaceTarget
 ) -> WorkspaceData {
-  
-  remove_all_epsilon_transitions(&mut state_positions);
-    
-  // I would like to be able to use a hashset of Strings here however unfortunately 
-  // Hashsets cannot be hashed so we have to convert and ensure that we correctly sort the
-  // hashsets we push into vecs
 
-  let reconstructed_state_positions = reconstruct_nfa_state_positions(
-    &state_positions, 
+  let reconstructed_state_positions = determinize_state_positions(
+    state_positions, 
     start_state_position
   );
+  let reconstructed_state_positions = layout_state_positions(&reconstructed_state_positions);
 
   let connections = create_connections_from_state_positions(&reconstructed_state_positions);
 
-  save_workspace(workspace_name, reconstructed_state_positions, email, connections);
+  save_automata_to_target(
+    workspace_name,
+    email,
+    target,
+    reconstructed_state_positions,
+    connections,
+    Some(TypeOfAutomata::DFA),
+    None
+  )
+
+}
+
+// Moves every state into columns by its distance from the start state, redrawing every connection to match
+#[tauri::command]
+pub fn auto_layout_workspace(workspace_name: &str, email: &str, target: WorkspaceTarget) -> WorkspaceData {
+
   let workspace_data = retrieve_workspace_data(workspace_name, email);
 
-  return workspace_data;
+  let state_positions = layout_state_positions(workspace_data.get_state_positions());
+  let connections = create_connections_from_state_positions(&state_positions);
+
+  save_automata_to_target(workspace_name, email, target, state_positions, connections, None, None)
+
+}
+
+// Removes all ϵ transitions and then performs the subset construction. The start state keeps its original key
+pub fn determinize_state_positions(
+  mut state_positions: HashMap<String, State>,
+  start_state_position: &str
+) -> HashMap<String, State> {
+
+  // Unnamed states are given the names they are shown under before any ϵ transitions are removed, so the subsets
+  // are named after the states of the automaton as the user sees it
+  let state_names = create_state_names(&state_positions);
+  for (state_key, state) in state_positions.iter_mut() {
+    state.set_name(Some(state_names[state_key].to_owned()));
+  }
+
+  // Overlapping labels such as a and [a-z] are split into disjoint ones first, so that sets of states are
+  // only ever merged over labels standing for exactly the same symbols
+  split_labels_into_minterms(&mut state_positions);
+  remove_all_epsilon_transitions(&mut state_positions);
+
+  // I would like to be able to use a hashset of Strings here however unfortunately 
+  // Hashsets cannot be hashed so we have to convert and ensure that we correctly sort the
+  // hashsets we push into vecs
+  reconstruct_nfa_state_positions(&state_positions, start_state_position)
 
 }
 
@@ -323,13 +421,42 @@ pub fn reconstruct_nfa_state_positions(
   let mut reconstructed_state_positions: HashMap<String, RefCell<State>> = HashMap::new();
   let mut hashed_state_keys: HashMap<Vec<String>, String> = HashMap::new();
 
-  let start_state = state_positions
-  .get(start_state_position)
-  .expect("There was an error retrieving the start state")
-  .to_owned();
-
-  reconstructed_state_positions
-    .insert(start_state_position.to_owned(), RefCell::from(start_state.to_owned()));
+  let mut start_state_keys: Vec<String> = state_positions
+    .iter()
+    .filter(|(state_key, state)| state.is_start() || *state_key == start_state_position)
+    .map(|(state_key, _)| state_key.to_owned())
+    .collect();
+  start_state_keys.sort();
+
+  // With several initial states, the DFA starts from the set of all of them. It is given a key of its own, as the keys
+  // of the original states are how connections which have not yet been replaced are told apart, and is moved to the key
+  // of the given start state once the construction is finished
+  let merged_start_state_key: Option<String> = if start_state_keys.len() > 1 {
+    let merged_start_state_coords = create_unique_state_coordinates(&state_positions.keys().cloned().collect());
+    let start_state_key_set: HashSet<String> = start_state_keys.iter().cloned().collect();
+
+    let mut merged_start_state = State::new(
+      merged_start_state_coords,
+      true,
+      does_contain_final_state(state_positions, &start_state_key_set)
+    );
+    merged_start_state.set_all_connections(get_all_connected_state_keys(state_positions, &start_state_key_set));
+
+    reconstructed_state_positions.insert(merged_start_state_coords.into(), RefCell::from(merged_start_state));
+    hashed_state_keys.insert(start_state_keys.to_owned(), merged_start_state_coords.into());
+
+    Some(merged_start_state_coords.into())
+  } else {
+    let start_state = state_positions
+    .get(start_state_position)
+    .expect("There was an error retrieving the start state")
+    .to_owned();
+
+    reconstructed_state_positions
+      .insert(start_state_position.to_owned(), RefCell::from(start_state.to_owned()));
+
+    None
+  };
 
   let mut finished = false;
 
@@ -422,7 +549,7 @@ pub fn reconstruct_nfa_state_positions(
     }
 
     for state in states_to_add {
-      let state_key = state.get_position_as_string();
+      let state_key = state.get_id().to_owned();
 
       if !reconstructed_state_positions.contains_key(&state_key) {
         reconstructed_state_positions.insert(state_key, RefCell::from(state));
@@ -433,25 +560,83 @@ pub fn reconstruct_nfa_state_positions(
 
   };
 
+  // Every state is named after the set of states it stands for, as in {q0,q1}
+  let state_names = create_state_names(state_positions);
+  let state_numbers = create_state_numbers(state_positions);
+  let mut subset_state_keys: HashMap<&String, Vec<&String>> = hashed_state_keys
+    .iter()
+    .map(|(subset, state_key)| (state_key, subset.iter().collect()))
+    .collect();
+  let start_state_key = start_state_position.to_owned();
+  if merged_start_state_key.is_none() {
+    subset_state_keys.insert(&start_state_key, vec![&start_state_key]);
+  }
+
   // All necessary changes have been made to the reconstructed state positions, thus
   // we are safe to reconstruct the states and connections without fear they may later
   // become incorrect
   let mut final_state_positions = HashMap::new();
 
-  for (state_key, state) in reconstructed_state_positions {
+  for (state_key, state) in &reconstructed_state_positions {
+
+    let mut state = state.borrow().to_owned();
 
-    let state = state.borrow();
+    if let Some(subset) = subset_state_keys.get(state_key) {
+      state.set_name(Some(create_merged_state_name(&state_names, &state_numbers, subset, ('{', '}'))));
+    }
 
     final_state_positions
-      .insert(state_key.to_owned(), state.to_owned());
+      .insert(state_key.to_owned(), state);
 
   };
 
+  // Every connection now leads to a state created by the construction, so the given start state's key is free to be reused
+  if let Some(merged_start_state_key) = merged_start_state_key {
+    for state in final_state_positions.values_mut() {
+      for connected_state_keys in state.get_all_connections_mut().values_mut() {
+        if connected_state_keys.remove(&merged_start_state_key) {
+          connected_state_keys.insert(start_state_key.to_owned());
+        }
+      }
+    }
+
+    if let Some(merged_start_state) = final_state_positions.remove(&merged_start_state_key) {
+      let mut start_state = State::with_id(
+        start_state_key.to_owned(),
+        merged_start_state.get_position(),
+        true,
+        merged_start_state.is_final()
+      );
+      start_state.set_name(merged_start_state.get_name().map(String::from));
+      start_state.set_all_connections(merged_start_state.get_all_connections().to_owned());
+
+      final_state_positions.insert(start_state_key, start_state);
+    }
+  }
+
   return final_state_positions;
 
 }
 
 
+// Joins the names of the given states in the order they are numbered in, such as {q0,q2} for a subset or [q1,q3] for an equivalence class
+fn create_merged_state_name(
+  state_names: &BTreeMap<String, String>,
+  state_numbers: &BTreeMap<String, usize>,
+  state_keys: &[&String],
+  (open, close): (char, char)
+) -> String {
+
+  let mut state_keys: Vec<&String> = state_keys.iter().copied().filter(|state_key| state_names.contains_key(*state_key)).collect();
+  state_keys.sort_by_key(|state_key| state_numbers[*state_key]);
+  state_keys.dedup();
+
+  let merged_names: Vec<&str> = state_keys.into_iter().map(|state_key| state_names[state_key].as_str()).collect();
+
+  format!("{open}{}{close}", merged_names.join(","))
+
+}
+
 fn get_all_connected_state_keys (
   state_positions: &HashMap<String, State>, 
   state_keys: &HashSet<String>) -> HashMap<String, HashSet<String>> {
@@ -0,0 +1,193 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::advanced_automata_funcs::{determinize_state_positions, get_minterm_alphabet, minimize_state_positions};
+  use crate::testing_automata_funcs::{test_string_dfa, test_string_nfa};
+  use crate::test_fixtures::create_workspace_data;
+  use app::create_connections_from_state_positions;
+  use app::labels::get_connected_state_keys_by_symbol;
+  use app::models::{Coordinate, State, TypeOfAutomata};
+  use std::collections::{BTreeSet, HashMap};
+
+  #[test]
+  fn test_subset_construction_names() {
+    // An NFA for strings ending in ab, with the last state named by the user
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 500, y: 300 }, false, false);
+    let mut q2 = State::new(Coordinate { x: 700, y: 300 }, false, true);
+    q0.add_connection("a", "300,300");
+    q0.add_connection("a", "500,300");
+    q0.add_connection("b", "300,300");
+    q1.add_connection("b", "700,300");
+    q2.set_name(Some(String::from("end")));
+
+    let state_positions = HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("500,300"), q1),
+      (String::from("700,300"), q2)
+    ]);
+
+    let dfa_state_positions = determinize_state_positions(state_positions, "300,300");
+
+    assert_eq!(dfa_state_positions["300,300"].get_name(), Some("{q0}"));
+    assert_eq!(
+      dfa_state_positions.values().filter_map(|state| state.get_name()).collect::<BTreeSet<&str>>(),
+      BTreeSet::from(["{q0}", "{q0,q1}", "{q0,end}"])
+    );
+
+    let final_state = dfa_state_positions.values().find(|state| state.is_final()).unwrap();
+    assert_eq!(final_state.get_name(), Some("{q0,end}"));
+  }
+
+  #[test]
+  fn test_multiple_start_states() {
+    // An NFA accepting a from its first start state and b from its second
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 300, y: 500 }, true, false);
+    let q2 = State::new(Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection("a", "500,300");
+    q1.add_connection("b", "500,300");
+
+    let state_positions = HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("300,500"), q1),
+      (String::from("500,300"), q2)
+    ]);
+
+    let (is_accepted, states_visited) = test_string_nfa(state_positions.clone(), String::from("300,300"), String::from("b"));
+    assert!(is_accepted);
+    assert_eq!(states_visited[0].get_id(), "300,500");
+    assert!(!test_string_nfa(state_positions.clone(), String::from("300,300"), String::from("ab")).0);
+
+    let dfa_state_positions = determinize_state_positions(state_positions, "300,300");
+
+    let start_state = &dfa_state_positions["300,300"];
+    assert_eq!(start_state.get_name(), Some("{q0,q1}"));
+    assert!(!start_state.is_final());
+    assert_eq!(dfa_state_positions.values().filter(|state| state.is_start()).count(), 1);
+
+    for (string_to_check, should_accept) in [("a", true), ("b", true), ("", false), ("ab", false)] {
+      let (is_accepted, _) = test_string_dfa(dfa_state_positions.clone(), String::from("300,300"), String::from(string_to_check));
+      assert_eq!(is_accepted, should_accept, "{string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_determinizing_character_classes() {
+    // An NFA accepting a digit, or any letter other than x, or x followed by anything, where the classes overlap
+    // and the [*] of the second state should only apply to symbols that its own labels don't cover
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 500, y: 300 }, false, false);
+    let q2 = State::new(Coordinate { x: 700, y: 300 }, false, true);
+    q0.add_connection("[0-9]", "700,300");
+    q0.add_connection("[a-z]", "500,300");
+    q0.add_connection("x", "500,300");
+    q1.add_connection("ϵ", "700,300");
+    q1.add_connection("x", "500,300");
+    q1.add_connection("[*]", "700,300");
+
+    let state_positions = HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("500,300"), q1),
+      (String::from("700,300"), q2)
+    ]);
+
+    let strings_to_check = ["", "5", "a", "x", "xx", "ax", "a5", "x🙂", "5a", "🙂", "ab"];
+    let dfa_state_positions = determinize_state_positions(state_positions.clone(), "300,300");
+
+    for string_to_check in strings_to_check {
+      let (is_accepted_by_nfa, _) = test_string_nfa(state_positions.clone(), String::from("300,300"), String::from(string_to_check));
+      let (is_accepted_by_dfa, _) = test_string_dfa(dfa_state_positions.clone(), String::from("300,300"), String::from(string_to_check));
+      assert_eq!(is_accepted_by_nfa, is_accepted_by_dfa, "{string_to_check}");
+    }
+
+    // The letters other than x become one label so every state of the DFA agrees on them
+    let alphabet = get_minterm_alphabet(&dfa_state_positions, &[]);
+    assert_eq!(alphabet.iter().map(String::as_str).collect::<BTreeSet<&str>>(), BTreeSet::from(["[0-9]", "[a-wyz]", "x", "[*]"]));
+    for state in dfa_state_positions.values() {
+      for symbol in ["q", "x", "0", "🙂", "if"] {
+        assert!(get_connected_state_keys_by_symbol(state, symbol).len() <= 1, "{} on {symbol}", state.get_id());
+      }
+    }
+  }
+
+  #[test]
+  fn test_minimization_names() {
+    // A DFA for strings ending in a, where q0 is equivalent to q2 and q1 to q3
+    let mut state_positions = HashMap::new();
+    for (state_number, is_final) in [(0, false), (1, true), (2, false), (3, true)] {
+      let state_key = format!("q{state_number}");
+      let state = State::with_id(&state_key, Coordinate { x: 300 + 200 * state_number, y: 300 }, state_number == 0, is_final);
+      state_positions.insert(state_key, state);
+    }
+    for (from, connection_character, to) in [
+      ("q0", "a", "q1"), ("q0", "b", "q2"), ("q1", "a", "q3"), ("q1", "b", "q2"),
+      ("q2", "a", "q3"), ("q2", "b", "q0"), ("q3", "a", "q1"), ("q3", "b", "q0")
+    ] {
+      state_positions.get_mut(from).unwrap().add_connection(connection_character, to);
+    }
+    let connections = create_connections_from_state_positions(&state_positions);
+
+    let (minimized_state_positions, minimized_connections) =
+      minimize_state_positions(state_positions.clone(), connections, &[String::from("a"), String::from("b")]);
+
+    assert_eq!(
+      minimized_state_positions.values().filter_map(|state| state.get_name()).collect::<BTreeSet<&str>>(),
+      BTreeSet::from(["[q0,q2]", "[q1,q3]"])
+    );
+    let start_state = minimized_state_positions.values().find(|state| state.is_start()).unwrap();
+    assert_eq!(start_state.get_name(), Some("[q0,q2]"));
+
+    // Only the connections leaving the two states kept remain, each leading to one of them
+    assert_eq!(minimized_connections.len(), 4);
+    for connection in &minimized_connections {
+      assert!(minimized_state_positions.contains_key(&connection.get_start_state_key(&minimized_state_positions)));
+      assert!(minimized_state_positions.contains_key(&connection.get_end_state_key(&minimized_state_positions)));
+    }
+
+    for string_to_check in ["", "a", "b", "ab", "ba", "bba", "abab", "aaaa"] {
+      let (is_accepted, _) = test_string_dfa(minimized_state_positions.clone(), start_state.get_id().to_owned(), String::from(string_to_check));
+      assert_eq!(is_accepted, string_to_check.ends_with('a'), "{string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_previewing_results() {
+    // An NFA on a and ϵ, previewed as a DFA over a workspace that has nothing in it yet
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let q1 = State::new(Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection("a", "300,300");
+    q0.add_connection("ϵ", "500,300");
+
+    let state_positions = HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("500,300"), q1)
+    ]);
+
+    let empty_workspace_data = create_workspace_data(serde_json::json!({ "type_of_automata": "NFA", "alphabet": ["a"] }));
+
+    let dfa_state_positions = determinize_state_positions(state_positions, "300,300");
+    let connections = create_connections_from_state_positions(&dfa_state_positions);
+
+    let preview = empty_workspace_data.with_automata(
+      dfa_state_positions.clone(),
+      connections,
+      TypeOfAutomata::DFA,
+      vec![String::from("a")]
+    );
+
+    assert_eq!(preview.get_workspace_name(), "Untitled");
+    assert_eq!(preview.get_type_of_automata(), TypeOfAutomata::DFA);
+    assert_eq!(preview.get_start_state_position().as_deref(), Some("300,300"));
+    assert_eq!(preview.get_start_state_positions(), &[String::from("300,300")]);
+
+    // The transitions are read back from the connections, just as they would be after saving
+    assert_eq!(preview.get_state_positions(), &dfa_state_positions);
+    for state_key in dfa_state_positions.keys() {
+      assert_eq!(
+        preview.get_state_positions()[state_key].get_all_connections(),
+        dfa_state_positions[state_key].get_all_connections()
+      );
+    }
+  }
+
+}
@@ -0,0 +1,252 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use app::{get_alphabet_from_state_positions, sanitize_input_alphabet};
+use app::labels::is_symbolic_label;
+use app::models::{join_connection_characters, split_connection_label, Connection, State, WorkspaceData};
+
+use crate::saving_automata_funcs::{retrieve_workspace_data, save_automata_to_target, update_default_connection_character,
+update_workspace_alphabet, WorkspaceTarget};
+mod tests;
+
+// Symbols are edited by name, and a symbol counts as part of the workspace if it is either in the alphabet or labels
+// a transition, so symbols which were drawn but never added to the alphabet can be renamed and removed as well
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub enum AlphabetEdit {
+  Add { symbol: String },
+  Rename { symbol: String, new_symbol: String },
+  Remove { symbol: String }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum AlphabetEditError {
+  UnknownSymbol(String),
+  DuplicateSymbol(String),
+  InvalidSymbol(String)
+}
+
+// What an edit did, or would do if it has not been applied yet. Connections are the arrows on the canvas, each of
+// which may carry several symbols, while transitions are counted once per symbol and state they lead to
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct AlphabetChangeReport {
+  pub alphabet: Vec<String>,
+  pub added_symbols: Vec<String>,
+  pub removed_symbols: Vec<String>,
+  pub renamed_symbols: Vec<(String, String)>,
+  pub relabelled_connections: usize,
+  pub removed_connections: usize,
+  pub removed_transitions: usize,
+  pub is_applied: bool
+}
+
+// The workspace the edit gave, which is left out while a removal waits to be confirmed
+#[derive(Debug, Serialize)]
+pub struct AlphabetEditResult {
+  pub report: AlphabetChangeReport,
+  pub workspace_data: Option<WorkspaceData>
+}
+
+// Removing a symbol which labels any transition is only saved once it is confirmed. Until then nothing is saved and the
+// report says what would be removed, so it can be shown to the user before calling this again with is_confirmed set.
+// Previews never save anything, so they are given without waiting for confirmation
+#[tauri::command]
+pub fn edit_workspace_alphabet(
+  workspace_name: &str,
+  email: &str,
+  edit: AlphabetEdit,
+  is_confirmed: bool,
+  target: WorkspaceTarget
+) -> Result<AlphabetEditResult, AlphabetEditError> {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+  let mut state_positions = workspace_data.get_state_positions().to_owned();
+  let mut connections = workspace_data.get_list_of_connections().to_owned();
+
+  let mut report = apply_alphabet_edit(workspace_data.get_alphabet(), &mut state_positions, &mut connections, &edit)?;
+
+  if report.removed_transitions > 0 && !is_confirmed && target != WorkspaceTarget::Preview {
+    return Ok(AlphabetEditResult { report, workspace_data: None });
+  }
+
+  // New connections are drawn with the default character, so it follows the symbol it was renamed to
+  if let (AlphabetEdit::Rename { symbol, new_symbol }, WorkspaceTarget::Overwrite) = (&edit, &target) {
+    if workspace_data.get_default_connection_character() == symbol {
+      update_default_connection_character(workspace_name, email, new_symbol.to_owned());
+    }
+  }
+
+  report.is_applied = target != WorkspaceTarget::Preview;
+
+  let workspace_data = save_automata_to_target(
+    workspace_name,
+    email,
+    target,
+    state_positions,
+    connections,
+    None,
+    Some(report.alphabet.to_owned())
+  );
+
+  Ok(AlphabetEditResult { report, workspace_data: Some(workspace_data) })
+
+}
+
+// Replaces the alphabet with the symbols the transitions actually use, leaving the transitions themselves untouched
+#[tauri::command]
+pub fn infer_workspace_alphabet(workspace_name: &str, email: &str) -> AlphabetChangeReport {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+
+  let mut report = infer_alphabet(workspace_data.get_alphabet(), workspace_data.get_state_positions());
+
+  update_workspace_alphabet(workspace_name, email, report.alphabet.iter().map(|symbol| symbol.as_str()).collect());
+
+  report.is_applied = true;
+  report
+
+}
+
+pub fn apply_alphabet_edit(
+  alphabet: &[String],
+  state_positions: &mut HashMap<String, State>,
+  connections: &mut Vec<Connection>,
+  edit: &AlphabetEdit
+) -> Result<AlphabetChangeReport, AlphabetEditError> {
+
+  let used_symbols = get_alphabet_from_state_positions(state_positions);
+  let is_known_symbol = |symbol: &String| alphabet.contains(symbol) || used_symbols.contains(symbol);
+
+  let mut report = AlphabetChangeReport::default();
+
+  match edit {
+    AlphabetEdit::Add { symbol } => {
+      validate_symbol(symbol)?;
+      if alphabet.contains(symbol) {
+        return Err(AlphabetEditError::DuplicateSymbol(symbol.to_owned()));
+      }
+
+      report.alphabet = alphabet.to_vec();
+      report.alphabet.push(symbol.to_owned());
+      report.added_symbols.push(symbol.to_owned());
+    },
+    AlphabetEdit::Rename { symbol, new_symbol } => {
+      if !is_known_symbol(symbol) {
+        return Err(AlphabetEditError::UnknownSymbol(symbol.to_owned()));
+      }
+      validate_symbol(new_symbol)?;
+      // Renaming onto a symbol already in use would merge the two, which is a removal in disguise
+      if is_known_symbol(new_symbol) {
+        return Err(AlphabetEditError::DuplicateSymbol(new_symbol.to_owned()));
+      }
+
+      report.alphabet = alphabet
+        .iter()
+        .map(|other_symbol| if other_symbol == symbol { new_symbol.to_owned() } else { other_symbol.to_owned() })
+        .collect();
+      report.renamed_symbols.push((symbol.to_owned(), new_symbol.to_owned()));
+
+      for state in state_positions.values_mut() {
+        if let Some(connected_state_keys) = state.get_all_connections_mut().remove(symbol) {
+          state.get_all_connections_mut().insert(new_symbol.to_owned(), connected_state_keys);
+        }
+      }
+
+      for connection in connections.iter_mut() {
+        let connection_characters = split_connection_label(&connection.connection_character);
+        if !connection_characters.contains(symbol) {
+          continue;
+        }
+
+        let renamed_connection_characters: Vec<&str> = connection_characters
+          .iter()
+          .map(|connection_character| if connection_character == symbol { new_symbol.as_str() } else { connection_character.as_str() })
+          .collect();
+        connection.connection_character = join_connection_characters(renamed_connection_characters.into_iter());
+        report.relabelled_connections += 1;
+      }
+    },
+    AlphabetEdit::Remove { symbol } => {
+      if !is_known_symbol(symbol) {
+        return Err(AlphabetEditError::UnknownSymbol(symbol.to_owned()));
+      }
+
+      report.alphabet = alphabet
+        .iter()
+        .filter(|other_symbol| *other_symbol != symbol)
+        .cloned()
+        .collect();
+      report.removed_symbols.push(symbol.to_owned());
+
+      for state in state_positions.values_mut() {
+        if let Some(connected_state_keys) = state.get_all_connections_mut().remove(symbol) {
+          report.removed_transitions += connected_state_keys.len();
+        }
+      }
+
+      // Connections carrying other symbols as well only lose this one, and are removed once they carry nothing
+      connections.retain_mut(|connection| {
+        let connection_characters = split_connection_label(&connection.connection_character);
+        if !connection_characters.contains(symbol) {
+          return true;
+        }
+
+        let remaining_connection_characters: Vec<&str> = connection_characters
+          .iter()
+          .filter(|connection_character| *connection_character != symbol)
+          .map(|connection_character| connection_character.as_str())
+          .collect();
+
+        if remaining_connection_characters.is_empty() {
+          report.removed_connections += 1;
+          return false;
+        }
+
+        connection.connection_character = join_connection_characters(remaining_connection_characters.into_iter());
+        report.relabelled_connections += 1;
+        true
+      });
+    }
+  }
+
+  Ok(report)
+
+}
+
+// Character classes and [*] stand for many symbols rather than being one, so they are left out along with ϵ.
+// Symbols the transitions use are kept in the order they already had in the alphabet, with new ones sorted after them
+pub fn infer_alphabet(alphabet: &[String], state_positions: &HashMap<String, State>) -> AlphabetChangeReport {
+
+  let used_symbols: BTreeSet<String> = get_alphabet_from_state_positions(state_positions)
+    .into_iter()
+    .filter(|symbol| !is_symbolic_label(symbol))
+    .collect();
+
+  let (kept_symbols, removed_symbols): (Vec<String>, Vec<String>) = alphabet
+    .iter()
+    .cloned()
+    .partition(|symbol| used_symbols.contains(symbol));
+
+  let added_symbols: Vec<String> = used_symbols
+    .into_iter()
+    .filter(|symbol| !alphabet.contains(symbol))
+    .collect();
+
+  AlphabetChangeReport {
+    alphabet: kept_symbols.into_iter().chain(added_symbols.iter().cloned()).collect(),
+    added_symbols,
+    removed_symbols,
+    ..Default::default()
+  }
+
+}
+
+fn validate_symbol(symbol: &str) -> Result<(), AlphabetEditError> {
+
+  if sanitize_input_alphabet(vec![symbol]).is_empty() || is_symbolic_label(symbol) {
+    return Err(AlphabetEditError::InvalidSymbol(symbol.to_owned()));
+  }
+
+  Ok(())
+
+}
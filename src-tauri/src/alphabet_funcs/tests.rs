@@ -0,0 +1,127 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::alphabet_funcs::{apply_alphabet_edit, infer_alphabet, AlphabetEdit, AlphabetEditError};
+  use app::create_connections_from_state_positions;
+  use app::models::{Connection, Coordinate, State};
+  use std::collections::{BTreeSet, HashMap};
+
+  fn symbols(symbols: &[&str]) -> Vec<String> {
+    symbols.iter().map(|symbol| symbol.to_string()).collect()
+  }
+
+  // q0 reads a or b into q1, which loops on b and reads [0-9] back to q0
+  fn create_state_positions() -> HashMap<String, State> {
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection("a", "500,300");
+    q0.add_connection("b", "500,300");
+    q1.add_connection("b", "500,300");
+    q1.add_connection("[0-9]", "300,300");
+
+    HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("500,300"), q1)
+    ])
+  }
+
+  fn labels_of(connections: &[Connection]) -> BTreeSet<&str> {
+    connections.iter().map(|connection| connection.connection_character.as_str()).collect()
+  }
+
+  #[test]
+  fn test_renaming_symbols() {
+    let mut state_positions = create_state_positions();
+    let mut connections = create_connections_from_state_positions(&state_positions);
+
+    let report = apply_alphabet_edit(
+      &symbols(&["a", "b"]),
+      &mut state_positions,
+      &mut connections,
+      &AlphabetEdit::Rename { symbol: String::from("b"), new_symbol: String::from("then") }
+    ).unwrap();
+
+    assert_eq!(report.alphabet, symbols(&["a", "then"]));
+    assert_eq!(report.renamed_symbols, [(String::from("b"), String::from("then"))]);
+    assert_eq!(report.relabelled_connections, 2);
+    assert_eq!(report.removed_transitions, 0);
+    assert!(!report.is_applied);
+
+    assert_eq!(labels_of(&connections), BTreeSet::from(["a,then", "then", "[0-9]"]));
+    assert!(state_positions["300,300"].get_connections_by_character("b").is_none());
+    assert_eq!(state_positions["500,300"].get_first_connected_state_key_by_character("then").as_deref(), Some("500,300"));
+  }
+
+  #[test]
+  fn test_removing_symbols() {
+    let mut state_positions = create_state_positions();
+    let mut connections = create_connections_from_state_positions(&state_positions);
+
+    let report = apply_alphabet_edit(
+      &symbols(&["a", "b"]),
+      &mut state_positions,
+      &mut connections,
+      &AlphabetEdit::Remove { symbol: String::from("b") }
+    ).unwrap();
+
+    assert_eq!(report.alphabet, symbols(&["a"]));
+    assert_eq!(report.removed_symbols, symbols(&["b"]));
+    // The a,b connection keeps its a while the loop on b goes altogether
+    assert_eq!(report.relabelled_connections, 1);
+    assert_eq!(report.removed_connections, 1);
+    assert_eq!(report.removed_transitions, 2);
+
+    assert_eq!(labels_of(&connections), BTreeSet::from(["a", "[0-9]"]));
+    assert!(state_positions.values().all(|state| state.get_connections_by_character("b").is_none()));
+
+    // Symbols in the alphabet which no transition uses are removed without touching anything else
+    let report = apply_alphabet_edit(
+      &symbols(&["a", "c"]),
+      &mut state_positions,
+      &mut connections,
+      &AlphabetEdit::Remove { symbol: String::from("c") }
+    ).unwrap();
+    assert_eq!(report.alphabet, symbols(&["a"]));
+    assert_eq!(report.removed_transitions, 0);
+  }
+
+  #[test]
+  fn test_alphabet_edit_errors() {
+    let alphabet = symbols(&["a", "b"]);
+    let mut state_positions = create_state_positions();
+    let mut connections = create_connections_from_state_positions(&state_positions);
+
+    let mut apply = |edit: AlphabetEdit| apply_alphabet_edit(&alphabet, &mut state_positions, &mut connections, &edit);
+
+    assert_eq!(apply(AlphabetEdit::Add { symbol: String::from("if") }).unwrap().alphabet, symbols(&["a", "b", "if"]));
+    assert_eq!(apply(AlphabetEdit::Add { symbol: String::from("a") }), Err(AlphabetEditError::DuplicateSymbol(String::from("a"))));
+    for invalid_symbol in ["", "ϵ", "[a-z]", "[*]"] {
+      assert_eq!(
+        apply(AlphabetEdit::Add { symbol: invalid_symbol.to_owned() }),
+        Err(AlphabetEditError::InvalidSymbol(invalid_symbol.to_owned()))
+      );
+    }
+
+    assert_eq!(
+      apply(AlphabetEdit::Rename { symbol: String::from("c"), new_symbol: String::from("d") }),
+      Err(AlphabetEditError::UnknownSymbol(String::from("c")))
+    );
+    assert_eq!(
+      apply(AlphabetEdit::Rename { symbol: String::from("a"), new_symbol: String::from("b") }),
+      Err(AlphabetEditError::DuplicateSymbol(String::from("b")))
+    );
+    assert_eq!(apply(AlphabetEdit::Remove { symbol: String::from("c") }), Err(AlphabetEditError::UnknownSymbol(String::from("c"))));
+  }
+
+  #[test]
+  fn test_inferring_alphabet() {
+    let report = infer_alphabet(&symbols(&["b", "z", "a"]), &create_state_positions());
+    assert_eq!(report.alphabet, symbols(&["b", "a"]));
+    assert_eq!(report.removed_symbols, symbols(&["z"]));
+    assert!(report.added_symbols.is_empty());
+
+    let report = infer_alphabet(&[], &create_state_positions());
+    assert_eq!(report.alphabet, symbols(&["a", "b"]));
+    assert_eq!(report.added_symbols, symbols(&["a", "b"]));
+  }
+
+}
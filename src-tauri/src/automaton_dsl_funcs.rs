@@ -0,0 +1,544 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use serde::Serialize;
+
+use app::{create_connections_from_state_positions, create_state_names, create_state_numbers, create_unique_state_coordinates,
+get_alphabet_from_state_positions, get_type_of_automata_from_state_positions};
+use app::layout::layout_state_positions;
+use app::models::{Connection, Coordinate, State};
+
+use crate::saving_automata_funcs::{create_workspace_with_automata, retrieve_workspace_data};
+mod tests;
+
+// A small language for typing automata by hand, for example:
+//
+// start q0
+// final q2
+// q0 -a-> q1; q1 -a,b-> q2
+// q2 -ε-> q0   # comments run to the end of the line
+//
+// Statements are separated by semicolons or new lines, and may be one of
+//   start q0, q1        marks states as start states
+//   final q2            marks states as final
+//   state q3 at 500,300 declares a state, optionally giving its position on the canvas
+//   q0 -a,b-> q1 -c-> q2 adds transitions, where an empty label such as q0 --> q1, or ϵ, ε or λ, is an ϵ transition
+//
+// States are created the first time they are mentioned. Names are made of letters, digits and underscores,
+// and any other name or character can be written in double quotes, as in "{q0,q1}" -","-> q1.
+// If no state is given a position the automaton is laid out in columns from its start state, otherwise states without
+// a position are placed on a grid once every other state has been placed
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum AutomatonDslError {
+  // Columns are counted in characters from 1, as they would be shown in an editor
+  UnexpectedToken { line: usize, column: usize, found: String, expected: String },
+  UnterminatedString { line: usize, column: usize },
+  ConflictingPosition { line: usize, state_name: String },
+  PositionAlreadyTaken { line: usize, state_name: String, other_state_name: String }
+}
+
+#[tauri::command]
+pub fn parse_automaton_dsl(dsl: &str) -> Result<(HashMap<String, State>, Vec<Connection>), AutomatonDslError> {
+
+  let state_positions = convert_automaton_dsl_to_state_positions(dsl)?;
+  let connections = create_connections_from_state_positions(&state_positions);
+
+  Ok((state_positions, connections))
+
+}
+
+// Returns the name the workspace was created under, as a number is appended if the name is already taken
+#[tauri::command]
+pub fn create_workspace_from_automaton_dsl(email: &str, workspace_name: &str, dsl: &str) -> Result<String, AutomatonDslError> {
+
+  let (state_positions, connections) = parse_automaton_dsl(dsl)?;
+  let alphabet = get_alphabet_from_state_positions(&state_positions);
+  let type_of_automata = get_type_of_automata_from_state_positions(&state_positions);
+
+  Ok(create_workspace_with_automata(
+    email,
+    workspace_name,
+    type_of_automata,
+    alphabet.iter().map(|c| c.as_str()).collect(),
+    state_positions,
+    connections
+  ))
+
+}
+
+#[tauri::command]
+pub fn export_workspace_to_automaton_dsl(workspace_name: &str, email: &str, should_include_positions: bool) -> String {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+
+  convert_state_positions_to_automaton_dsl(workspace_data.get_state_positions(), should_include_positions)
+
+}
+
+// Transitions between the same pair of states are merged onto one line, and states which have no transitions are
+// declared on their own so they are not lost. Including positions means parsing the output gives back exactly the same state keys
+pub fn convert_state_positions_to_automaton_dsl(state_positions: &HashMap<String, State>, should_include_positions: bool) -> String {
+
+  let state_numbers = create_state_numbers(state_positions);
+  let state_names: BTreeMap<String, String> = create_state_names(state_positions)
+    .into_iter()
+    .map(|(state_key, state_name)| (state_key, format_state_name(&state_name)))
+    .collect();
+
+  let mut states_in_name_order: Vec<(&String, &State)> = state_positions.iter().collect();
+  states_in_name_order.sort_by_key(|(state_key, _)| state_numbers[*state_key]);
+
+  let mut dsl = String::new();
+
+  let start_state_names: Vec<&str> = states_in_name_order
+    .iter()
+    .filter(|(_, state)| state.is_start())
+    .map(|(state_key, _)| state_names[*state_key].as_str())
+    .collect();
+
+  let final_state_names: Vec<&str> = states_in_name_order
+    .iter()
+    .filter(|(_, state)| state.is_final())
+    .map(|(state_key, _)| state_names[*state_key].as_str())
+    .collect();
+
+  if !start_state_names.is_empty() {
+    dsl += &format!("start {}\n", start_state_names.join(", "));
+  }
+
+  if !final_state_names.is_empty() {
+    dsl += &format!("final {}\n", final_state_names.join(", "));
+  }
+
+  let connected_state_keys: HashSet<&String> = state_positions
+    .iter()
+    .filter(|(_, state)| !state.get_all_connected_state_keys().is_empty())
+    .map(|(state_key, _)| state_key)
+    .chain(state_positions.values().flat_map(|state| state.get_all_connected_state_keys()))
+    .collect();
+
+  for (state_key, state) in &states_in_name_order {
+    let position = state.get_position();
+
+    if should_include_positions {
+      dsl += &format!("state {} at {},{}\n", state_names[*state_key], position.x, position.y);
+    } else if !connected_state_keys.contains(state_key) && !state.is_start() && !state.is_final() {
+      dsl += &format!("state {}\n", state_names[*state_key]);
+    }
+  }
+
+  for (state_key, state) in &states_in_name_order {
+    let mut characters_by_connected_state_name: BTreeMap<(usize, &String), BTreeSet<&String>> = BTreeMap::new();
+
+    for (connection_character, connected_state_keys) in state.get_all_connections() {
+      for connected_state_key in connected_state_keys {
+        if let Some(connected_state_name) = state_names.get(connected_state_key) {
+          characters_by_connected_state_name
+            .entry((state_numbers[connected_state_key], connected_state_name))
+            .or_default()
+            .insert(connection_character);
+        }
+      }
+    }
+
+    for ((_, connected_state_name), connection_characters) in characters_by_connected_state_name {
+      let label = connection_characters
+        .into_iter()
+        .map(|connection_character| format_label(connection_character))
+        .collect::<Vec<String>>()
+        .join(",");
+
+      dsl += &format!("{} -{label}-> {connected_state_name}\n", state_names[*state_key]);
+    }
+  }
+
+  dsl
+
+}
+
+// Names which would otherwise be read as a keyword are quoted too, along with any the tokenizer would split
+fn format_state_name(state_name: &str) -> String {
+  match state_name {
+    "start" | "final" | "state" | "at" => format!("\"{state_name}\""),
+    _ => format_label(state_name)
+  }
+}
+
+// Characters which the tokenizer would otherwise split on are written in double quotes
+fn format_label(connection_character: &str) -> String {
+  let needs_quotes = connection_character.is_empty() || connection_character
+    .chars()
+    .any(|c| c.is_whitespace() || matches!(c, ';' | ',' | '"' | '-' | '>' | '#' | '/' | '\\'));
+
+  if needs_quotes {
+    format!("\"{}\"", connection_character.replace('\\', "\\\\").replace('"', "\\\""))
+  } else {
+    connection_character.to_owned()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DslToken {
+  Word(String),
+  // Quoted strings are kept separate so a quoted "start" names a state rather than being a keyword
+  QuotedString(String),
+  Symbol(&'static str),
+  EndOfStatement
+}
+
+impl DslToken {
+
+  fn to_display_string(&self) -> String {
+    match self {
+      DslToken::Word(word) => format!("'{word}'"),
+      DslToken::QuotedString(string) => format!("\"{string}\""),
+      DslToken::Symbol(symbol) => format!("'{symbol}'"),
+      DslToken::EndOfStatement => String::from("the end of the statement")
+    }
+  }
+
+}
+
+// Each token is paired with its line and column
+type PositionedToken = (DslToken, usize, usize);
+
+fn tokenize_automaton_dsl(dsl: &str) -> Result<Vec<PositionedToken>, AutomatonDslError> {
+
+  let mut tokens = vec![];
+
+  for (line_index, line) in dsl.lines().enumerate() {
+    let line_number = line_index + 1;
+    let characters: Vec<char> = line.chars().collect();
+    let mut index = 0;
+
+    while index < characters.len() {
+      let c = characters[index];
+      let column = index + 1;
+
+      match c {
+        c if c.is_whitespace() => index += 1,
+        '#' => break,
+        '/' if characters.get(index + 1) == Some(&'/') => break,
+        ';' => {
+          tokens.push((DslToken::EndOfStatement, line_number, column));
+          index += 1;
+        },
+        ',' => {
+          tokens.push((DslToken::Symbol(","), line_number, column));
+          index += 1;
+        },
+        '-' if characters.get(index + 1) == Some(&'>') => {
+          tokens.push((DslToken::Symbol("->"), line_number, column));
+          index += 2;
+        },
+        '-' => {
+          tokens.push((DslToken::Symbol("-"), line_number, column));
+          index += 1;
+        },
+        '"' => {
+          let mut string = String::new();
+          index += 1;
+
+          loop {
+            match characters.get(index) {
+              None => return Err(AutomatonDslError::UnterminatedString { line: line_number, column }),
+              Some('"') => break,
+              Some('\\') if characters.get(index + 1).is_some() => {
+                string.push(characters[index + 1]);
+                index += 1;
+              },
+              Some(c) => string.push(*c)
+            }
+            index += 1;
+          }
+
+          index += 1;
+          tokens.push((DslToken::QuotedString(string), line_number, column));
+        },
+        _ => {
+          let mut word = String::new();
+
+          while index < characters.len() && !characters[index].is_whitespace() && !matches!(characters[index], ';' | ',' | '"' | '-' | '>' | '#') {
+            word.push(characters[index]);
+            index += 1;
+          }
+
+          if word.is_empty() {
+            return Err(AutomatonDslError::UnexpectedToken {
+              line: line_number,
+              column,
+              found: format!("'{c}'"),
+              expected: String::from("a state name, keyword or transition")
+            });
+          }
+
+          tokens.push((DslToken::Word(word), line_number, column));
+        }
+      }
+    }
+
+    tokens.push((DslToken::EndOfStatement, line_number, characters.len() + 1));
+  }
+
+  Ok(tokens)
+
+}
+
+#[derive(Default)]
+struct DslState {
+  is_start: bool,
+  is_final: bool,
+  // The position given by a state statement, along with the line it was given on
+  position: Option<(Coordinate, usize)>
+}
+
+struct DslParser {
+  tokens: Vec<PositionedToken>,
+  index: usize,
+  // Kept in the order states are first mentioned, so the same text always places states in the same positions
+  state_names: Vec<String>,
+  states: HashMap<String, DslState>,
+  transitions: Vec<(String, String, String)>
+}
+
+impl DslParser {
+
+  fn peek(&self) -> &DslToken {
+    &self.tokens[self.index].0
+  }
+
+  fn unexpected_token(&self, expected: &str) -> AutomatonDslError {
+    let (token, line, column) = &self.tokens[self.index];
+    AutomatonDslError::UnexpectedToken {
+      line: *line,
+      column: *column,
+      found: token.to_display_string(),
+      expected: expected.to_owned()
+    }
+  }
+
+  fn get_line(&self) -> usize {
+    self.tokens[self.index].1
+  }
+
+  fn add_state(&mut self, state_name: &str) {
+    if !self.states.contains_key(state_name) {
+      self.state_names.push(state_name.to_owned());
+      self.states.insert(state_name.to_owned(), DslState::default());
+    }
+  }
+
+  fn next_state_name(&mut self) -> Result<String, AutomatonDslError> {
+    match self.peek().to_owned() {
+      DslToken::Word(state_name) | DslToken::QuotedString(state_name) => {
+        self.index += 1;
+        self.add_state(&state_name);
+        Ok(state_name)
+      },
+      _ => Err(self.unexpected_token("a state name"))
+    }
+  }
+
+  fn next_integer(&mut self) -> Result<i32, AutomatonDslError> {
+    let is_negative = self.peek() == &DslToken::Symbol("-");
+    if is_negative {
+      self.index += 1;
+    }
+
+    let integer = match self.peek() {
+      DslToken::Word(word) => word.parse::<i32>().ok(),
+      _ => None
+    }.ok_or_else(|| self.unexpected_token("a whole number"))?;
+
+    self.index += 1;
+    Ok(if is_negative { -integer } else { integer })
+  }
+
+  fn parse(&mut self) -> Result<(), AutomatonDslError> {
+    while self.index < self.tokens.len() {
+      if self.peek() != &DslToken::EndOfStatement {
+        self.parse_statement()?;
+      }
+
+      if self.peek() != &DslToken::EndOfStatement {
+        return Err(self.unexpected_token("';' or a new line"));
+      }
+      self.index += 1;
+    }
+
+    Ok(())
+  }
+
+  fn parse_statement(&mut self) -> Result<(), AutomatonDslError> {
+
+    // A state may share its name with a keyword, as in start -a-> q1, so keywords are only recognised when not followed by a transition
+    let keyword = match (self.peek(), &self.tokens[self.index + 1].0) {
+      (DslToken::Word(word), next_token) if next_token != &DslToken::Symbol("-") => Some(word.to_owned()),
+      _ => None
+    };
+
+    match keyword.as_deref() {
+      Some("start") | Some("final") => {
+        self.index += 1;
+
+        loop {
+          let state_name = self.next_state_name()?;
+          let state = self.states.get_mut(&state_name).expect("The state should have just been added");
+
+          if keyword.as_deref() == Some("start") {
+            state.is_start = true;
+          } else {
+            state.is_final = true;
+          }
+
+          if self.peek() != &DslToken::Symbol(",") {
+            return Ok(());
+          }
+          self.index += 1;
+        }
+      },
+      Some("state") => {
+        self.index += 1;
+        let state_name = self.next_state_name()?;
+
+        if self.peek() == &DslToken::Word(String::from("at")) {
+          let line = self.get_line();
+          self.index += 1;
+
+          let x = self.next_integer()?;
+          if self.peek() != &DslToken::Symbol(",") {
+            return Err(self.unexpected_token("','"));
+          }
+          self.index += 1;
+          let y = self.next_integer()?;
+
+          let state = self.states.get_mut(&state_name).expect("The state should have just been added");
+          match state.position {
+            Some((position, _)) if position != (Coordinate { x, y }) => {
+              return Err(AutomatonDslError::ConflictingPosition { line, state_name });
+            },
+            _ => state.position = Some((Coordinate { x, y }, line))
+          }
+        }
+
+        Ok(())
+      },
+      _ => {
+        let mut state_name = self.next_state_name()?;
+
+        if self.peek() != &DslToken::Symbol("-") {
+          return Err(self.unexpected_token("a transition such as -a->"));
+        }
+
+        // Transitions may be chained, as in q0 -a-> q1 -b-> q2
+        while self.peek() == &DslToken::Symbol("-") {
+          self.index += 1;
+          let mut connection_characters = vec![];
+
+          while self.peek() != &DslToken::Symbol("->") {
+            match self.peek().to_owned() {
+              DslToken::Word(connection_character) | DslToken::QuotedString(connection_character) => {
+                connection_characters.push(connection_character);
+                self.index += 1;
+              },
+              _ => return Err(self.unexpected_token("a character or '->'"))
+            }
+
+            match self.peek() {
+              DslToken::Symbol(",") => self.index += 1,
+              DslToken::Symbol("->") => (),
+              _ => return Err(self.unexpected_token("',' or '->'"))
+            }
+          }
+          self.index += 1;
+
+          if connection_characters.is_empty() {
+            connection_characters.push(String::from("ϵ"));
+          }
+
+          let connected_state_name = self.next_state_name()?;
+
+          for connection_character in connection_characters {
+            let connection_character = match connection_character.as_str() {
+              "ϵ" | "ε" | "λ" => String::from("ϵ"),
+              _ => connection_character
+            };
+            self.transitions.push((state_name.to_owned(), connection_character, connected_state_name.to_owned()));
+          }
+
+          state_name = connected_state_name;
+        }
+
+        Ok(())
+      }
+    }
+
+  }
+
+}
+
+pub fn convert_automaton_dsl_to_state_positions(dsl: &str) -> Result<HashMap<String, State>, AutomatonDslError> {
+
+  let mut parser = DslParser {
+    tokens: tokenize_automaton_dsl(dsl)?,
+    index: 0,
+    state_names: vec![],
+    states: HashMap::new(),
+    transitions: vec![]
+  };
+  parser.parse()?;
+
+  let mut state_keys_by_name: HashMap<&String, String> = HashMap::new();
+  let mut state_names_by_key: HashMap<String, &String> = HashMap::new();
+
+  // States with a given position are placed first, so the grid used for the others can avoid them
+  for state_name in &parser.state_names {
+    if let Some((position, line)) = parser.states[state_name].position {
+      let state_key: String = position.into();
+
+      if let Some(other_state_name) = state_names_by_key.get(&state_key) {
+        return Err(AutomatonDslError::PositionAlreadyTaken {
+          line,
+          state_name: state_name.to_owned(),
+          other_state_name: other_state_name.to_string()
+        });
+      }
+
+      state_names_by_key.insert(state_key.to_owned(), state_name);
+      state_keys_by_name.insert(state_name, state_key);
+    }
+  }
+
+  for state_name in &parser.state_names {
+    if !state_keys_by_name.contains_key(state_name) {
+      let state_key: String = create_unique_state_coordinates(&state_names_by_key.keys().cloned().collect()).into();
+      state_names_by_key.insert(state_key.to_owned(), state_name);
+      state_keys_by_name.insert(state_name, state_key);
+    }
+  }
+
+  let mut state_positions: HashMap<String, State> = parser.state_names
+    .iter()
+    .map(|state_name| {
+      let state_key = &state_keys_by_name[state_name];
+      let state = &parser.states[state_name];
+      let coordinate: Coordinate = state_key.try_into().expect("Every state key should be a valid coordinate");
+      let mut new_state = State::new(coordinate, state.is_start, state.is_final);
+      new_state.set_name(Some(state_name.to_owned()));
+      (state_key.to_owned(), new_state)
+    })
+    .collect();
+
+  for (state_name, connection_character, connected_state_name) in &parser.transitions {
+    state_positions
+      .get_mut(&state_keys_by_name[state_name])
+      .expect("Every state name should correspond to a created state")
+      .add_connection(connection_character, state_keys_by_name[connected_state_name].to_owned());
+  }
+
+  if parser.states.values().all(|state| state.position.is_none()) {
+    return Ok(layout_state_positions(&state_positions));
+  }
+
+  Ok(state_positions)
+
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::automaton_dsl_funcs::{convert_automaton_dsl_to_state_positions, convert_state_positions_to_automaton_dsl,
+  AutomatonDslError};
+  use crate::regular_expression_funcs::convert_regex_to_state_positions;
+  use crate::testing_automata_funcs::{test_string_dfa, test_string_nfa};
+  use crate::test_fixtures::get_start_state_key;
+
+  #[test]
+  fn test_automaton_dsl_parsing() {
+    let state_positions = convert_automaton_dsl_to_state_positions("start q0; final q2; q0 -a-> q1; q1 -a,b-> q2; q2 -ε-> q0").unwrap();
+    let start_state_key = get_start_state_key(&state_positions);
+
+    // Without any positions, states are laid out in columns by their distance from the start state
+    let find_state_key_at = |position: &str| state_positions
+      .iter()
+      .find(|(_, state)| state.get_position_as_string() == position)
+      .map(|(state_key, _)| state_key.to_owned())
+      .unwrap();
+    let (q0, q1, q2) = (find_state_key_at("300,300"), find_state_key_at("500,300"), find_state_key_at("700,300"));
+
+    assert_eq!(start_state_key, q0);
+    assert!(state_positions[&q2].is_final());
+    assert_eq!(state_positions[&q2].get_first_connected_state_key_by_character("ϵ"), Some(q0));
+    assert_eq!(state_positions[&q1].get_first_connected_state_key_by_character("b"), Some(q2));
+
+    for (string_to_check, expected) in [("aa", true), ("ab", true), ("abaa", true), ("a", false), ("b", false)] {
+      let (is_accepted, _) = test_string_nfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned());
+      assert_eq!(is_accepted, expected, "Failed on {string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_automaton_dsl_extended_syntax() {
+    let dsl = "
+      # states can be given positions, and the rest fill in the grid around them
+      state start at 300,300
+      state \"{q0,q1}\" at -100, 500
+      start start
+      final \"{q0,q1}\", end   // several states can be marked at once
+      start -a-> \"{q0,q1}\" -\",\",b-> end --> start
+      lonely
+    ";
+    let state_positions = convert_automaton_dsl_to_state_positions(dsl).unwrap_err();
+    assert_eq!(state_positions, AutomatonDslError::UnexpectedToken {
+      line: 8,
+      column: 13,
+      found: String::from("the end of the statement"),
+      expected: String::from("a transition such as -a->")
+    });
+
+    let state_positions = convert_automaton_dsl_to_state_positions(&dsl.replace("lonely", "state lonely")).unwrap();
+
+    assert_eq!(state_positions.len(), 4);
+    assert!(state_positions["300,300"].is_start());
+    assert!(state_positions["-100,500"].is_final());
+    // The grid skips the position already taken by start
+    assert!(state_positions["500,300"].is_final());
+    assert!(state_positions["700,300"].get_all_connections().is_empty());
+
+    assert_eq!(state_positions["300,300"].get_first_connected_state_key_by_character("a"), Some(String::from("-100,500")));
+    assert_eq!(state_positions["-100,500"].get_first_connected_state_key_by_character(","), Some(String::from("500,300")));
+    assert_eq!(state_positions["-100,500"].get_first_connected_state_key_by_character("b"), Some(String::from("500,300")));
+    assert_eq!(state_positions["500,300"].get_first_connected_state_key_by_character("ϵ"), Some(String::from("300,300")));
+  }
+
+  #[test]
+  fn test_automaton_dsl_errors() {
+    assert_eq!(
+      convert_automaton_dsl_to_state_positions("start q0\nq0 -a q1").unwrap_err(),
+      AutomatonDslError::UnexpectedToken { line: 2, column: 7, found: String::from("'q1'"), expected: String::from("',' or '->'") }
+    );
+    assert_eq!(
+      convert_automaton_dsl_to_state_positions("q0 -a-> q1 q2").unwrap_err(),
+      AutomatonDslError::UnexpectedToken { line: 1, column: 12, found: String::from("'q2'"), expected: String::from("';' or a new line") }
+    );
+    assert_eq!(
+      convert_automaton_dsl_to_state_positions("final").unwrap_err(),
+      AutomatonDslError::UnexpectedToken { line: 1, column: 6, found: String::from("the end of the statement"), expected: String::from("a state name") }
+    );
+    assert_eq!(
+      convert_automaton_dsl_to_state_positions("\n  q0 -\"a-> q1").unwrap_err(),
+      AutomatonDslError::UnterminatedString { line: 2, column: 7 }
+    );
+    assert_eq!(
+      convert_automaton_dsl_to_state_positions("state q0 at 1,x").unwrap_err(),
+      AutomatonDslError::UnexpectedToken { line: 1, column: 15, found: String::from("'x'"), expected: String::from("a whole number") }
+    );
+    assert_eq!(
+      convert_automaton_dsl_to_state_positions("state q0 at 1,1\nstate q0 at 2,2").unwrap_err(),
+      AutomatonDslError::ConflictingPosition { line: 2, state_name: String::from("q0") }
+    );
+    assert_eq!(
+      convert_automaton_dsl_to_state_positions("state q0 at 1,1\nstate q1 at 1,1").unwrap_err(),
+      AutomatonDslError::PositionAlreadyTaken { line: 2, state_name: String::from("q1"), other_state_name: String::from("q0") }
+    );
+  }
+
+  #[test]
+  fn test_automaton_dsl_pretty_printing() {
+    let state_positions = convert_automaton_dsl_to_state_positions("start q0; final q2; q0 -b,a-> q1; q1 -a-> q2; q2 -\"-\"-> q0; state q3").unwrap();
+
+    assert_eq!(
+      convert_state_positions_to_automaton_dsl(&state_positions, false),
+      "start q0\nfinal q2\nstate q3\nq0 -a,b-> q1\nq1 -a-> q2\nq2 -\"-\"-> q0\n"
+    );
+    assert_eq!(
+      convert_state_positions_to_automaton_dsl(&state_positions, true),
+      "start q0\nfinal q2\nstate q0 at 300,300\nstate q1 at 500,400\nstate q2 at 700,400\nstate q3 at 300,500\nq0 -a,b-> q1\nq1 -a-> q2\nq2 -\"-\"-> q0\n"
+    );
+  }
+
+  #[test]
+  fn test_automaton_dsl_round_trip() {
+    for regex in ["(a+b)*abb", "ab*c+ca"] {
+      let (state_positions, start_state_key) = convert_regex_to_state_positions(regex).unwrap();
+
+      // With positions included the exact same states come back
+      let dsl = convert_state_positions_to_automaton_dsl(&state_positions, true);
+      let reimported_state_positions = convert_automaton_dsl_to_state_positions(&dsl).unwrap();
+
+      assert_eq!(reimported_state_positions.len(), state_positions.len());
+      for (state_key, state) in &state_positions {
+        let reimported_state = &reimported_state_positions[state_key];
+        assert_eq!(reimported_state.is_start(), state.is_start());
+        assert_eq!(reimported_state.is_final(), state.is_final());
+        assert_eq!(reimported_state.get_all_connections(), state.get_all_connections());
+      }
+
+      // Without positions the states move, but the text and language stay the same
+      let dsl = convert_state_positions_to_automaton_dsl(&state_positions, false);
+      let reimported_state_positions = convert_automaton_dsl_to_state_positions(&dsl).unwrap();
+      let reimported_start_state_key = get_start_state_key(&reimported_state_positions);
+
+      assert_eq!(convert_state_positions_to_automaton_dsl(&reimported_state_positions, false), dsl);
+
+      for string_to_check in ["", "a", "ab", "abb", "babb", "ac", "ca", "abbc"] {
+        assert_eq!(
+          test_string_dfa(reimported_state_positions.to_owned(), reimported_start_state_key.to_owned(), string_to_check.to_owned()).0,
+          test_string_dfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned()).0,
+          "Failed on {string_to_check} for {regex}"
+        );
+      }
+    }
+  }
+
+}
@@ -0,0 +1,269 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use app::{create_state_numbers, get_type_of_automata_from_state_positions};
+use app::labels::is_symbolic_label;
+use app::models::{State, TypeOfAutomata};
+
+use crate::saving_automata_funcs::retrieve_workspace_data;
+mod tests;
+
+// Every target reads the input one character (Unicode code point) at a time, as test_string_dfa does for single character symbols,
+// and rejects as soon as a transition is missing. States are numbered in the same order they are named in exports,
+// so state 0 is always the start state
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum CodeGenerationTarget {
+  Rust,
+  C,
+  Python
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum CodeGenerationError {
+  NotADfa,
+  NoStartState,
+  InvalidFunctionName,
+  // Matchers only compare single characters, so classes and [*] have to be replaced by the symbols they stand for first
+  SymbolicLabel(String),
+  // Likewise a symbol such as "if" would have to be read over several characters, which the matchers don't do
+  MultiCharacterLabel(String)
+}
+
+// The workspace should be minimized beforehand if the smallest matcher is wanted, as the states are emitted as they are
+#[tauri::command]
+pub fn generate_matcher_from_workspace(
+  workspace_name: &str,
+  email: &str,
+  target: CodeGenerationTarget,
+  function_name: &str
+) -> Result<String, CodeGenerationError> {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+
+  generate_matcher(workspace_data.get_state_positions(), target, function_name)
+
+}
+
+pub fn generate_matcher(
+  state_positions: &HashMap<String, State>,
+  target: CodeGenerationTarget,
+  function_name: &str
+) -> Result<String, CodeGenerationError> {
+
+  let is_valid_function_name = function_name
+    .chars()
+    .next()
+    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+    && function_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+  if !is_valid_function_name {
+    return Err(CodeGenerationError::InvalidFunctionName);
+  }
+
+  if get_type_of_automata_from_state_positions(state_positions) != TypeOfAutomata::DFA {
+    return Err(CodeGenerationError::NotADfa);
+  }
+
+  if !state_positions.values().any(|state| state.is_start()) {
+    return Err(CodeGenerationError::NoStartState);
+  }
+
+  let symbolic_label = state_positions
+    .values()
+    .flat_map(|state| state.get_all_connections().keys())
+    .filter(|label| is_symbolic_label(label))
+    .min();
+
+  if let Some(symbolic_label) = symbolic_label {
+    return Err(CodeGenerationError::SymbolicLabel(symbolic_label.to_owned()));
+  }
+
+  let multi_character_label = state_positions
+    .values()
+    .flat_map(|state| state.get_all_connections().keys())
+    .filter(|label| label.chars().count() != 1)
+    .min();
+
+  if let Some(multi_character_label) = multi_character_label {
+    return Err(CodeGenerationError::MultiCharacterLabel(multi_character_label.to_owned()));
+  }
+
+  let transitions = create_transition_table(state_positions);
+  let final_states: Vec<usize> = transitions
+    .iter()
+    .enumerate()
+    .filter(|(_, (is_final, _))| *is_final)
+    .map(|(state_number, _)| state_number)
+    .collect();
+
+  Ok(match target {
+    CodeGenerationTarget::Rust => generate_rust_matcher(&transitions, &final_states, function_name),
+    CodeGenerationTarget::C => generate_c_matcher(&transitions, &final_states, function_name),
+    CodeGenerationTarget::Python => generate_python_matcher(&transitions, &final_states, function_name)
+  })
+
+}
+
+// Each state, in order of its number, paired with whether it is final and its transitions in character order.
+// Classes, [*] and symbols of more than one character never reach here, as generate_matcher rejects them
+type TransitionTable = Vec<(bool, BTreeMap<char, usize>)>;
+
+fn create_transition_table(state_positions: &HashMap<String, State>) -> TransitionTable {
+
+  let state_numbers = create_state_numbers(state_positions);
+
+  let mut transitions = vec![(false, BTreeMap::new()); state_positions.len()];
+
+  for (state_key, state) in state_positions {
+    let (is_final, state_transitions) = &mut transitions[state_numbers[state_key]];
+    *is_final = state.is_final();
+
+    for (connection_character, connected_state_keys) in state.get_all_connections() {
+      let c = connection_character
+        .chars()
+        .next()
+        .expect("Every label should be a single character by now");
+
+      if let Some(connected_state_number) = connected_state_keys.iter().find_map(|state_key| state_numbers.get(state_key)) {
+        state_transitions.insert(c, *connected_state_number);
+      }
+    }
+  }
+
+  transitions
+
+}
+
+fn generate_rust_matcher(transitions: &TransitionTable, final_states: &[usize], function_name: &str) -> String {
+
+  let mut code = format!(
+    "// Generated from a DFA with {} states. Returns whether the whole of the input is accepted\npub fn {function_name}(input: &str) -> bool {{\n",
+    transitions.len()
+  );
+
+  if final_states.is_empty() {
+    code += "    let _ = input;\n    false\n}\n";
+    return code;
+  }
+
+  code += "    let mut state: usize = 0;\n\n    for c in input.chars() {\n        state = match (state, c) {\n";
+
+  for (state_number, (_, state_transitions)) in transitions.iter().enumerate() {
+    for (c, connected_state_number) in state_transitions {
+      code += &format!("            ({state_number}, {c:?}) => {connected_state_number},\n");
+    }
+  }
+
+  code += "            _ => return false,\n        };\n    }\n\n";
+  code += &format!(
+    "    matches!(state, {})\n}}\n",
+    final_states.iter().map(|state_number| state_number.to_string()).collect::<Vec<String>>().join(" | ")
+  );
+
+  code
+
+}
+
+fn generate_c_matcher(transitions: &TransitionTable, final_states: &[usize], function_name: &str) -> String {
+
+  let mut code = format!(
+    "#include <stdbool.h>\n\n// Generated from a DFA with {} states. Reads the input as UTF-8 and returns whether the whole of it is accepted\nbool {function_name}(const char *input) {{\n",
+    transitions.len()
+  );
+
+  code += "    const unsigned char *s = (const unsigned char *) input;\n";
+  code += "    int state = 0;\n\n";
+  code += "    while (*s) {\n";
+  code += "        unsigned long c = *s++;\n";
+  code += "        // The lead byte gives the number of continuation bytes, each of which holds six more bits of the code point\n";
+  code += "        int continuation_bytes = c >= 0xF0 ? 3 : c >= 0xE0 ? 2 : c >= 0xC0 ? 1 : 0;\n";
+  code += "        if (continuation_bytes) c &= 0x3F >> continuation_bytes;\n";
+  code += "        while (continuation_bytes-- > 0) {\n";
+  code += "            if ((*s & 0xC0) != 0x80) return false;\n";
+  code += "            c = (c << 6) | (*s++ & 0x3F);\n";
+  code += "        }\n\n";
+  code += "        switch (state) {\n";
+
+  for (state_number, (_, state_transitions)) in transitions.iter().enumerate() {
+    if state_transitions.is_empty() {
+      continue;
+    }
+
+    code += &format!("        case {state_number}:\n            switch (c) {{\n");
+
+    for (c, connected_state_number) in state_transitions {
+      code += &format!("            case 0x{:X}: state = {connected_state_number}; break;", *c as u32);
+      if c.is_ascii_graphic() {
+        code += &format!(" // '{c}'");
+      }
+      code += "\n";
+    }
+
+    code += "            default: return false;\n            }\n            break;\n";
+  }
+
+  code += "        default:\n            return false;\n        }\n    }\n\n";
+
+  if final_states.is_empty() {
+    code += "    return false;\n}\n";
+  } else {
+    code += &format!(
+      "    return {};\n}}\n",
+      final_states.iter().map(|state_number| format!("state == {state_number}")).collect::<Vec<String>>().join(" || ")
+    );
+  }
+
+  code
+
+}
+
+fn generate_python_matcher(transitions: &TransitionTable, final_states: &[usize], function_name: &str) -> String {
+
+  // The tables are prefixed with the function name so several matchers can live in the same module
+  let table_prefix = function_name.to_uppercase();
+
+  let format_character = |c: &char| {
+    if c.is_ascii_graphic() && *c != '"' && *c != '\\' {
+      format!("\"{c}\"")
+    } else {
+      format!("\"\\U{:08x}\"", *c as u32)
+    }
+  };
+
+  let mut code = format!("# Generated from a DFA with {} states\n{table_prefix}_TRANSITIONS = [\n", transitions.len());
+
+  for (_, state_transitions) in transitions {
+    code += &format!(
+      "    {{{}}},\n",
+      state_transitions
+        .iter()
+        .map(|(c, connected_state_number)| format!("{}: {connected_state_number}", format_character(c)))
+        .collect::<Vec<String>>()
+        .join(", ")
+    );
+  }
+
+  code += "]\n";
+
+  if final_states.is_empty() {
+    code += &format!("{table_prefix}_FINAL_STATES = set()\n");
+  } else {
+    code += &format!(
+      "{table_prefix}_FINAL_STATES = {{{}}}\n",
+      final_states.iter().map(|state_number| state_number.to_string()).collect::<Vec<String>>().join(", ")
+    );
+  }
+
+  code += &format!("\n\ndef {function_name}(string):\n");
+  code += "    \"\"\"Returns whether the whole of the string is accepted\"\"\"\n";
+  code += "    state = 0\n";
+  code += "    for c in string:\n";
+  code += &format!("        state = {table_prefix}_TRANSITIONS[state].get(c)\n");
+  code += "        if state is None:\n";
+  code += "            return False\n";
+  code += &format!("    return state in {table_prefix}_FINAL_STATES\n");
+
+  code
+
+}
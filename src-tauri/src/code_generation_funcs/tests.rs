@@ -0,0 +1,182 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::code_generation_funcs::{generate_matcher, CodeGenerationError, CodeGenerationTarget};
+  use crate::regular_expression_funcs::convert_regex_to_state_positions;
+  use crate::testing_automata_funcs::test_string_dfa;
+  use app::models::{Coordinate, State};
+  use std::collections::HashMap;
+  use std::fs;
+  use std::path::PathBuf;
+  use std::process::Command;
+
+  const STRINGS_TO_CHECK: [&str; 12] = ["", "a", "b", "ab", "abb", "babb", "aabb", "ac", "ca", "abbc", "αβ", "αββ"];
+
+  // αβ*, using characters which take more than one byte in UTF-8, with q1 also moving on ' to check it is escaped
+  fn create_greek_dfa() -> (HashMap<String, State>, String) {
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection("α", "500,300");
+    q1.add_connection("β", "500,300");
+    q1.add_connection("'", "300,300");
+
+    (HashMap::from([(String::from("300,300"), q0), (String::from("500,300"), q1)]), String::from("300,300"))
+  }
+
+  fn create_test_automata() -> Vec<(HashMap<String, State>, String)> {
+    let mut automata: Vec<(HashMap<String, State>, String)> = ["(a+b)*abb", "ab*c+ca"]
+      .iter()
+      .map(|regex| convert_regex_to_state_positions(regex).unwrap())
+      .collect();
+    automata.push(create_greek_dfa());
+    automata
+  }
+
+  fn create_temporary_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("inner_machinations_{name}_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    directory
+  }
+
+  // Runs the compiled or interpreted matcher with every string as an argument, and checks it prints the same result test_string_dfa gives
+  fn check_matcher_output(command: &mut Command, state_positions: &HashMap<String, State>, start_state_key: &str) {
+    let output = command.args(STRINGS_TO_CHECK).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let results: Vec<String> = String::from_utf8(output.stdout).unwrap().lines().map(|line| line.to_owned()).collect();
+    assert_eq!(results.len(), STRINGS_TO_CHECK.len());
+
+    for (string_to_check, result) in STRINGS_TO_CHECK.iter().zip(results) {
+      let (is_accepted, _) = test_string_dfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_string());
+      assert_eq!(result == "1", is_accepted, "Failed on {string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_rust_matcher_generation() {
+    let directory = create_temporary_directory("rust_matcher");
+    let rustc = std::env::var("RUSTC").unwrap_or(String::from("rustc"));
+
+    for (index, (state_positions, start_state_key)) in create_test_automata().into_iter().enumerate() {
+      let matcher = generate_matcher(&state_positions, CodeGenerationTarget::Rust, "is_accepted").unwrap();
+      let source_path = directory.join(format!("matcher_{index}.rs"));
+      let binary_path = directory.join(format!("matcher_{index}"));
+
+      fs::write(
+        &source_path,
+        matcher + "\nfn main() {\n    for arg in std::env::args().skip(1) {\n        println!(\"{}\", is_accepted(&arg) as u8);\n    }\n}\n"
+      ).unwrap();
+
+      let compilation = Command::new(&rustc)
+        .args(["--edition", "2021", "-o"])
+        .arg(&binary_path)
+        .arg(&source_path)
+        .output()
+        .unwrap();
+      assert!(compilation.status.success(), "{}", String::from_utf8_lossy(&compilation.stderr));
+
+      check_matcher_output(&mut Command::new(&binary_path), &state_positions, &start_state_key);
+    }
+
+    fs::remove_dir_all(directory).unwrap();
+  }
+
+  // The C and Python matchers are only run if a compiler or interpreter can be found, as neither is needed to build the app
+  #[test]
+  fn test_c_matcher_generation() {
+    let directory = create_temporary_directory("c_matcher");
+
+    for (index, (state_positions, start_state_key)) in create_test_automata().into_iter().enumerate() {
+      let matcher = generate_matcher(&state_positions, CodeGenerationTarget::C, "is_accepted").unwrap();
+      let source_path = directory.join(format!("matcher_{index}.c"));
+      let binary_path = directory.join(format!("matcher_{index}"));
+
+      fs::write(
+        &source_path,
+        String::from("#include <stdio.h>\n") + &matcher
+          + "\nint main(int argc, char **argv) {\n    for (int i = 1; i < argc; i++) printf(\"%d\\n\", is_accepted(argv[i]));\n    return 0;\n}\n"
+      ).unwrap();
+
+      let compilation = match Command::new("cc").arg("-o").arg(&binary_path).arg(&source_path).output() {
+        Ok(compilation) => compilation,
+        Err(_) => break
+      };
+      assert!(compilation.status.success(), "{}", String::from_utf8_lossy(&compilation.stderr));
+
+      check_matcher_output(&mut Command::new(&binary_path), &state_positions, &start_state_key);
+    }
+
+    fs::remove_dir_all(directory).unwrap();
+  }
+
+  #[test]
+  fn test_python_matcher_generation() {
+    if Command::new("python3").arg("--version").output().is_err() {
+      return;
+    }
+
+    for (state_positions, start_state_key) in create_test_automata() {
+      let matcher = generate_matcher(&state_positions, CodeGenerationTarget::Python, "is_accepted").unwrap();
+      let script = matcher + "\n\nimport sys\nfor arg in sys.argv[1:]:\n    print(int(is_accepted(arg)))\n";
+
+      check_matcher_output(Command::new("python3").args(["-c", &script]), &state_positions, &start_state_key);
+    }
+  }
+
+  #[test]
+  fn test_matcher_generation_output() {
+    let (state_positions, _) = create_greek_dfa();
+
+    let expected_rust = [
+      "// Generated from a DFA with 2 states. Returns whether the whole of the input is accepted",
+      "pub fn is_greek(input: &str) -> bool {",
+      "    let mut state: usize = 0;",
+      "",
+      "    for c in input.chars() {",
+      "        state = match (state, c) {",
+      "            (0, 'α') => 1,",
+      "            (1, '\\'') => 0,",
+      "            (1, 'β') => 1,",
+      "            _ => return false,",
+      "        };",
+      "    }",
+      "",
+      "    matches!(state, 1)",
+      "}\n"
+    ].join("\n");
+    assert_eq!(generate_matcher(&state_positions, CodeGenerationTarget::Rust, "is_greek").unwrap(), expected_rust);
+
+    let python = generate_matcher(&state_positions, CodeGenerationTarget::Python, "is_greek").unwrap();
+    assert!(python.contains("IS_GREEK_TRANSITIONS = [\n    {\"\\U000003b1\": 1},\n    {\"'\": 0, \"\\U000003b2\": 1},\n]\nIS_GREEK_FINAL_STATES = {1}\n"));
+  }
+
+  #[test]
+  fn test_matcher_generation_errors() {
+    let (mut state_positions, _) = create_greek_dfa();
+
+    assert_eq!(generate_matcher(&state_positions, CodeGenerationTarget::Rust, "1st").unwrap_err(), CodeGenerationError::InvalidFunctionName);
+    assert_eq!(generate_matcher(&state_positions, CodeGenerationTarget::C, "is-greek").unwrap_err(), CodeGenerationError::InvalidFunctionName);
+
+    state_positions.get_mut("500,300").unwrap().add_connection("ϵ", "300,300");
+    assert_eq!(generate_matcher(&state_positions, CodeGenerationTarget::Python, "is_greek").unwrap_err(), CodeGenerationError::NotADfa);
+
+    let (mut state_positions, _) = create_greek_dfa();
+    state_positions.get_mut("500,300").unwrap().add_connection("[*]", "300,300");
+    state_positions.get_mut("300,300").unwrap().add_connection("[0-9]", "300,300");
+    assert_eq!(
+      generate_matcher(&state_positions, CodeGenerationTarget::C, "is_greek").unwrap_err(),
+      CodeGenerationError::SymbolicLabel(String::from("[*]"))
+    );
+
+    // Symbols of more than one character are rejected rather than left out of the matcher
+    let (mut state_positions, _) = create_greek_dfa();
+    state_positions.get_mut("300,300").unwrap().add_connection("if", "500,300");
+    assert_eq!(
+      generate_matcher(&state_positions, CodeGenerationTarget::Python, "is_greek").unwrap_err(),
+      CodeGenerationError::MultiCharacterLabel(String::from("if"))
+    );
+
+    let state_positions = HashMap::from([(String::from("300,300"), State::new(Coordinate { x: 300, y: 300 }, false, true))]);
+    assert_eq!(generate_matcher(&state_positions, CodeGenerationTarget::Rust, "is_empty").unwrap_err(), CodeGenerationError::NoStartState);
+  }
+
+}
@@ -0,0 +1,670 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use app::{create_connections_from_state_positions, create_state_names, escape_dot_string, find_duplicate_state_name,
+get_alphabet_from_state_positions, get_type_of_automata_from_state_positions};
+use app::layout::create_layered_layout;
+use app::models::{join_connection_characters, split_connection_label, Coordinate, State, TypeOfAutomata};
+
+use crate::saving_automata_funcs::{create_workspace_with_automata, retrieve_workspace_data};
+mod tests;
+
+// Only the subset of the DOT language which is needed to describe an automaton is supported, namely node, edge and
+// attribute statements, along with subgraphs which are simply flattened. HTML labels and edges to subgraphs are not supported
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum DotError {
+  UnableToReadFile,
+  UnableToWriteFile,
+  // Undirected graphs have no way of representing the direction of a transition
+  NotADigraph,
+  UnexpectedToken { line: usize, token: String },
+  UnexpectedEndOfFile,
+  UnterminatedString { line: usize },
+  UnsupportedSyntax { line: usize, token: String },
+  DuplicateStateName(String)
+}
+
+#[tauri::command]
+pub fn export_workspace_to_dot(workspace_name: &str, email: &str, path: &str) -> Result<(), DotError> {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+  let dot = convert_state_positions_to_dot(workspace_data.get_state_positions());
+
+  fs::write(path, dot)
+    .map_err(|_| DotError::UnableToWriteFile)
+
+}
+
+// The workspace is named after the graph if it has a name, otherwise after the file.
+// Returns the name the workspace was imported under, as a number is appended if the name is already taken
+#[tauri::command]
+pub fn import_dot_file(email: &str, path: &str) -> Result<String, DotError> {
+
+  let dot = fs::read_to_string(path)
+    .map_err(|_| DotError::UnableToReadFile)?;
+
+  let (graph_name, state_positions, type_of_automata) = parse_dot(&dot)?;
+
+  let workspace_name = graph_name.unwrap_or_else(|| Path::new(path)
+    .file_stem()
+    .and_then(|file_stem| file_stem.to_str())
+    .unwrap_or("Imported DOT automaton")
+    .to_owned());
+
+  let alphabet = get_alphabet_from_state_positions(&state_positions);
+  let connections = create_connections_from_state_positions(&state_positions);
+
+  Ok(create_workspace_with_automata(
+    email,
+    &workspace_name,
+    type_of_automata,
+    alphabet.iter().map(|c| c.as_str()).collect(),
+    state_positions,
+    connections
+  ))
+
+}
+
+// States are written under their names, as they are in every other export, with states listed in sorted order of their keys
+// so the same workspace always produces the same file.
+// Every state is given a pinned position, with the y axis flipped as Graphviz places the origin at the bottom left,
+// so neato -n reproduces the layout from the workspace and importing the file again gives back the same state keys
+pub fn convert_state_positions_to_dot(state_positions: &HashMap<String, State>) -> String {
+
+  let sorted_state_positions: BTreeMap<&String, &State> = state_positions.iter().collect();
+  let names_by_state_key: BTreeMap<String, String> = create_state_names(state_positions)
+    .into_iter()
+    .map(|(state_key, state_name)| (state_key, format_dot_id(&state_name)))
+    .collect();
+
+  let mut dot = String::from("digraph automaton {\n  rankdir=LR;\n  node [shape=circle];\n");
+
+  for (state_key, state) in &sorted_state_positions {
+    let position = state.get_position();
+    let shape = if state.is_final() { "doublecircle" } else { "circle" };
+
+    dot += &format!(
+      "  {} [shape={}, pos=\"{},{}!\"];\n",
+      names_by_state_key[*state_key],
+      shape,
+      position.x,
+      -position.y
+    );
+  }
+
+  // Start states are marked by an arrow coming from an invisible point, as is usual when drawing automata
+  for (index, (state_key, _)) in sorted_state_positions.iter().filter(|(_, state)| state.is_start()).enumerate() {
+    dot += &format!("  __start{index} [shape=point, style=invis];\n");
+    dot += &format!("  __start{index} -> {};\n", names_by_state_key[*state_key]);
+  }
+
+  for (state_key, state) in &sorted_state_positions {
+
+    // Every character connecting the same pair of states is merged onto a single edge, giving labels such as "a,b".
+    // A , symbol is written as an empty character either side of a separator, as it is when saving
+    let mut characters_by_connected_state_name: BTreeMap<&String, BTreeSet<&String>> = BTreeMap::new();
+
+    for (connection_character, connected_state_keys) in state.get_all_connections() {
+      for connected_state_key in connected_state_keys {
+        if let Some(connected_state_name) = names_by_state_key.get(connected_state_key) {
+          characters_by_connected_state_name
+            .entry(connected_state_name)
+            .or_default()
+            .insert(connection_character);
+        }
+      }
+    }
+
+    for (connected_state_name, connection_characters) in characters_by_connected_state_name {
+      let label = join_connection_characters(connection_characters.into_iter().map(|connection_character| connection_character.as_str()));
+
+      dot += &format!(
+        "  {} -> {} [label=\"{}\"];\n",
+        names_by_state_key[*state_key],
+        connected_state_name,
+        escape_dot_string(&label)
+      );
+    }
+  }
+
+  dot += "}\n";
+  dot
+
+}
+
+// Names which are not plain identifiers, such as {q0,q1} from the subset construction, are quoted
+fn format_dot_id(state_name: &str) -> String {
+  let is_keyword = ["node", "edge", "graph", "digraph", "subgraph", "strict"]
+    .iter()
+    .any(|keyword| keyword.eq_ignore_ascii_case(state_name));
+  let is_identifier = state_name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+    && state_name.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+  if is_identifier && !is_keyword {
+    state_name.to_owned()
+  } else {
+    format!("\"{}\"", escape_dot_string(state_name))
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DotToken {
+  // Quoted strings are kept separate from identifiers as they can never be keywords
+  Identifier(String),
+  QuotedString(String),
+  Symbol(&'static str)
+}
+
+impl DotToken {
+
+  fn to_display_string(&self) -> String {
+    match self {
+      DotToken::Identifier(identifier) => identifier.to_owned(),
+      DotToken::QuotedString(string) => format!("\"{string}\""),
+      DotToken::Symbol(symbol) => symbol.to_string()
+    }
+  }
+
+  fn is_keyword(&self, keyword: &str) -> bool {
+    matches!(self, DotToken::Identifier(identifier) if identifier.eq_ignore_ascii_case(keyword))
+  }
+
+  fn get_id(&self) -> Option<&String> {
+    match self {
+      DotToken::Identifier(id) | DotToken::QuotedString(id) => Some(id),
+      DotToken::Symbol(_) => None
+    }
+  }
+
+}
+
+// Every token is paired with the line it appeared on, so errors can point the user to the right place in the file
+fn tokenize_dot(dot: &str) -> Result<Vec<(DotToken, usize)>, DotError> {
+
+  let characters: Vec<char> = dot.chars().collect();
+  let mut tokens = vec![];
+  let mut line = 1;
+  let mut index = 0;
+
+  while index < characters.len() {
+    let c = characters[index];
+
+    match c {
+      '\n' => {
+        line += 1;
+        index += 1;
+      },
+      c if c.is_whitespace() => index += 1,
+      // Lines starting with # are output by the C preprocessor and are treated as comments
+      '#' => {
+        while index < characters.len() && characters[index] != '\n' {
+          index += 1;
+        }
+      },
+      '/' if characters.get(index + 1) == Some(&'/') => {
+        while index < characters.len() && characters[index] != '\n' {
+          index += 1;
+        }
+      },
+      '/' if characters.get(index + 1) == Some(&'*') => {
+        index += 2;
+        while index < characters.len() && !(characters[index] == '*' && characters.get(index + 1) == Some(&'/')) {
+          if characters[index] == '\n' {
+            line += 1;
+          }
+          index += 1;
+        }
+        index += 2;
+      },
+      '"' => {
+        let starting_line = line;
+        let mut string = String::new();
+        index += 1;
+
+        loop {
+          match characters.get(index) {
+            None => return Err(DotError::UnterminatedString { line: starting_line }),
+            Some('"') => break,
+            Some('\\') if characters.get(index + 1) == Some(&'"') => {
+              string.push('"');
+              index += 1;
+            },
+            // Backslashes are doubled in labels, as escape_dot_string writes them
+            Some('\\') if characters.get(index + 1) == Some(&'\\') => {
+              string.push('\\');
+              index += 1;
+            },
+            // A backslash before a newline continues the string onto the next line
+            Some('\\') if characters.get(index + 1) == Some(&'\n') => {
+              line += 1;
+              index += 1;
+            },
+            Some(c) => {
+              if *c == '\n' {
+                line += 1;
+              }
+              string.push(*c);
+            }
+          }
+          index += 1;
+        }
+
+        index += 1;
+        tokens.push((DotToken::QuotedString(string), starting_line));
+      },
+      '-' if characters.get(index + 1) == Some(&'>') => {
+        tokens.push((DotToken::Symbol("->"), line));
+        index += 2;
+      },
+      '-' if characters.get(index + 1) == Some(&'-') => {
+        tokens.push((DotToken::Symbol("--"), line));
+        index += 2;
+      },
+      '{' | '}' | '[' | ']' | ';' | ',' | '=' | ':' | '<' | '+' => {
+        let symbol = match c {
+          '{' => "{",
+          '}' => "}",
+          '[' => "[",
+          ']' => "]",
+          ';' => ";",
+          ',' => ",",
+          '=' => "=",
+          ':' => ":",
+          '<' => "<",
+          _ => "+"
+        };
+        tokens.push((DotToken::Symbol(symbol), line));
+        index += 1;
+      },
+      // Identifiers are any run of letters, digits, underscores and dots, which also covers numerals such as -1.5
+      c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+        let mut identifier = String::from(c);
+        index += 1;
+
+        while index < characters.len() && (characters[index].is_alphanumeric() || characters[index] == '_' || characters[index] == '.') {
+          identifier.push(characters[index]);
+          index += 1;
+        }
+
+        tokens.push((DotToken::Identifier(identifier), line));
+      },
+      _ => return Err(DotError::UnexpectedToken { line, token: c.to_string() })
+    }
+  }
+
+  Ok(tokens)
+
+}
+
+// Attributes set by node and edge statements only apply to the nodes and edges which come after them,
+// and only until the end of the subgraph they were set in
+#[derive(Clone, Default)]
+struct DefaultAttributes {
+  node: HashMap<String, String>,
+  edge: HashMap<String, String>
+}
+
+#[derive(Default)]
+struct DotGraph {
+  name: Option<String>,
+  // Kept in the order the nodes first appear, so the layout is the same every time a file is imported
+  node_names: Vec<String>,
+  node_attributes: HashMap<String, HashMap<String, String>>,
+  edges: Vec<(String, String, HashMap<String, String>)>
+}
+
+impl DotGraph {
+
+  fn add_node(&mut self, node_name: &str, default_attributes: &DefaultAttributes) {
+    if !self.node_attributes.contains_key(node_name) {
+      self.node_names.push(node_name.to_owned());
+      self.node_attributes.insert(node_name.to_owned(), default_attributes.node.to_owned());
+    }
+  }
+
+}
+
+struct DotParser {
+  tokens: Vec<(DotToken, usize)>,
+  index: usize
+}
+
+impl DotParser {
+
+  fn peek(&self) -> Option<&DotToken> {
+    self.tokens.get(self.index).map(|(token, _)| token)
+  }
+
+  fn next(&mut self) -> Result<(DotToken, usize), DotError> {
+    let token = self.tokens
+      .get(self.index)
+      .cloned()
+      .ok_or(DotError::UnexpectedEndOfFile)?;
+    self.index += 1;
+    Ok(token)
+  }
+
+  fn unexpected_token(&self) -> DotError {
+    match self.tokens.get(self.index) {
+      Some((token, line)) => DotError::UnexpectedToken { line: *line, token: token.to_display_string() },
+      None => DotError::UnexpectedEndOfFile
+    }
+  }
+
+  fn expect_symbol(&mut self, symbol: &'static str) -> Result<(), DotError> {
+    if self.skip_symbol(symbol) {
+      Ok(())
+    } else {
+      Err(self.unexpected_token())
+    }
+  }
+
+  fn skip_symbol(&mut self, symbol: &'static str) -> bool {
+    if self.peek() == Some(&DotToken::Symbol(symbol)) {
+      self.index += 1;
+      true
+    } else {
+      false
+    }
+  }
+
+  fn next_id(&mut self) -> Result<String, DotError> {
+    let (token, line) = self.next()?;
+
+    match token {
+      DotToken::Identifier(id) | DotToken::QuotedString(id) => {
+        // Quoted strings can be joined together with +, as in "a" + "b"
+        let mut id = id;
+        while self.peek() == Some(&DotToken::Symbol("+")) {
+          self.index += 1;
+          match self.next()? {
+            (DotToken::QuotedString(string), _) => id += &string,
+            (token, line) => return Err(DotError::UnexpectedToken { line, token: token.to_display_string() })
+          }
+        }
+        Ok(id)
+      },
+      DotToken::Symbol("<") => Err(DotError::UnsupportedSyntax { line, token: String::from("<") }),
+      token => Err(DotError::UnexpectedToken { line, token: token.to_display_string() })
+    }
+  }
+
+  // graph : [ strict ] digraph [ ID ] '{' stmt_list '}'
+  fn parse_graph(&mut self) -> Result<DotGraph, DotError> {
+    let mut graph = DotGraph::default();
+
+    if self.peek().is_some_and(|token| token.is_keyword("strict")) {
+      self.index += 1;
+    }
+
+    match self.peek() {
+      Some(token) if token.is_keyword("digraph") => self.index += 1,
+      Some(token) if token.is_keyword("graph") => return Err(DotError::NotADigraph),
+      _ => return Err(self.unexpected_token())
+    }
+
+    if self.peek().is_some_and(|token| token.get_id().is_some()) {
+      graph.name = Some(self.next_id()?);
+    }
+
+    self.expect_symbol("{")?;
+    self.parse_statement_list(&mut graph, DefaultAttributes::default())?;
+    self.expect_symbol("}")?;
+
+    if self.index < self.tokens.len() {
+      return Err(self.unexpected_token());
+    }
+
+    Ok(graph)
+  }
+
+  fn parse_statement_list(&mut self, graph: &mut DotGraph, mut default_attributes: DefaultAttributes) -> Result<(), DotError> {
+
+    while self.peek().is_some() && self.peek() != Some(&DotToken::Symbol("}")) {
+      self.parse_statement(graph, &mut default_attributes)?;
+
+      if !self.skip_symbol(";") {
+        self.skip_symbol(",");
+      }
+    }
+
+    Ok(())
+
+  }
+
+  fn parse_statement(&mut self, graph: &mut DotGraph, default_attributes: &mut DefaultAttributes) -> Result<(), DotError> {
+
+    let token = self.peek().cloned().ok_or(DotError::UnexpectedEndOfFile)?;
+
+    // Subgraphs are flattened into the main graph, but any default attributes they set stop applying once they end
+    if token.is_keyword("subgraph") || token == DotToken::Symbol("{") {
+      if token.is_keyword("subgraph") {
+        self.index += 1;
+        if self.peek().is_some_and(|token| token.get_id().is_some()) {
+          self.next_id()?;
+        }
+      }
+
+      self.expect_symbol("{")?;
+      self.parse_statement_list(graph, default_attributes.to_owned())?;
+      self.expect_symbol("}")?;
+
+      if self.peek() == Some(&DotToken::Symbol("->")) {
+        let (_, line) = self.tokens[self.index];
+        return Err(DotError::UnsupportedSyntax { line, token: String::from("subgraph edge") });
+      }
+
+      return Ok(());
+    }
+
+    if token.is_keyword("graph") || token.is_keyword("node") || token.is_keyword("edge") {
+      self.index += 1;
+      let attributes = self.parse_attribute_lists()?;
+
+      if token.is_keyword("node") {
+        default_attributes.node.extend(attributes);
+      } else if token.is_keyword("edge") {
+        default_attributes.edge.extend(attributes);
+      }
+
+      return Ok(());
+    }
+
+    let node_name = self.next_id()?;
+    self.skip_port()?;
+
+    // A graph attribute such as rankdir=LR
+    if self.skip_symbol("=") {
+      self.next_id()?;
+      return Ok(());
+    }
+
+    let mut node_names = vec![node_name];
+
+    while self.skip_symbol("->") {
+      if self.peek() == Some(&DotToken::Symbol("{")) || self.peek().is_some_and(|token| token.is_keyword("subgraph")) {
+        let (_, line) = self.tokens[self.index];
+        return Err(DotError::UnsupportedSyntax { line, token: String::from("subgraph edge") });
+      }
+      node_names.push(self.next_id()?);
+      self.skip_port()?;
+    }
+
+    if self.peek() == Some(&DotToken::Symbol("--")) {
+      return Err(DotError::NotADigraph);
+    }
+
+    let attributes = self.parse_attribute_lists()?;
+
+    for node_name in &node_names {
+      graph.add_node(node_name, default_attributes);
+    }
+
+    if node_names.len() == 1 {
+      graph.node_attributes
+        .get_mut(&node_names[0])
+        .expect("The node should have just been added")
+        .extend(attributes);
+    } else {
+      for edge in node_names.windows(2) {
+        let mut edge_attributes = default_attributes.edge.to_owned();
+        edge_attributes.extend(attributes.to_owned());
+        graph.edges.push((edge[0].to_owned(), edge[1].to_owned(), edge_attributes));
+      }
+    }
+
+    Ok(())
+
+  }
+
+  // Ports such as a:n or a:port:sw only affect where edges are drawn, so they are ignored
+  fn skip_port(&mut self) -> Result<(), DotError> {
+    while self.skip_symbol(":") {
+      self.next_id()?;
+    }
+    Ok(())
+  }
+
+  // attr_list : '[' [ a_list ] ']' [ attr_list ]
+  fn parse_attribute_lists(&mut self) -> Result<HashMap<String, String>, DotError> {
+    let mut attributes = HashMap::new();
+
+    while self.skip_symbol("[") {
+      while !self.skip_symbol("]") {
+        let key = self.next_id()?;
+        let value = if self.skip_symbol("=") { self.next_id()? } else { String::from("true") };
+        attributes.insert(key.to_lowercase(), value);
+
+        if !self.skip_symbol(";") {
+          self.skip_symbol(",");
+        }
+      }
+    }
+
+    Ok(attributes)
+  }
+
+}
+
+type ParsedDotAutomaton = (Option<String>, HashMap<String, State>, TypeOfAutomata);
+
+// Returns the name of the graph, if it has one, alongside the parsed states and whether they form a DFA or NFA.
+// Nodes drawn as a point, or which are invisible, are taken to be the markers from which start arrows are drawn rather than states,
+// and nodes drawn as a double circle are final. Edge labels may hold several characters separated by commas,
+// and unlabelled edges, or those labelled ϵ, ε or λ, are ϵ transitions
+fn parse_dot(dot: &str) -> Result<ParsedDotAutomaton, DotError> {
+
+  let mut parser = DotParser {
+    tokens: tokenize_dot(dot)?,
+    index: 0
+  };
+  let graph = parser.parse_graph()?;
+
+  let is_start_marker = |node_name: &String| {
+    let attributes = &graph.node_attributes[node_name];
+    let shape = attributes.get("shape").map(|shape| shape.to_lowercase()).unwrap_or_default();
+    let style = attributes.get("style").map(|style| style.to_lowercase()).unwrap_or_default();
+    matches!(shape.as_str(), "point" | "none" | "plaintext" | "plain") || style.contains("invis")
+  };
+
+  let state_names: Vec<&String> = graph.node_names
+    .iter()
+    .filter(|node_name| !is_start_marker(node_name))
+    .collect();
+
+  let start_state_names: HashSet<&String> = graph.edges
+    .iter()
+    .filter(|(from, to, _)| is_start_marker(from) && !is_start_marker(to))
+    .map(|(_, to, _)| to)
+    .collect();
+
+  let coordinates_by_state_name = match get_pinned_coordinates(&graph, &state_names) {
+    Some(coordinates_by_state_name) => coordinates_by_state_name,
+    None => {
+      let state_keys: Vec<&str> = state_names.iter().map(|state_name| state_name.as_str()).collect();
+      let start_state_keys: HashSet<&str> = start_state_names.iter().map(|state_name| state_name.as_str()).collect();
+      let connections: Vec<(&str, &str)> = graph.edges.iter().map(|(from, to, _)| (from.as_str(), to.as_str())).collect();
+
+      create_layered_layout(&state_keys, &start_state_keys, &connections)
+        .into_iter()
+        .map(|(state_name, coordinate)| (state_name.to_owned(), coordinate))
+        .collect()
+    }
+  };
+
+  let mut state_positions: HashMap<String, State> = HashMap::new();
+
+  for state_name in &state_names {
+    let attributes = &graph.node_attributes[*state_name];
+    let is_final = attributes.get("shape").is_some_and(|shape| shape.eq_ignore_ascii_case("doublecircle"))
+      || attributes.get("peripheries").is_some_and(|peripheries| peripheries.trim() == "2");
+
+    let coordinate = coordinates_by_state_name[*state_name];
+    let mut state = State::new(coordinate, start_state_names.contains(state_name), is_final);
+    state.set_name(Some(state_name.to_string()));
+    state_positions.insert(coordinate.into(), state);
+  }
+
+  for (from, to, attributes) in &graph.edges {
+    if is_start_marker(from) || is_start_marker(to) {
+      continue;
+    }
+
+    let end_state_key: String = coordinates_by_state_name[to].into();
+    let label = attributes.get("label").map(|label| label.as_str()).unwrap_or_default();
+
+    let start_state = state_positions
+      .get_mut::<String>(&coordinates_by_state_name[from].into())
+      .expect("Every node which is not a start marker should have a state");
+
+    // Labels are split the same way saved connections are, so the , symbol and classes such as [,;] survive a round trip
+    for connection_character in split_connection_label(label) {
+      let connection_character = match connection_character.trim() {
+        "" | "ϵ" | "ε" | "λ" => "ϵ",
+        connection_character => connection_character
+      };
+      start_state.add_connection(connection_character, end_state_key.to_owned());
+    }
+  }
+
+  // States are named after their node ids, which are unique already, but are checked like every other import's names
+  if let Some(state_name) = find_duplicate_state_name(&state_positions) {
+    return Err(DotError::DuplicateStateName(state_name));
+  }
+
+  let type_of_automata = get_type_of_automata_from_state_positions(&state_positions);
+
+  Ok((graph.name, state_positions, type_of_automata))
+
+}
+
+// Files which have already been laid out, such as those exported by this app, pin each node with a pos attribute.
+// These positions are only used if every state has one and no two states end up on the same coordinate
+fn get_pinned_coordinates(graph: &DotGraph, state_names: &[&String]) -> Option<HashMap<String, Coordinate>> {
+
+  let mut coordinates_by_state_name = HashMap::new();
+  let mut used_state_keys = HashSet::new();
+
+  for state_name in state_names {
+    let position = graph.node_attributes[*state_name].get("pos")?;
+    let (x, y) = position.trim_end_matches('!').split_once(',')?;
+
+    let coordinate = Coordinate {
+      x: x.trim().parse::<f64>().ok()?.round() as i32,
+      y: -y.trim().parse::<f64>().ok()?.round() as i32
+    };
+
+    let state_key: String = coordinate.into();
+    if !used_state_keys.insert(state_key) {
+      return None;
+    }
+
+    coordinates_by_state_name.insert(state_name.to_string(), coordinate);
+  }
+
+  Some(coordinates_by_state_name)
+
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::graphviz_funcs::{convert_state_positions_to_dot, parse_dot, DotError};
+  use crate::regular_expression_funcs::convert_regex_to_state_positions;
+  use crate::testing_automata_funcs::{test_string_dfa, test_string_nfa};
+  use crate::test_fixtures::get_start_state_key;
+  use app::models::{Coordinate, State, TypeOfAutomata};
+  use std::collections::HashMap;
+
+  // The style of DOT file commonly found alongside papers and in the Graphviz gallery
+  const ENDS_WITH_AB: &str = r#"
+    /* Accepts every string over {a, b} ending in ab */
+    digraph "Ends with ab" {
+      rankdir = LR;
+      node [shape = doublecircle]; q2;
+      node [shape = circle];
+      init [shape = point];
+
+      init -> q0;
+      q0 -> q0 [label = "a, b"];
+      q0 -> q1 [label = "a"]; // guess that this is the final a
+      q1 -> q2 [label = "b"];
+    }
+  "#;
+
+  const EVEN_NUMBER_OF_AS: &str = "
+    digraph {
+      start [style=invis]
+      start -> even
+      even [peripheries=2]
+      even -> odd -> even [label=a]
+      even -> even [label=b]
+      odd -> odd [label=\"b\"]
+    }
+  ";
+
+  #[test]
+  fn test_dot_import() {
+    let (graph_name, state_positions, type_of_automata) = parse_dot(ENDS_WITH_AB).unwrap();
+    let start_state_key = get_start_state_key(&state_positions);
+
+    assert_eq!(graph_name, Some(String::from("Ends with ab")));
+    assert_eq!(type_of_automata, TypeOfAutomata::NFA);
+    // The point used to draw the start arrow is not a state
+    assert_eq!(state_positions.len(), 3);
+
+    // States are laid out in columns by their distance from the start state
+    assert_eq!(start_state_key, "300,300");
+    assert!(state_positions.contains_key("500,300"));
+    assert!(state_positions["700,300"].is_final());
+
+    for (string_to_check, expected) in [("ab", true), ("bab", true), ("abab", true), ("ba", false), ("", false)] {
+      let (is_accepted, _) = test_string_nfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned());
+      assert_eq!(is_accepted, expected, "Failed on {string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_dot_import_with_edge_chains_and_peripheries() {
+    let (graph_name, state_positions, type_of_automata) = parse_dot(EVEN_NUMBER_OF_AS).unwrap();
+    let start_state_key = get_start_state_key(&state_positions);
+
+    assert_eq!(graph_name, None);
+    assert_eq!(type_of_automata, TypeOfAutomata::DFA);
+
+    for (string_to_check, expected) in [("", true), ("a", false), ("aba", true), ("bab", false)] {
+      let (is_accepted, _) = test_string_dfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned());
+      assert_eq!(is_accepted, expected, "Failed on {string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_dot_import_errors() {
+    assert_eq!(parse_dot("graph { a -- b }").unwrap_err(), DotError::NotADigraph);
+    assert_eq!(parse_dot("digraph { a -> b").unwrap_err(), DotError::UnexpectedEndOfFile);
+    assert_eq!(parse_dot("digraph {\n  a -> b [label=\"a]\n}").unwrap_err(), DotError::UnterminatedString { line: 2 });
+    assert_eq!(
+      parse_dot("digraph {\n  a -> b\n  b -> ]\n}").unwrap_err(),
+      DotError::UnexpectedToken { line: 3, token: String::from("]") }
+    );
+    assert_eq!(
+      parse_dot("digraph {\n  a -> { b c }\n}").unwrap_err(),
+      DotError::UnsupportedSyntax { line: 2, token: String::from("subgraph edge") }
+    );
+  }
+
+  #[test]
+  fn test_dot_export() {
+    let (state_positions, _) = convert_regex_to_state_positions("(a+b)*abb").unwrap();
+    let dot = convert_state_positions_to_dot(&state_positions);
+
+    assert!(dot.starts_with("digraph automaton {"));
+    assert!(dot.contains("shape=doublecircle"));
+    assert_eq!(dot.matches("[shape=point, style=invis];").count(), 1);
+    // Transitions between the same pair of states are merged onto a single edge
+    assert!(dot.contains("[label=\"a,b\"]") || dot.contains("[label=\"a\"]"));
+    let number_of_edges = dot.matches(" -> ").count() - 1;
+    let number_of_state_pairs: usize = state_positions
+      .values()
+      .map(|state| state.get_all_connected_state_keys().len())
+      .sum();
+    assert_eq!(number_of_edges, number_of_state_pairs);
+  }
+
+  #[test]
+  fn test_dot_round_trip() {
+    for regex in ["(a+b)*abb", "ab*c+ca", "a*"] {
+      let (state_positions, start_state_key) = convert_regex_to_state_positions(regex).unwrap();
+
+      let dot = convert_state_positions_to_dot(&state_positions);
+      let (_, reimported_state_positions, type_of_automata) = parse_dot(&dot).unwrap();
+
+      // Every state is pinned to its position, so the state keys are unchanged
+      assert_eq!(reimported_state_positions.len(), state_positions.len());
+      for (state_key, state) in &state_positions {
+        let reimported_state = &reimported_state_positions[state_key];
+        assert_eq!(reimported_state.is_start(), state.is_start());
+        assert_eq!(reimported_state.is_final(), state.is_final());
+        assert_eq!(reimported_state.get_all_connections(), state.get_all_connections());
+      }
+      assert_eq!(type_of_automata, TypeOfAutomata::DFA);
+      assert_eq!(convert_state_positions_to_dot(&reimported_state_positions), dot);
+
+      for string_to_check in ["", "a", "ab", "abb", "babb", "ac", "ca", "aaa"] {
+        assert_eq!(
+          test_string_dfa(reimported_state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned()).0,
+          test_string_dfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned()).0,
+          "Failed on {string_to_check} for {regex}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_dot_round_trip_with_commas() {
+    // A , symbol merged onto the same edge as a, and a class containing a comma on its own edge
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection(",", "500,300");
+    q0.add_connection("a", "500,300");
+    q1.add_connection("[,;]", "300,300");
+
+    let state_positions = HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("500,300"), q1)
+    ]);
+
+    let dot = convert_state_positions_to_dot(&state_positions);
+    let (_, reimported_state_positions, _) = parse_dot(&dot).unwrap();
+
+    for (state_key, state) in &state_positions {
+      assert_eq!(reimported_state_positions[state_key].get_all_connections(), state.get_all_connections());
+    }
+    assert!(reimported_state_positions.values().all(|state| state.get_connections_by_character("ϵ").is_none()));
+  }
+
+}
@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use app::{create_connections_from_state_positions, create_state_names, find_duplicate_state_name,
+get_alphabet_from_state_positions, get_type_of_automata_from_state_positions};
+use app::models::{Coordinate, State, TypeOfAutomata};
+
+use crate::saving_automata_funcs::{create_workspace_with_automata, retrieve_workspace_data};
+mod tests;
+
+// The subset of JFLAP's .jff format used for finite automata. JFLAP 7 wraps the states and transitions
+// in an automaton element whereas JFLAP 6 places them directly inside the structure element, so both are accepted
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename = "structure")]
+struct JflapStructure {
+  #[serde(rename = "type")]
+  type_of_structure: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  automaton: Option<JflapAutomaton>,
+  #[serde(default, rename = "state", skip_serializing_if = "Vec::is_empty")]
+  states: Vec<JflapState>,
+  #[serde(default, rename = "transition", skip_serializing_if = "Vec::is_empty")]
+  transitions: Vec<JflapTransition>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JflapAutomaton {
+  #[serde(default, rename = "state")]
+  states: Vec<JflapState>,
+  #[serde(default, rename = "transition")]
+  transitions: Vec<JflapTransition>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JflapState {
+  #[serde(rename = "@id")]
+  id: String,
+  #[serde(rename = "@name", skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+  x: f64,
+  y: f64,
+  // JFLAP marks start and final states with empty <initial/> and <final/> elements
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  initial: Option<()>,
+  #[serde(default, rename = "final", skip_serializing_if = "Option::is_none")]
+  is_final: Option<()>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JflapTransition {
+  from: String,
+  to: String,
+  // λ (or ϵ) transitions are written by JFLAP as an empty <read/> element
+  #[serde(default)]
+  read: String
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum JflapError {
+  UnableToReadFile,
+  UnableToWriteFile,
+  InvalidXml,
+  // JFLAP files can also hold turing machines, grammars etc. which cannot be represented as a workspace
+  NotAFiniteAutomaton,
+  UnknownStateId(String),
+  DuplicateStateId(String),
+  // Names must be unique within a workspace, which JFLAP does not enforce
+  DuplicateStateName(String)
+}
+
+#[tauri::command]
+pub fn import_jflap_file(email: &str, path: &str) -> Result<String, JflapError> {
+
+  let xml = fs::read_to_string(path)
+    .map_err(|_| JflapError::UnableToReadFile)?;
+
+  let (state_positions, type_of_automata) = parse_jflap(&xml)?;
+
+  let workspace_name = Path::new(path)
+    .file_stem()
+    .and_then(|file_stem| file_stem.to_str())
+    .unwrap_or("Imported JFLAP automaton");
+
+  let alphabet = get_alphabet_from_state_positions(&state_positions);
+  let connections = create_connections_from_state_positions(&state_positions);
+
+  Ok(create_workspace_with_automata(
+    email,
+    workspace_name,
+    type_of_automata,
+    alphabet.iter().map(|c| c.as_str()).collect(),
+    state_positions,
+    connections
+  ))
+
+}
+
+#[tauri::command]
+pub fn export_workspace_to_jflap(workspace_name: &str, email: &str, path: &str) -> Result<(), JflapError> {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+  let xml = convert_state_positions_to_jflap(workspace_data.get_state_positions());
+
+  fs::write(path, xml)
+    .map_err(|_| JflapError::UnableToWriteFile)
+
+}
+
+// Returns the parsed states alongside whether the automaton is a DFA or NFA, as JFLAP does not distinguish between the two
+fn parse_jflap(xml: &str) -> Result<(HashMap<String, State>, TypeOfAutomata), JflapError> {
+
+  // JFLAP 7 writes an escaped carriage return (&#13;) after every element, which would otherwise
+  // be read as text sitting between the elements and cause deserialization to fail
+  let structure: JflapStructure = quick_xml::de::from_str(&xml.replace("&#13;", ""))
+    .map_err(|_| JflapError::InvalidXml)?;
+
+  if structure.type_of_structure.trim() != "fa" {
+    return Err(JflapError::NotAFiniteAutomaton);
+  }
+
+  let (jflap_states, jflap_transitions) = match structure.automaton {
+    Some(automaton) => (automaton.states, automaton.transitions),
+    None => (structure.states, structure.transitions)
+  };
+
+  // States are keyed by their JFLAP ids, which transitions refer to them by, so states which round to the same position stay apart
+  let mut state_positions: HashMap<String, State> = HashMap::new();
+
+  for jflap_state in jflap_states {
+    let id = jflap_state.id.trim().to_owned();
+    if state_positions.contains_key(&id) {
+      return Err(JflapError::DuplicateStateId(id));
+    }
+
+    let coordinate = Coordinate {
+      x: jflap_state.x.round() as i32,
+      y: jflap_state.y.round() as i32
+    };
+
+    let mut state = State::with_id(id.to_owned(), coordinate, jflap_state.initial.is_some(), jflap_state.is_final.is_some());
+    state.set_name(jflap_state.name);
+    state_positions.insert(id, state);
+  }
+
+  for jflap_transition in jflap_transitions {
+    let start_state_key = jflap_transition.from.trim();
+    let end_state_key = jflap_transition.to.trim();
+
+    for state_key in [start_state_key, end_state_key] {
+      if !state_positions.contains_key(state_key) {
+        return Err(JflapError::UnknownStateId(state_key.to_owned()));
+      }
+    }
+
+    // JFLAP reads a transition such as <read>ab</read> as a then b, so it becomes a chain of transitions through new states
+    // spaced out between its ends, or above the state for a loop
+    let connection_characters: Vec<String> = match jflap_transition.read.trim() {
+      "" | "λ" | "ϵ" | "ε" => vec![String::from("ϵ")],
+      read => read.chars().map(String::from).collect()
+    };
+
+    let start_position = state_positions[start_state_key].get_position();
+    let end_position = state_positions[end_state_key].get_position();
+    let loop_offset = if start_position == end_position { 100 } else { 0 };
+    let number_of_characters = connection_characters.len() as i32;
+
+    let mut current_state_key = start_state_key.to_owned();
+
+    for (index, connection_character) in (1..).zip(connection_characters) {
+      let next_state_key = if index == number_of_characters {
+        end_state_key.to_owned()
+      } else {
+        let mut next_state_key = format!("{start_state_key}-{end_state_key}-{index}");
+        while state_positions.contains_key(&next_state_key) {
+          next_state_key.push('\'');
+        }
+
+        let coordinate = Coordinate {
+          x: start_position.x + (end_position.x - start_position.x) * index / number_of_characters,
+          y: start_position.y + (end_position.y - start_position.y) * index / number_of_characters - loop_offset
+        };
+        state_positions.insert(next_state_key.to_owned(), State::with_id(next_state_key.to_owned(), coordinate, false, false));
+        next_state_key
+      };
+
+      state_positions
+        .get_mut(&current_state_key)
+        .expect("Every state key should correspond to a parsed state")
+        .add_connection(&connection_character, next_state_key.to_owned());
+
+      current_state_key = next_state_key;
+    }
+  }
+
+  if let Some(state_name) = find_duplicate_state_name(&state_positions) {
+    return Err(JflapError::DuplicateStateName(state_name));
+  }
+
+  let type_of_automata = get_type_of_automata_from_state_positions(&state_positions);
+
+  Ok((state_positions, type_of_automata))
+
+}
+
+fn convert_state_positions_to_jflap(state_positions: &HashMap<String, State>) -> String {
+
+  // States are numbered in sorted order of their keys so the same workspace always produces the same file,
+  // and keep the same names as in every other export
+  let state_names = create_state_names(state_positions);
+  let sorted_state_positions: BTreeMap<&String, &State> = state_positions.iter().collect();
+  let ids_by_state_key: HashMap<&String, String> = sorted_state_positions
+    .keys()
+    .enumerate()
+    .map(|(id, state_key)| (*state_key, id.to_string()))
+    .collect();
+
+  let mut jflap_states = vec![];
+  let mut jflap_transitions = vec![];
+
+  for (state_key, state) in &sorted_state_positions {
+    let id = ids_by_state_key[state_key].to_owned();
+    let position = state.get_position();
+
+    jflap_states.push(JflapState {
+      name: Some(state_names[*state_key].to_owned()),
+      id: id.to_owned(),
+      x: position.x as f64,
+      y: position.y as f64,
+      initial: if state.is_start() { Some(()) } else { None },
+      is_final: if state.is_final() { Some(()) } else { None }
+    });
+
+    let sorted_connections: BTreeMap<&String, &HashSet<String>> = state.get_all_connections().iter().collect();
+
+    for (connection_character, connected_state_keys) in sorted_connections {
+      let mut connected_ids: Vec<&String> = connected_state_keys
+        .iter()
+        .filter_map(|connected_state_key| ids_by_state_key.get(connected_state_key))
+        .collect();
+      connected_ids.sort();
+
+      for connected_id in connected_ids {
+        jflap_transitions.push(JflapTransition {
+          from: id.to_owned(),
+          to: connected_id.to_owned(),
+          read: if connection_character == "ϵ" { String::new() } else { connection_character.to_owned() }
+        });
+      }
+    }
+  }
+
+  let structure = JflapStructure {
+    type_of_structure: String::from("fa"),
+    automaton: Some(JflapAutomaton {
+      states: jflap_states,
+      transitions: jflap_transitions
+    }),
+    states: vec![],
+    transitions: vec![]
+  };
+
+  let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?><!--Created with Inner Machinations.-->\n");
+  let mut serializer = quick_xml::se::Serializer::new(&mut xml);
+  serializer.indent('\t', 1);
+
+  structure
+    .serialize(serializer)
+    .expect("A JFLAP structure should always be serializable");
+
+  xml
+
+}
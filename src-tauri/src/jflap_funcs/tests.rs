@@ -0,0 +1,188 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::jflap_funcs::{convert_state_positions_to_jflap, parse_jflap, JflapError};
+  use crate::testing_automata_funcs::{test_string_dfa, test_string_nfa};
+  use crate::test_fixtures::get_start_state_key;
+  use app::models::{State, TypeOfAutomata};
+  use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+  const EVEN_NUMBER_OF_AS: &str = include_str!("samples/even_number_of_as.jff");
+  const ENDS_WITH_AB_LAMBDA: &str = include_str!("samples/ends_with_ab_lambda.jff");
+  const A_OR_BC: &str = include_str!("samples/a_or_bc.jff");
+  const TURING_MACHINE: &str = include_str!("samples/turing_machine.jff");
+  const MULTI_CHARACTER_READS: &str = include_str!("samples/multi_character_reads.jff");
+
+  // Whether the state is a start state, whether it is final, and its connections
+  type SortedState = (bool, bool, BTreeMap<String, Vec<String>>);
+
+  // Exporting numbers states afresh, so states and the states they connect to are compared by position. Connections are
+  // sorted, as the order of a HashSet is not stable
+  fn get_sorted_state_positions(state_positions: &HashMap<String, State>) -> BTreeMap<String, SortedState> {
+    state_positions
+      .values()
+      .map(|state| {
+        let connections = state
+          .get_all_connections()
+          .iter()
+          .map(|(connection_character, connected_state_keys)| {
+            let mut connected_positions: Vec<String> = connected_state_keys
+              .iter()
+              .map(|connected_state_key| state_positions[connected_state_key].get_position_as_string())
+              .collect();
+            connected_positions.sort();
+            (connection_character.to_owned(), connected_positions)
+          })
+          .collect();
+        (state.get_position_as_string(), (state.is_start(), state.is_final(), connections))
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_jflap_7_dfa_import() {
+    let (state_positions, type_of_automata) = parse_jflap(EVEN_NUMBER_OF_AS).unwrap();
+    let start_state_key = get_start_state_key(&state_positions);
+
+    assert_eq!(type_of_automata, TypeOfAutomata::DFA);
+    assert_eq!(state_positions.len(), 2);
+    // States are keyed by their JFLAP ids and keep their positions
+    assert_eq!(start_state_key, "0");
+    assert!(state_positions["0"].is_final());
+    assert!(!state_positions["1"].is_final());
+    assert_eq!(state_positions["1"].get_position_as_string(), "262,141");
+
+    for (string_to_check, expected) in [("", true), ("a", false), ("aa", true), ("abba", true), ("bab", false)] {
+      let (is_accepted, _) = test_string_dfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned());
+      assert_eq!(is_accepted, expected, "Failed on {string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_jflap_6_nfa_with_lambda_transition_import() {
+    let (state_positions, type_of_automata) = parse_jflap(ENDS_WITH_AB_LAMBDA).unwrap();
+    let start_state_key = get_start_state_key(&state_positions);
+
+    assert_eq!(type_of_automata, TypeOfAutomata::NFA);
+    assert_eq!(state_positions.len(), 4);
+    // 180.5 is rounded to the nearest whole coordinate
+    assert_eq!(state_positions["1"].get_position_as_string(), "181,120");
+    assert!(state_positions[&start_state_key].get_connections_by_character("ϵ").is_some());
+
+    for (string_to_check, expected) in [("ab", true), ("bab", true), ("aab", true), ("ba", false), ("", false)] {
+      let (is_accepted, _) = test_string_nfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned());
+      assert_eq!(is_accepted, expected, "Failed on {string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_jflap_import_keeps_names() {
+    let (state_positions, type_of_automata) = parse_jflap(A_OR_BC).unwrap();
+    let start_state_key = get_start_state_key(&state_positions);
+
+    assert_eq!(type_of_automata, TypeOfAutomata::DFA);
+    assert_eq!(state_positions[&start_state_key].get_name(), Some("start"));
+    assert_eq!(
+      state_positions.values().filter_map(|state| state.get_name()).collect::<BTreeSet<&str>>(),
+      BTreeSet::from(["start", "after b", "accept"])
+    );
+
+    for (string_to_check, expected) in [("a", true), ("bc", true), ("b", false), ("abc", false)] {
+      let (is_accepted, _) = test_string_dfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned());
+      assert_eq!(is_accepted, expected, "Failed on {string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_jflap_import_of_multi_character_reads() {
+    let (state_positions, type_of_automata) = parse_jflap(MULTI_CHARACTER_READS).unwrap();
+
+    // ab and the loop on cd each pass through one new state, and the spaces around c are not part of what is read
+    assert_eq!(type_of_automata, TypeOfAutomata::DFA);
+    assert_eq!(state_positions.len(), 4);
+    assert_eq!(state_positions["0-1-1"].get_position_as_string(), "250,200");
+    assert_eq!(state_positions["1-1-1"].get_position_as_string(), "400,100");
+    assert_eq!(
+      state_positions.values().flat_map(|state| state.get_all_connections().keys()).map(String::as_str).collect::<BTreeSet<&str>>(),
+      BTreeSet::from(["a", "b", "c", "d"])
+    );
+
+    for (string_to_check, expected) in [("ab", true), ("c", true), ("abcd", true), ("ccdcd", true), ("a", false), ("abc", false), (" c ", false)] {
+      let (is_accepted, _) = test_string_dfa(state_positions.to_owned(), String::from("0"), string_to_check.to_owned());
+      assert_eq!(is_accepted, expected, "Failed on {string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_jflap_import_errors() {
+    assert_eq!(parse_jflap(TURING_MACHINE).unwrap_err(), JflapError::NotAFiniteAutomaton);
+    assert_eq!(parse_jflap("<structure><type>fa</type>").unwrap_err(), JflapError::InvalidXml);
+
+    let unknown_state = "<structure><type>fa</type><automaton>\
+      <state id=\"0\"><x>0</x><y>0</y><initial/></state>\
+      <transition><from>0</from><to>4</to><read>a</read></transition>\
+      </automaton></structure>";
+    assert_eq!(parse_jflap(unknown_state).unwrap_err(), JflapError::UnknownStateId(String::from("4")));
+
+    let duplicate_name = "<structure><type>fa</type><automaton>\
+      <state id=\"0\" name=\"q\"><x>0</x><y>0</y><initial/></state>\
+      <state id=\"1\" name=\"q\"><x>100</x><y>0</y></state>\
+      </automaton></structure>";
+    assert_eq!(parse_jflap(duplicate_name).unwrap_err(), JflapError::DuplicateStateName(String::from("q")));
+
+    let duplicate_id = "<structure><type>fa</type><automaton>\
+      <state id=\"0\"><x>0</x><y>0</y><initial/></state>\
+      <state id=\"0\"><x>100</x><y>0</y></state>\
+      </automaton></structure>";
+    assert_eq!(parse_jflap(duplicate_id).unwrap_err(), JflapError::DuplicateStateId(String::from("0")));
+  }
+
+  #[test]
+  fn test_jflap_import_of_states_at_the_same_position() {
+    // 100.4 and 99.6 both round to 100, but the states are told apart by their ids rather than their positions
+    let overlapping_states = "<structure><type>fa</type><automaton>\
+      <state id=\"0\"><x>100.4</x><y>0</y><initial/></state>\
+      <state id=\"1\"><x>99.6</x><y>0</y><final/></state>\
+      <transition><from>0</from><to>1</to><read>a</read></transition>\
+      </automaton></structure>";
+    let (state_positions, _) = parse_jflap(overlapping_states).unwrap();
+
+    assert_eq!(state_positions.len(), 2);
+    assert_eq!(state_positions["0"].get_position(), state_positions["1"].get_position());
+    assert_eq!(state_positions["0"].get_first_connected_state_key_by_character("a").as_deref(), Some("1"));
+    assert!(test_string_dfa(state_positions.to_owned(), String::from("0"), String::from("a")).0);
+  }
+
+  #[test]
+  fn test_jflap_round_trip() {
+    for sample in [EVEN_NUMBER_OF_AS, ENDS_WITH_AB_LAMBDA, A_OR_BC, MULTI_CHARACTER_READS] {
+      let (state_positions, type_of_automata) = parse_jflap(sample).unwrap();
+
+      let exported_xml = convert_state_positions_to_jflap(&state_positions);
+      let (reimported_state_positions, reimported_type_of_automata) = parse_jflap(&exported_xml).unwrap();
+
+      assert_eq!(reimported_type_of_automata, type_of_automata);
+      assert_eq!(get_sorted_state_positions(&reimported_state_positions), get_sorted_state_positions(&state_positions));
+
+      // Exporting is deterministic, so exporting the re-imported automaton gives back exactly the same file
+      assert_eq!(convert_state_positions_to_jflap(&reimported_state_positions), exported_xml);
+    }
+  }
+
+  #[test]
+  fn test_jflap_export_format() {
+    let (state_positions, _) = parse_jflap(ENDS_WITH_AB_LAMBDA).unwrap();
+    let exported_xml = convert_state_positions_to_jflap(&state_positions);
+
+    assert!(exported_xml.starts_with("<?xml"));
+    assert!(exported_xml.contains("<type>fa</type>"));
+    // States are numbered by sorted key but keep the names they were imported with
+    assert!(exported_xml.contains("<state id=\"0\" name=\"q"));
+    assert!(exported_xml.contains("name=\"q0\">"));
+    assert!(exported_xml.contains("<initial/>"));
+    assert!(exported_xml.contains("<final/>"));
+    // The ϵ transition is written the way JFLAP writes λ transitions
+    assert!(exported_xml.contains("<read/>") || exported_xml.contains("<read></read>"));
+    assert!(!exported_xml.contains("ϵ"));
+  }
+
+}
@@ -0,0 +1,430 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::models::State;
+mod tests;
+
+// Besides a single symbol, a connection may be labelled with a class of characters written in brackets as in regexes,
+// such as [0-9] or [a-zA-Z_], the complement of one such as [^0-9], or [*], which is followed on any symbol no other
+// connection leaving the same state is labelled with. Classes only ever match a single character, so a multi character
+// symbol of the alphabet can only be matched by name or by [*]. Within brackets, \ escapes the character after it,
+// and \u{...} may be used for any character by its code point
+pub const OTHERWISE_LABEL: &str = "[*]";
+const EPSILON_LABEL: &str = "ϵ";
+
+const LAST_CHARACTER: u32 = char::MAX as u32;
+const SURROGATES: (u32, u32) = (0xD800, 0xDFFF);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransitionLabel {
+  Epsilon,
+  Symbol(String),
+  CharacterClass(CharacterSet),
+  Otherwise
+}
+
+// Sorted, disjoint and non adjacent inclusive ranges of code points, none of which are surrogates
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct CharacterSet {
+  ranges: Vec<(u32, u32)>
+}
+
+impl CharacterSet {
+
+  pub fn new(ranges: Vec<(u32, u32)>) -> Self {
+
+    let mut ranges = ranges;
+    ranges.sort();
+
+    let mut merged_ranges: Vec<(u32, u32)> = vec![];
+
+    for (start, end) in ranges {
+      // Surrogates are not characters, so are taken out of any range spanning them
+      let pieces = [(start, end.min(SURROGATES.0 - 1)), (start.max(SURROGATES.1 + 1), end)];
+
+      for (start, end) in pieces {
+        if start > end {
+          continue;
+        }
+        match merged_ranges.last_mut() {
+          Some(last_range) if start <= last_range.1.saturating_add(1) => last_range.1 = last_range.1.max(end),
+          _ => merged_ranges.push((start, end))
+        }
+      }
+    }
+
+    CharacterSet { ranges: merged_ranges }
+
+  }
+
+  pub fn complement(&self) -> Self {
+
+    let mut ranges = vec![];
+    let mut next_start = 0;
+
+    for (start, end) in &self.ranges {
+      if *start > next_start {
+        ranges.push((next_start, start - 1));
+      }
+      next_start = end + 1;
+    }
+
+    if next_start <= LAST_CHARACTER {
+      ranges.push((next_start, LAST_CHARACTER));
+    }
+
+    CharacterSet::new(ranges)
+
+  }
+
+  pub fn contains(&self, c: char) -> bool {
+    let code_point = c as u32;
+    self.ranges.iter().any(|(start, end)| *start <= code_point && code_point <= *end)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.ranges.is_empty()
+  }
+
+  // Written as a single character where possible, and otherwise as a class. Sets reaching the last character are
+  // written as the complement of what they leave out, so the class matching anything but a digit reads [^0-9]
+  fn to_label(&self) -> String {
+
+    if let [(start, end)] = self.ranges[..] {
+      let c = char::from_u32(start).expect("Sets should never contain surrogates");
+      if start == end && c.to_string() != EPSILON_LABEL {
+        return c.to_string();
+      }
+    }
+
+    let write_ranges = |ranges: &[(u32, u32)]| {
+      ranges
+        .iter()
+        .map(|(start, end)| match end - start {
+          0 => escape_class_character(*start),
+          1 => escape_class_character(*start) + &escape_class_character(*end),
+          _ => format!("{}-{}", escape_class_character(*start), escape_class_character(*end))
+        })
+        .collect::<String>()
+    };
+
+    if self.ranges.last().is_some_and(|(_, end)| *end == LAST_CHARACTER) {
+      format!("[^{}]", write_ranges(&self.complement().ranges))
+    } else {
+      format!("[{}]", write_ranges(&self.ranges))
+    }
+
+  }
+
+}
+
+fn escape_class_character(code_point: u32) -> String {
+
+  let c = char::from_u32(code_point).expect("Sets should never contain surrogates");
+
+  match c {
+    '\\' | '[' | ']' | '-' | '^' => format!("\\{c}"),
+    c if c.is_control() || (c.is_whitespace() && c != ' ') => format!("\\u{{{code_point:x}}}"),
+    c => c.to_string()
+  }
+
+}
+
+impl TransitionLabel {
+
+  // Any label which is not a well formed class is taken to be a symbol, so a symbol such as [ or [] can still be used
+  pub fn parse(label: &str) -> Self {
+
+    if label == EPSILON_LABEL {
+      return TransitionLabel::Epsilon;
+    } else if label == OTHERWISE_LABEL {
+      return TransitionLabel::Otherwise;
+    }
+
+    match parse_character_class(label) {
+      Some(character_set) => TransitionLabel::CharacterClass(character_set),
+      None => TransitionLabel::Symbol(label.to_owned())
+    }
+
+  }
+
+  pub fn matches(&self, symbol: &str) -> bool {
+
+    match self {
+      TransitionLabel::Symbol(label_symbol) => label_symbol == symbol,
+      TransitionLabel::CharacterClass(character_set) => {
+        let mut characters = symbol.chars();
+        match (characters.next(), characters.next()) {
+          (Some(c), None) => character_set.contains(c),
+          _ => false
+        }
+      },
+      // Otherwise depends on the other labels of the state, and is dealt with by get_connected_state_keys_by_symbol
+      TransitionLabel::Epsilon | TransitionLabel::Otherwise => false
+    }
+
+  }
+
+}
+
+pub fn is_symbolic_label(label: &str) -> bool {
+  matches!(TransitionLabel::parse(label), TransitionLabel::CharacterClass(_) | TransitionLabel::Otherwise)
+}
+
+fn parse_character_class(label: &str) -> Option<CharacterSet> {
+
+  let body = label.strip_prefix('[')?.strip_suffix(']')?;
+  if body.is_empty() {
+    return None;
+  }
+
+  let (is_complement, body) = match body.strip_prefix('^') {
+    Some(body) => (true, body),
+    None => (false, body)
+  };
+
+  let mut characters = body.chars().peekable();
+  let mut class_characters: Vec<(u32, bool)> = vec![];
+
+  // Each character is paired with whether it was written as is, as only an unescaped - forms a range
+  while let Some(c) = characters.next() {
+    match c {
+      '\\' => {
+        let escaped_character = characters.next()?;
+        if escaped_character == 'u' && characters.peek() == Some(&'{') {
+          characters.next();
+          let hex_digits: String = characters.by_ref().take_while(|c| *c != '}').collect();
+          let code_point = u32::from_str_radix(&hex_digits, 16).ok()?;
+          char::from_u32(code_point)?;
+          class_characters.push((code_point, false));
+        } else {
+          class_characters.push((escaped_character as u32, false));
+        }
+      },
+      // An unescaped ] would have closed the class early
+      ']' => return None,
+      c => class_characters.push((c as u32, true))
+    }
+  }
+
+  let mut ranges = vec![];
+  let mut index = 0;
+
+  while index < class_characters.len() {
+    let (start, _) = class_characters[index];
+
+    // A - at the start or end of the class is the character itself
+    let is_range = index + 2 < class_characters.len() && class_characters[index + 1] == ('-' as u32, true);
+
+    if is_range {
+      let (end, _) = class_characters[index + 2];
+      if end < start {
+        return None;
+      }
+      ranges.push((start, end));
+      index += 3;
+    } else {
+      ranges.push((start, start));
+      index += 1;
+    }
+  }
+
+  let character_set = CharacterSet::new(ranges);
+
+  if is_complement {
+    Some(character_set.complement())
+  } else {
+    Some(character_set)
+  }
+
+}
+
+// Every state connected to by reading the symbol, following [*] only if none of the state's other labels match it
+pub fn get_connected_state_keys_by_symbol<'a>(state: &'a State, symbol: &str) -> HashSet<&'a String> {
+
+  let mut connected_state_keys = HashSet::new();
+  let mut otherwise_state_keys = HashSet::new();
+
+  for (label, state_keys) in state.get_all_connections() {
+    match TransitionLabel::parse(label) {
+      TransitionLabel::Otherwise => otherwise_state_keys.extend(state_keys),
+      transition_label if transition_label.matches(symbol) => connected_state_keys.extend(state_keys),
+      _ => ()
+    }
+  }
+
+  if connected_state_keys.is_empty() {
+    otherwise_state_keys
+  } else {
+    connected_state_keys
+  }
+
+}
+
+// Replaces every class and [*] with a connection on each symbol of the alphabet it matches, which leaves an automaton
+// over plain symbols that can be walked one symbol at a time. The alphabet is the given one along with every plain
+// symbol labelling a connection, and is returned as it is the alphabet the automaton is now over
+pub fn expand_symbolic_labels(state_positions: &mut HashMap<String, State>, input_alphabet: &[String]) -> BTreeSet<String> {
+
+  let labels: BTreeSet<&String> = state_positions
+    .values()
+    .flat_map(|state| state.get_all_connections().keys())
+    .collect();
+  let has_symbolic_label = labels.iter().any(|label| is_symbolic_label(label));
+
+  let alphabet: BTreeSet<String> = labels
+    .into_iter()
+    .chain(input_alphabet)
+    .filter(|symbol| symbol.as_str() != EPSILON_LABEL && !is_symbolic_label(symbol))
+    .cloned()
+    .collect();
+
+  if !has_symbolic_label {
+    return alphabet;
+  }
+
+  for state in state_positions.values_mut() {
+    let mut expanded_connections: HashMap<String, HashSet<String>> = HashMap::new();
+
+    if let Some(connected_state_keys) = state.get_connections_by_character(EPSILON_LABEL) {
+      expanded_connections.insert(EPSILON_LABEL.to_owned(), connected_state_keys.to_owned());
+    }
+
+    for symbol in &alphabet {
+      let connected_state_keys: HashSet<String> = get_connected_state_keys_by_symbol(state, symbol)
+        .into_iter()
+        .cloned()
+        .collect();
+      if !connected_state_keys.is_empty() {
+        expanded_connections.insert(symbol.to_owned(), connected_state_keys);
+      }
+    }
+
+    state.set_all_connections(expanded_connections);
+  }
+
+  alphabet
+
+}
+
+// Rewrites every connection so that no two labels in the automaton overlap, by splitting them into the minterms formed
+// by all of the labels in it. Afterwards every label stands for the same symbols in every state, any symbol matches
+// at most one label of a state, and [*] only stands for symbols no other label anywhere in the automaton matches.
+// This lets the subset construction and minimization treat labels as plain symbols, as they do for automata without classes
+pub fn split_labels_into_minterms(state_positions: &mut HashMap<String, State>) {
+
+  let labels: BTreeSet<String> = state_positions
+    .values()
+    .flat_map(|state| state.get_all_connections().keys())
+    .filter(|label| label.as_str() != EPSILON_LABEL)
+    .cloned()
+    .collect();
+
+  // Automata without any classes are left exactly as they are
+  if !labels.iter().any(|label| is_symbolic_label(label)) {
+    return;
+  }
+
+  let parsed_labels: BTreeMap<&String, TransitionLabel> = labels
+    .iter()
+    .map(|label| (label, TransitionLabel::parse(label)))
+    .collect();
+
+  // Minterms are identified by the labels which match them. Characters are split at every point a label's range starts
+  // or ends, and every multi character symbol is a minterm of its own
+  let mut character_sets: Vec<(&String, CharacterSet)> = vec![];
+  let mut minterms: BTreeMap<BTreeSet<&String>, Minterm> = BTreeMap::new();
+
+  for (label, parsed_label) in &parsed_labels {
+    match parsed_label {
+      TransitionLabel::CharacterClass(character_set) => character_sets.push((label, character_set.to_owned())),
+      TransitionLabel::Symbol(symbol) if symbol.chars().count() == 1 => {
+        let code_point = symbol.chars().next().unwrap() as u32;
+        character_sets.push((label, CharacterSet::new(vec![(code_point, code_point)])));
+      },
+      TransitionLabel::Symbol(symbol) => {
+        minterms.insert(BTreeSet::from([*label]), Minterm::Symbol(symbol.to_owned()));
+      },
+      TransitionLabel::Epsilon | TransitionLabel::Otherwise => ()
+    }
+  }
+
+  let mut boundaries: BTreeSet<u32> = BTreeSet::new();
+  for (_, character_set) in &character_sets {
+    for (start, end) in &character_set.ranges {
+      boundaries.insert(*start);
+      boundaries.insert(end + 1);
+    }
+  }
+
+  let mut ranges_by_labels: BTreeMap<BTreeSet<&String>, Vec<(u32, u32)>> = BTreeMap::new();
+  let boundaries: Vec<u32> = boundaries.into_iter().collect();
+
+  for window in boundaries.windows(2) {
+    let (start, end) = (window[0], window[1] - 1);
+    let matching_labels: BTreeSet<&String> = character_sets
+      .iter()
+      .filter(|(_, character_set)| character_set.ranges.iter().any(|(range_start, range_end)| *range_start <= start && end <= *range_end))
+      .map(|(label, _)| *label)
+      .collect();
+
+    if !matching_labels.is_empty() {
+      ranges_by_labels.entry(matching_labels).or_default().push((start, end));
+    }
+  }
+
+  for (matching_labels, ranges) in ranges_by_labels {
+    minterms.insert(matching_labels, Minterm::Characters(CharacterSet::new(ranges)));
+  }
+
+  for state in state_positions.values_mut() {
+
+    let connections = state.get_all_connections().to_owned();
+    let mut split_connections: HashMap<String, HashSet<String>> = HashMap::new();
+
+    let otherwise_state_keys = connections.get(OTHERWISE_LABEL).cloned().unwrap_or_default();
+
+    for (matching_labels, minterm) in &minterms {
+      // A minterm is matched by [*] unless one of the state's own labels matches it
+      let mut connected_state_keys: HashSet<String> = matching_labels
+        .iter()
+        .filter_map(|label| connections.get(*label))
+        .flatten()
+        .cloned()
+        .collect();
+
+      if !matching_labels.iter().any(|label| connections.contains_key(*label)) {
+        connected_state_keys.clone_from(&otherwise_state_keys);
+      }
+
+      if !connected_state_keys.is_empty() {
+        split_connections.insert(minterm.to_label(), connected_state_keys);
+      }
+    }
+
+    if !otherwise_state_keys.is_empty() {
+      split_connections.insert(OTHERWISE_LABEL.to_owned(), otherwise_state_keys);
+    }
+
+    if let Some(epsilon_state_keys) = connections.get(EPSILON_LABEL) {
+      split_connections.insert(EPSILON_LABEL.to_owned(), epsilon_state_keys.to_owned());
+    }
+
+    state.set_all_connections(split_connections);
+
+  }
+
+}
+
+enum Minterm {
+  Characters(CharacterSet),
+  Symbol(String)
+}
+
+impl Minterm {
+  fn to_label(&self) -> String {
+    match self {
+      Minterm::Characters(character_set) => character_set.to_label(),
+      Minterm::Symbol(symbol) => symbol.to_owned()
+    }
+  }
+}
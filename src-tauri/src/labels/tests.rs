@@ -0,0 +1,142 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::labels::{expand_symbolic_labels, get_connected_state_keys_by_symbol, split_labels_into_minterms, TransitionLabel};
+  use crate::models::{join_connection_characters, split_connection_label, Coordinate, State};
+  use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+  #[test]
+  fn test_parsing_labels() {
+    let digits = TransitionLabel::parse("[0-9]");
+    assert!(digits.matches("0") && digits.matches("7") && digits.matches("9"));
+    assert!(!digits.matches("a") && !digits.matches("10") && !digits.matches(""));
+
+    let identifier = TransitionLabel::parse("[a-zA-Z_]");
+    assert!(identifier.matches("q") && identifier.matches("Q") && identifier.matches("_"));
+    assert!(!identifier.matches("-") && !identifier.matches("é"));
+
+    // Complements match any single character other than the ones listed, but never a multi character symbol
+    let not_digits = TransitionLabel::parse("[^0-9]");
+    assert!(not_digits.matches("a") && not_digits.matches("🙂") && !not_digits.matches("5") && !not_digits.matches("ab"));
+
+    let escaped = TransitionLabel::parse(r"[\]\-\\a-]");
+    assert!(escaped.matches("]") && escaped.matches("-") && escaped.matches("\\") && escaped.matches("a"));
+    assert!(!escaped.matches("b"));
+
+    assert!(TransitionLabel::parse(r"[\u{3b1}-\u{3c9}]").matches("λ"));
+
+    assert_eq!(TransitionLabel::parse("ϵ"), TransitionLabel::Epsilon);
+    assert_eq!(TransitionLabel::parse("[*]"), TransitionLabel::Otherwise);
+    for symbol in ["a", "if", "[", "[]", "[z-a]", "[a]b]"] {
+      assert_eq!(TransitionLabel::parse(symbol), TransitionLabel::Symbol(symbol.to_owned()), "{symbol}");
+    }
+  }
+
+  #[test]
+  fn test_otherwise_label() {
+    let mut state = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    state.add_connection("[0-9]", "digit");
+    state.add_connection("if", "keyword");
+    state.add_connection("[*]", "other");
+
+    let connected_state_key = |symbol: &str| {
+      get_connected_state_keys_by_symbol(&state, symbol).into_iter().cloned().collect::<Vec<String>>()
+    };
+
+    assert_eq!(connected_state_key("4"), ["digit"]);
+    assert_eq!(connected_state_key("if"), ["keyword"]);
+    assert_eq!(connected_state_key("x"), ["other"]);
+    assert_eq!(connected_state_key("then"), ["other"]);
+  }
+
+  #[test]
+  fn test_splitting_labels_into_minterms() {
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection("[a-z]", "500,300");
+    q0.add_connection("x", "300,300");
+    q0.add_connection("ϵ", "500,300");
+    q1.add_connection("[0-9x]", "500,300");
+    q1.add_connection("[*]", "300,300");
+
+    let mut state_positions = HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("500,300"), q1)
+    ]);
+    let original_state_positions = state_positions.clone();
+
+    split_labels_into_minterms(&mut state_positions);
+
+    let labels_of = |state_key: &str| {
+      state_positions[state_key]
+        .get_all_connections()
+        .iter()
+        .map(|(label, state_keys)| (label.as_str(), state_keys.iter().cloned().collect()))
+        .collect::<BTreeMap<&str, BTreeSet<String>>>()
+    };
+
+    assert_eq!(labels_of("300,300"), BTreeMap::from([
+      ("[a-wyz]", BTreeSet::from([String::from("500,300")])),
+      ("x", BTreeSet::from([String::from("300,300"), String::from("500,300")])),
+      ("ϵ", BTreeSet::from([String::from("500,300")]))
+    ]));
+    // The [*] of the second state is split over the minterms it covered
+    assert_eq!(labels_of("500,300"), BTreeMap::from([
+      ("[0-9]", BTreeSet::from([String::from("500,300")])),
+      ("[a-wyz]", BTreeSet::from([String::from("300,300")])),
+      ("x", BTreeSet::from([String::from("500,300")])),
+      ("[*]", BTreeSet::from([String::from("300,300")]))
+    ]));
+
+    // Every symbol still leads to the same states it did before
+    for symbol in ["a", "x", "z", "5", "_", "if", "🙂"] {
+      for state_key in ["300,300", "500,300"] {
+        assert_eq!(
+          get_connected_state_keys_by_symbol(&state_positions[state_key], symbol),
+          get_connected_state_keys_by_symbol(&original_state_positions[state_key], symbol),
+          "{state_key} on {symbol}"
+        );
+      }
+    }
+
+    // Splitting again changes nothing
+    let split_state_positions = state_positions.clone();
+    split_labels_into_minterms(&mut state_positions);
+    assert_eq!(state_positions, split_state_positions);
+  }
+
+  #[test]
+  fn test_classes_in_joined_labels() {
+    for connection_characters in [vec!["[,;]", "a"], vec!["[a-z]", ","], vec!["[", "a"], vec![r"[\],]", "[^,]"]] {
+      let label = join_connection_characters(connection_characters.iter().copied());
+      assert_eq!(split_connection_label(&label), connection_characters, "Failed on {label}");
+    }
+  }
+
+  #[test]
+  fn test_expanding_symbolic_labels() {
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    q0.add_connection("[a-c]", "letter");
+    q0.add_connection("b", "b");
+    q0.add_connection("[*]", "other");
+    q0.add_connection("ϵ", "empty");
+
+    let mut state_positions = HashMap::from([(String::from("300,300"), q0)]);
+    let alphabet = expand_symbolic_labels(&mut state_positions, &[String::from("a"), String::from("if"), String::from("[0-9]")]);
+
+    // Classes in the given alphabet are left out, as they are not symbols
+    assert_eq!(alphabet, BTreeSet::from([String::from("a"), String::from("b"), String::from("if")]));
+
+    let labels_of_state = state_positions["300,300"]
+      .get_all_connections()
+      .iter()
+      .map(|(label, state_keys)| (label.as_str(), state_keys.iter().cloned().collect()))
+      .collect::<BTreeMap<&str, BTreeSet<String>>>();
+    assert_eq!(labels_of_state, BTreeMap::from([
+      ("a", BTreeSet::from([String::from("letter")])),
+      ("b", BTreeSet::from([String::from("letter"), String::from("b")])),
+      ("if", BTreeSet::from([String::from("other")])),
+      ("ϵ", BTreeSet::from([String::from("empty")]))
+    ]));
+  }
+
+}
@@ -1,9 +1,32 @@
 use std::collections::{HashMap, HashSet};
 
+use app::get_alphabet_from_state_positions;
+use app::labels::expand_symbolic_labels;
 use app::models::TypeOfAutomata;
+use serde::Serialize;
 
 use app::models::State;
-use crate::advanced_automata_funcs::reconstruct_nfa_state_positions;
+use crate::advanced_automata_funcs::{determinize_state_positions, reconstruct_nfa_state_positions};
+use crate::string_generation_funcs::get_state_keys_which_reach_final_state;
+use crate::string_sampling_funcs::count_accepted_strings_by_length;
+mod tests;
+
+#[derive(Debug, Serialize)]
+pub struct LanguageSize {
+  // Index n holds the number of accepted strings of length n
+  counts_by_length: Vec<u64>,
+  is_finite: bool,
+  // Both of the following are only known when the language is finite, and the longest string
+  // is None when the language is empty. If there are several longest strings, the alphabetically first is given
+  total_number_of_strings: Option<u64>,
+  longest_string: Option<String>
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize)]
+pub enum CountingError {
+  // The number of accepted strings of some length, or in total, does not fit into a u64
+  TooManyStrings
+}
 
 // Need to refactor to use less cloning in future
 fn find_all_paths_to_final_state(start_state: &State, 
@@ -16,7 +39,7 @@ fn find_all_paths_to_final_state(start_state: &State,
   // Since we already handle the empty string prior to running this code
   // We will only count strings with a positive length
 
-  if start_state == end_state && consumed_string.len() > 0 {
+  if start_state == end_state && !consumed_string.is_empty() {
 
     match strings_to_final_state.get_mut(end_state) {
       Some(previous_ways) => {
@@ -139,7 +162,7 @@ fn convert_acceptance_paths_to_string(acceptance_paths: &HashMap<String, HashSet
 
   for (direct_path, looping_paths) in acceptance_paths {
 
-    if representation_of_acceptance_path.len() == 1 {
+    if representation_of_acceptance_path == "{" {
       representation_of_acceptance_path += direct_path.as_str();
     } else {
       representation_of_acceptance_path += format!(" + {direct_path}").as_str();
@@ -151,7 +174,7 @@ fn convert_acceptance_paths_to_string(acceptance_paths: &HashMap<String, HashSet
       let mut looping_paths_to_add = String::new();
 
       for looping_path in looping_paths {
-        if looping_paths_to_add.len() == 0 {
+        if looping_paths_to_add.is_empty() {
           looping_paths_to_add += looping_path.as_str();
 
         } else {
@@ -173,11 +196,15 @@ fn convert_acceptance_paths_to_string(acceptance_paths: &HashMap<String, HashSet
 // Todo: Fix
 #[tauri::command]
 pub fn determine_language_of_automata(
-  state_positions: HashMap<String, State>, 
+  mut state_positions: HashMap<String, State>, 
   start_state_key: &str, 
   type_of_automata: TypeOfAutomata,
+  input_alphabet: Vec<String>
 ) -> String {
 
+  // Paths are written out symbol by symbol, so classes and [*] are replaced by the symbols of the alphabet they match
+  expand_symbolic_labels(&mut state_positions, &input_alphabet);
+
   match type_of_automata {
     TypeOfAutomata::DFA => {
       determine_language_of_dfa(&state_positions, &start_state_key)
@@ -193,7 +220,7 @@ pub fn determine_language_of_automata(
       let mut start_state_key = String::new();
       for state in state_positions.values() {
         if state.is_start() {
-          start_state_key = state.get_position_as_string();
+          start_state_key = state.get_id().to_owned();
         }
       }
       if start_state_key != "" {
@@ -301,4 +328,152 @@ fn determine_language_of_dfa(state_positions: &HashMap<String, State>, start_sta
 
 }
 
+#[tauri::command]
+pub fn count_language(
+  mut state_positions: HashMap<String, State>,
+  start_state_key: &str,
+  type_of_automata: TypeOfAutomata,
+  input_alphabet: Vec<String>,
+  maximum_length: usize
+) -> Result<LanguageSize, CountingError> {
+
+  if !state_positions.contains_key(start_state_key) {
+    return Ok(LanguageSize {
+      counts_by_length: vec![0; maximum_length + 1],
+      is_finite: true,
+      total_number_of_strings: Some(0),
+      longest_string: None
+    });
+  }
+
+  expand_symbolic_labels(&mut state_positions, &input_alphabet);
+
+  // Counting over an NFA would count a string once for every path which accepts it
+  let state_positions = match type_of_automata {
+    TypeOfAutomata::DFA => state_positions,
+    TypeOfAutomata::NFA => determinize_state_positions(state_positions, start_state_key)
+  };
+
+  let alphabet = get_alphabet_from_state_positions(&state_positions);
+
+  // Only states which can be reached from the start state and can themselves reach a final state
+  // have any effect on which strings are accepted
+  let reachable_state_keys = get_state_keys_reachable_from_state(&state_positions, start_state_key);
+  let useful_state_keys: HashSet<String> = get_state_keys_which_reach_final_state(&state_positions)
+    .intersection(&reachable_state_keys)
+    .cloned()
+    .collect();
+
+  let is_finite = !does_contain_cycle(&state_positions, &useful_state_keys);
 
+  // A path through n useful states without any loops consumes at most n - 1 characters,
+  // so when the language is finite no accepted string can be longer than that
+  let longest_possible_length = useful_state_keys.len().saturating_sub(1);
+  let counting_length = if is_finite { maximum_length.max(longest_possible_length) } else { maximum_length };
+
+  let accepted_string_counts = count_accepted_strings_by_length(&state_positions, &alphabet, counting_length)
+    .map_err(|_| CountingError::TooManyStrings)?;
+
+  let counts_by_length: Vec<u64> = accepted_string_counts
+    .iter()
+    .map(|counts| counts[start_state_key])
+    .collect();
+
+  if !is_finite {
+    return Ok(LanguageSize {
+      counts_by_length,
+      is_finite,
+      total_number_of_strings: None,
+      longest_string: None
+    });
+  }
+
+  let mut total_number_of_strings: u64 = 0;
+  for count in &counts_by_length {
+    total_number_of_strings = total_number_of_strings
+      .checked_add(*count)
+      .ok_or(CountingError::TooManyStrings)?;
+  }
+
+  let longest_string = counts_by_length
+    .iter()
+    .rposition(|count| *count > 0)
+    .map(|longest_length| {
+      // Walk from the start state always taking the alphabetically first character which can still lead to acceptance
+      let mut longest_string = String::new();
+      let mut current_state_key = start_state_key.to_owned();
+
+      for remaining_length in (1..=longest_length).rev() {
+        for character in &alphabet {
+          let next_state_key = state_positions[&current_state_key].get_first_connected_state_key_by_character(character);
+
+          if let Some(next_state_key) = next_state_key {
+            if accepted_string_counts[remaining_length - 1][&next_state_key] > 0 {
+              longest_string += character;
+              current_state_key = next_state_key;
+              break;
+            }
+          }
+        }
+      }
+
+      longest_string
+    });
+
+  Ok(LanguageSize {
+    counts_by_length: counts_by_length[..=maximum_length].to_vec(),
+    is_finite,
+    total_number_of_strings: Some(total_number_of_strings),
+    longest_string
+  })
+
+}
+
+fn get_state_keys_reachable_from_state(state_positions: &HashMap<String, State>, state_key: &str) -> HashSet<String> {
+
+  let mut reachable_state_keys = HashSet::from([state_key.to_owned()]);
+  let mut state_keys_to_visit = vec![state_key.to_owned()];
+
+  while let Some(current_state_key) = state_keys_to_visit.pop() {
+    let current_state = match state_positions.get(&current_state_key) {
+      Some(state) => state,
+      None => continue
+    };
+
+    for connected_state_key in current_state.get_all_connected_state_keys() {
+      if reachable_state_keys.insert(connected_state_key.to_owned()) {
+        state_keys_to_visit.push(connected_state_key.to_owned());
+      }
+    }
+  }
+
+  reachable_state_keys
+
+}
+
+// Checks for a cycle amongst the given states, ignoring connections to any other state, by repeatedly removing
+// states which have no connections to remaining states. Only a cycle can prevent every state from being removed
+fn does_contain_cycle(state_positions: &HashMap<String, State>, state_keys: &HashSet<String>) -> bool {
+
+  let mut remaining_state_keys = state_keys.to_owned();
+  let mut finished = false;
+
+  while !finished {
+    finished = true;
+
+    for state_key in remaining_state_keys.clone() {
+      let has_remaining_connection = state_positions[&state_key]
+        .get_all_connected_state_keys()
+        .iter()
+        .any(|connected_state_key| remaining_state_keys.contains(*connected_state_key));
+
+      if !has_remaining_connection {
+        remaining_state_keys.remove(&state_key);
+        finished = false;
+      }
+    }
+  }
+
+  !remaining_state_keys.is_empty()
+
+}
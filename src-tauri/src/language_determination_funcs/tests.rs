@@ -0,0 +1,90 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::language_determination_funcs::{count_language, determine_language_of_automata, CountingError};
+  use crate::test_fixtures::create_state_positions_with_classes;
+  use app::models::{Coordinate, State, TypeOfAutomata};
+  use std::collections::HashMap;
+
+  #[test]
+  fn test_counting_language_with_classes() {
+    let state_positions = create_state_positions_with_classes();
+    let alphabet = symbols(&["a", "b", "7", "if"]);
+
+    let language_size = count_language(state_positions.clone(), "q0", TypeOfAutomata::DFA, alphabet.clone(), 3).unwrap();
+    assert_eq!(language_size.counts_by_length, [0, 2, 2, 0]);
+    assert!(language_size.is_finite);
+    assert_eq!(language_size.total_number_of_strings, Some(4));
+    assert_eq!(language_size.longest_string.as_deref(), Some("77"));
+
+    let language = determine_language_of_automata(state_positions, "q0", TypeOfAutomata::DFA, alphabet);
+    for accepted_string in ["a", "b", "77", "if7"] {
+      assert!(language.contains(accepted_string), "{language}");
+    }
+    assert!(!language.contains('['), "{language}");
+  }
+
+  fn create_state_positions(final_state_keys: &[&str], transitions: &[(&str, &str, &str)]) -> HashMap<String, State> {
+    let mut state_positions: HashMap<String, State> = HashMap::new();
+    for (from, _, to) in transitions {
+      for state_key in [from, to] {
+        let state = State::with_id(*state_key, Coordinate { x: 300, y: 300 }, *state_key == "q0", final_state_keys.contains(state_key));
+        state_positions.entry(state_key.to_string()).or_insert(state);
+      }
+    }
+    for (from, connection_character, to) in transitions {
+      state_positions.get_mut(*from).unwrap().add_connection(connection_character, *to);
+    }
+    state_positions
+  }
+
+  fn symbols(symbols: &[&str]) -> Vec<String> {
+    symbols.iter().map(|symbol| symbol.to_string()).collect()
+  }
+
+  #[test]
+  fn test_counting_infinite_language() {
+    // ab*
+    let state_positions = create_state_positions(&["q1"], &[("q0", "a", "q1"), ("q1", "b", "q1")]);
+
+    let language_size = count_language(state_positions.clone(), "q0", TypeOfAutomata::DFA, symbols(&["a", "b"]), 3).unwrap();
+    assert_eq!(language_size.counts_by_length, [0, 1, 1, 1]);
+    assert!(!language_size.is_finite);
+    assert_eq!(language_size.total_number_of_strings, None);
+    assert_eq!(language_size.longest_string, None);
+
+    // (a+b)*, which has more strings of length 70 than fit into a u64
+    let state_positions = create_state_positions(&["q0"], &[("q0", "a", "q0"), ("q0", "b", "q0")]);
+    assert_eq!(
+      count_language(state_positions, "q0", TypeOfAutomata::DFA, symbols(&["a", "b"]), 70).unwrap_err(),
+      CountingError::TooManyStrings
+    );
+  }
+
+  #[test]
+  fn test_counting_nfa_language() {
+    // Accepts a along two paths and ab along one, with each string still only counted once
+    let state_positions = create_state_positions(&["q1", "q3", "q4"], &[("q0", "a", "q1"), ("q0", "a", "q2"), ("q2", "b", "q3"), ("q0", "a", "q4")]);
+
+    let language_size = count_language(state_positions, "q0", TypeOfAutomata::NFA, symbols(&["a", "b"]), 2).unwrap();
+    assert_eq!(language_size.counts_by_length, [0, 1, 1]);
+    assert!(language_size.is_finite);
+    assert_eq!(language_size.total_number_of_strings, Some(2));
+    assert_eq!(language_size.longest_string.as_deref(), Some("ab"));
+  }
+
+  #[test]
+  fn test_finding_longest_string() {
+    // {b, ab, ba}, where ab is the alphabetically first of the longest strings. They are still found when longer
+    // than the maximum length, which only limits the counts by length
+    let state_positions = create_state_positions(
+      &["q2", "q3"],
+      &[("q0", "a", "q1"), ("q0", "b", "q2"), ("q1", "b", "q3"), ("q2", "a", "q3")]
+    );
+
+    let language_size = count_language(state_positions, "q0", TypeOfAutomata::DFA, symbols(&["a", "b"]), 1).unwrap();
+    assert_eq!(language_size.counts_by_length, [0, 1]);
+    assert_eq!(language_size.total_number_of_strings, Some(3));
+    assert_eq!(language_size.longest_string.as_deref(), Some("ab"));
+  }
+
+}
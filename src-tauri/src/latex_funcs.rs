@@ -0,0 +1,322 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use serde::Serialize;
+
+use app::{create_state_names, create_state_numbers, get_alphabet_from_state_positions};
+use app::models::{split_connection_label, Connection, Coordinate, State, TypeOfAutomata};
+
+use crate::saving_automata_funcs::retrieve_workspace_data;
+mod tests;
+
+// Coordinates on the canvas are in pixels with y increasing downwards, whereas TikZ uses centimetres with y increasing upwards
+const PIXELS_PER_CENTIMETRE: f64 = 100.0;
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum LatexExportError {
+  UnableToWriteFile
+}
+
+#[tauri::command]
+pub fn export_workspace_to_tikz(workspace_name: &str, email: &str) -> String {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+
+  convert_automata_to_tikz(workspace_data.get_state_positions(), workspace_data.get_list_of_connections())
+
+}
+
+#[tauri::command]
+pub fn export_workspace_formal_definition(workspace_name: &str, email: &str) -> String {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+
+  convert_automata_to_formal_definition(
+    workspace_data.get_state_positions(),
+    workspace_data.get_alphabet(),
+    workspace_data.get_type_of_automata()
+  )
+
+}
+
+// Writes a complete document holding both the picture and the formal definition, which can be compiled as is
+#[tauri::command]
+pub fn export_workspace_to_latex_file(workspace_name: &str, email: &str, path: &str) -> Result<(), LatexExportError> {
+
+  let document = format!(
+    "\\documentclass{{article}}\n\\usepackage{{tikz}}\n\\usetikzlibrary{{automata, positioning, arrows}}\n\n\\begin{{document}}\n\n{}\n{}\n\\end{{document}}\n",
+    export_workspace_to_tikz(workspace_name, email),
+    export_workspace_formal_definition(workspace_name, email)
+  );
+
+  fs::write(path, document)
+    .map_err(|_| LatexExportError::UnableToWriteFile)
+
+}
+
+// Names such as q12 are written as q_{12} so the number is subscripted, which also applies to each of the states
+// named within those given by the subset construction or minimization such as {q0,q1}. Anything else is escaped
+fn convert_state_name_to_latex(state_name: &str) -> String {
+
+  let mut latex = String::new();
+  let mut characters = state_name.chars().peekable();
+  let mut previous_character: Option<char> = None;
+
+  while let Some(c) = characters.next() {
+    let is_start_of_word = !previous_character.is_some_and(|previous_character| previous_character.is_alphanumeric());
+
+    if c == 'q' && is_start_of_word && characters.peek().is_some_and(|next_character| next_character.is_ascii_digit()) {
+      let mut number = String::new();
+      while let Some(digit) = characters.next_if(|next_character| next_character.is_ascii_digit()) {
+        number.push(digit);
+      }
+      latex += &format!("q_{{{number}}}");
+      previous_character = number.chars().last();
+    } else {
+      latex += &convert_character_to_latex(&c.to_string());
+      previous_character = Some(c);
+    }
+  }
+
+  latex
+
+}
+
+// Connection characters are placed in maths mode, so characters with a special meaning to LaTeX are escaped
+fn convert_character_to_latex(connection_character: &str) -> String {
+  connection_character
+    .chars()
+    .map(|c| match c {
+      'ϵ' | 'ε' | 'λ' => String::from("\\varepsilon"),
+      '#' | '$' | '%' | '&' | '_' | '{' | '}' => format!("\\{c}"),
+      '~' => String::from("\\sim"),
+      '^' => String::from("\\hat{}"),
+      '\\' => String::from("\\backslash"),
+      c => c.to_string()
+    })
+    .collect()
+}
+
+fn format_coordinate(coordinate: Coordinate) -> String {
+  let x = coordinate.x as f64 / PIXELS_PER_CENTIMETRE;
+  let y = -coordinate.y as f64 / PIXELS_PER_CENTIMETRE;
+  format!("({}, {})", format_number(x), format_number(y))
+}
+
+fn format_number(number: f64) -> String {
+  let formatted = format!("{:.2}", number);
+  let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+  if formatted == "-0" { String::from("0") } else { formatted.to_owned() }
+}
+
+// Each state is placed at its position on the canvas, and each connection follows its stored bezier curve.
+// Connections which share the same curve, which happens when several characters move between the same two states,
+// are drawn once with their characters joined by commas
+pub fn convert_automata_to_tikz(state_positions: &HashMap<String, State>, connections: &[Connection]) -> String {
+
+  let state_numbers = create_state_numbers(state_positions);
+  let state_names = create_state_names(state_positions);
+  let mut tikz = String::from("\\begin{tikzpicture}[shorten >=1pt, node distance=2cm, on grid, auto, >=stealth]\n");
+
+  // Names can hold characters TikZ does not allow in node names, so nodes are always named after the state's number
+  let node_names: BTreeMap<&String, String> = state_numbers
+    .iter()
+    .map(|(state_key, state_number)| (state_key, format!("q{state_number}")))
+    .collect();
+
+  let mut states_in_name_order: Vec<(&String, &State)> = state_positions.iter().collect();
+  states_in_name_order.sort_by_key(|(state_key, _)| state_numbers[*state_key]);
+
+  for (state_key, state) in states_in_name_order {
+    let mut options = vec!["state"];
+    if state.is_start() {
+      options.push("initial");
+    }
+    if state.is_final() {
+      options.push("accepting");
+    }
+
+    tikz += &format!(
+      "  \\node[{}] ({}) at {} {{${}$}};\n",
+      options.join(", "),
+      node_names[state_key],
+      format_coordinate(state.get_position()),
+      convert_state_name_to_latex(&state_names[state_key])
+    );
+  }
+
+  // Connections are grouped by their curve, keeping the order in which each curve was first seen
+  let mut curves: Vec<(&Connection, Vec<String>)> = vec![];
+
+  for connection in connections {
+    let curve = &connection.curve;
+    let existing_curve = curves.iter_mut().find(|(other_connection, _)| {
+      let other_curve = &other_connection.curve;
+      (other_curve.start_point, other_curve.control_point_one, other_curve.control_point_two, other_curve.end_point)
+        == (curve.start_point, curve.control_point_one, curve.control_point_two, curve.end_point)
+    });
+
+    let connection_characters = split_connection_label(&connection.connection_character)
+      .iter()
+      .map(|connection_character| convert_character_to_latex(connection_character))
+      .collect();
+
+    match existing_curve {
+      Some((_, existing_connection_characters)) => existing_connection_characters.extend(connection_characters),
+      None => curves.push((connection, connection_characters))
+    }
+  }
+
+  for (connection, connection_characters) in curves {
+    let curve = &connection.curve;
+
+    let start_state_name = node_names.get(&connection.get_start_state_key(state_positions));
+    let end_state_name = node_names.get(&connection.get_end_state_key(state_positions));
+
+    let (start_state_name, end_state_name) = match (start_state_name, end_state_name) {
+      (Some(start_state_name), Some(end_state_name)) => (start_state_name, end_state_name),
+      _ => continue
+    };
+
+    let label = format!("${}$", connection_characters.join(", "));
+
+    // Straight connections have their control points on the states themselves, which TikZ cannot use to work out
+    // where the line should leave each state, so they are drawn as plain edges instead
+    if curve.control_point_one == curve.start_point && curve.control_point_two == curve.end_point {
+      tikz += &format!("  \\path[->] ({start_state_name}) edge node {{{label}}} ({end_state_name});\n");
+    } else {
+      tikz += &format!(
+        "  \\draw[->] ({start_state_name}) .. controls {} and {} .. node {{{label}}} ({end_state_name});\n",
+        format_coordinate(curve.control_point_one),
+        format_coordinate(curve.control_point_two)
+      );
+    }
+  }
+
+  tikz += "\\end{tikzpicture}\n";
+  tikz
+
+}
+
+// The definition lists Q, Σ, q0 and F, followed by δ as a table with a row for every state and a column for every character.
+// The start state is marked with an arrow and final states with an asterisk, as is common in textbooks.
+// A DFA has a single state in each cell, or a dash where the transition is missing, whilst an NFA has a set of states
+// in each cell and an extra column for ϵ if any ϵ transitions are used
+pub fn convert_automata_to_formal_definition(
+  state_positions: &HashMap<String, State>,
+  input_alphabet: &[String],
+  type_of_automata: TypeOfAutomata
+) -> String {
+
+  let state_numbers = create_state_numbers(state_positions);
+  let state_names = create_state_names(state_positions);
+
+  let mut states_in_name_order: Vec<(&String, &State)> = state_positions.iter().collect();
+  states_in_name_order.sort_by_key(|(state_key, _)| state_numbers[*state_key]);
+
+  // The workspace's alphabet keeps the order the user gave it, followed by any other characters which are used
+  let mut alphabet: Vec<String> = input_alphabet.to_vec();
+  for connection_character in get_alphabet_from_state_positions(state_positions) {
+    if !alphabet.contains(&connection_character) {
+      alphabet.push(connection_character);
+    }
+  }
+
+  let has_epsilon_transitions = state_positions
+    .values()
+    .any(|state| state.get_connections_by_character("ϵ").is_some_and(|connected_state_keys| !connected_state_keys.is_empty()));
+
+  let mut columns = alphabet.to_owned();
+  if type_of_automata == TypeOfAutomata::NFA && has_epsilon_transitions {
+    columns.push(String::from("ϵ"));
+  }
+
+  let format_set = |names: Vec<String>| {
+    if names.is_empty() { String::from("\\emptyset") } else { format!("\\{{{}\\}}", names.join(", ")) }
+  };
+
+  let all_state_names: Vec<String> = states_in_name_order
+    .iter()
+    .map(|(state_key, _)| convert_state_name_to_latex(&state_names[*state_key]))
+    .collect();
+
+  let start_state_names: Vec<String> = states_in_name_order
+    .iter()
+    .filter(|(_, state)| state.is_start())
+    .map(|(state_key, _)| convert_state_name_to_latex(&state_names[*state_key]))
+    .collect();
+
+  let final_state_names: Vec<String> = states_in_name_order
+    .iter()
+    .filter(|(_, state)| state.is_final())
+    .map(|(state_key, _)| convert_state_name_to_latex(&state_names[*state_key]))
+    .collect();
+
+  let machine_name = if type_of_automata == TypeOfAutomata::DFA { "DFA" } else { "NFA" };
+
+  let mut definition = format!("The {machine_name} is $M = (Q, \\Sigma, \\delta, q_0, F)$ where\n\\begin{{itemize}}\n");
+  definition += &format!("  \\item $Q = {}$\n", format_set(all_state_names));
+  definition += &format!(
+    "  \\item $\\Sigma = {}$\n",
+    format_set(alphabet.iter().map(|connection_character| convert_character_to_latex(connection_character)).collect())
+  );
+  definition += "  \\item $\\delta$ is given by the table below\n";
+
+  match start_state_names.first() {
+    Some(start_state_name) => definition += &format!("  \\item $q_0 = {start_state_name}$\n"),
+    None => definition += "  \\item there is no start state\n"
+  }
+
+  definition += &format!("  \\item $F = {}$\n\\end{{itemize}}\n\n", format_set(final_state_names));
+
+  definition += &format!("\\begin{{tabular}}{{r|{}}}\n", "c".repeat(columns.len()));
+  definition += &format!(
+    "  $\\delta$ & {} \\\\\n  \\hline\n",
+    columns
+      .iter()
+      .map(|connection_character| format!("${}$", convert_character_to_latex(connection_character)))
+      .collect::<Vec<String>>()
+      .join(" & ")
+  );
+
+  for (state_key, state) in &states_in_name_order {
+    let mut row_label = convert_state_name_to_latex(&state_names[*state_key]);
+    if state.is_final() {
+      row_label = format!("*{row_label}");
+    }
+    if state.is_start() {
+      row_label = format!("\\rightarrow {row_label}");
+    }
+
+    let cells: Vec<String> = columns
+      .iter()
+      .map(|connection_character| {
+        let mut connected_state_keys: Vec<&String> = state
+          .get_connections_by_character(connection_character)
+          .into_iter()
+          .flatten()
+          .filter(|connected_state_key| state_names.contains_key(*connected_state_key))
+          .collect();
+        connected_state_keys.sort_by_key(|connected_state_key| state_numbers[*connected_state_key]);
+
+        let connected_state_names: Vec<String> = connected_state_keys
+          .into_iter()
+          .map(|connected_state_key| convert_state_name_to_latex(&state_names[connected_state_key]))
+          .collect();
+
+        match type_of_automata {
+          TypeOfAutomata::DFA => connected_state_names.first().cloned().unwrap_or(String::from("-")),
+          TypeOfAutomata::NFA => format_set(connected_state_names)
+        }
+      })
+      .map(|cell| format!("${cell}$"))
+      .collect();
+
+    definition += &format!("  ${row_label}$ & {} \\\\\n", cells.join(" & "));
+  }
+
+  definition += "\\end{tabular}\n";
+  definition
+
+}
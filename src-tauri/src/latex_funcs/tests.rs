@@ -0,0 +1,118 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::latex_funcs::{convert_automata_to_formal_definition, convert_automata_to_tikz};
+  use app::{create_connections_from_state_positions, create_state_names};
+  use app::models::{Coordinate, State, TypeOfAutomata};
+  use std::collections::HashMap;
+
+  // q0 -a-> q1 -b-> q2, with q2 final and looping on a or b, laid out from right to left so
+  // the names cannot simply come from sorting the state keys
+  fn create_starts_with_ab() -> HashMap<String, State> {
+    let mut q0 = State::new(Coordinate { x: 700, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 500, y: 300 }, false, false);
+    let mut q2 = State::new(Coordinate { x: 300, y: 300 }, false, true);
+
+    q0.add_connection("a", "500,300");
+    q1.add_connection("b", "300,300");
+    q2.add_connection("a", "300,300");
+    q2.add_connection("b", "300,300");
+
+    HashMap::from([
+      (String::from("700,300"), q0),
+      (String::from("500,300"), q1),
+      (String::from("300,300"), q2)
+    ])
+  }
+
+  #[test]
+  fn test_state_names_follow_breadth_first_order() {
+    let state_names = create_state_names(&create_starts_with_ab());
+
+    assert_eq!(state_names["700,300"], "q0");
+    assert_eq!(state_names["500,300"], "q1");
+    assert_eq!(state_names["300,300"], "q2");
+  }
+
+  #[test]
+  fn test_user_state_names() {
+    // The user's names are kept, and a generated name the user has already taken gets a prime instead
+    let mut state_positions = create_starts_with_ab();
+    state_positions.get_mut("700,300").unwrap().set_name(Some(String::from("{q0,q1}")));
+    state_positions.get_mut("300,300").unwrap().set_name(Some(String::from("q1")));
+
+    let state_names = create_state_names(&state_positions);
+    assert_eq!(state_names["700,300"], "{q0,q1}");
+    assert_eq!(state_names["500,300"], "q1'");
+    assert_eq!(state_names["300,300"], "q1");
+
+    // Nodes keep their numbered names in TikZ, as names can hold characters which are not allowed there
+    let connections = create_connections_from_state_positions(&state_positions);
+    let tikz = convert_automata_to_tikz(&state_positions, &connections);
+    assert!(tikz.contains("\\node[state, initial] (q0) at (7, -3) {$\\{q_{0},q_{1}\\}$};"));
+    assert!(tikz.contains("\\node[state] (q1) at (5, -3) {$q_{1}'$};"));
+    assert!(tikz.contains("\\path[->] (q0) edge node {$a$} (q1);"));
+
+    let definition = convert_automata_to_formal_definition(&state_positions, &[String::from("a"), String::from("b")], TypeOfAutomata::DFA);
+    assert!(definition.contains("\\item $Q = \\{\\{q_{0},q_{1}\\}, q_{1}', q_{1}\\}$"));
+  }
+
+  #[test]
+  fn test_tikz_export() {
+    let state_positions = create_starts_with_ab();
+    let connections = create_connections_from_state_positions(&state_positions);
+    let tikz = convert_automata_to_tikz(&state_positions, &connections);
+
+    assert!(tikz.starts_with("\\begin{tikzpicture}"));
+    assert!(tikz.ends_with("\\end{tikzpicture}\n"));
+    assert!(tikz.contains("\\node[state, initial] (q0) at (7, -3) {$q_{0}$};"));
+    assert!(tikz.contains("\\node[state] (q1) at (5, -3) {$q_{1}$};"));
+    assert!(tikz.contains("\\node[state, accepting] (q2) at (3, -3) {$q_{2}$};"));
+    assert!(tikz.contains("\\path[->] (q0) edge node {$a$} (q1);"));
+    // Both characters of the self loop share the same curve, and so are drawn as a single connection
+    assert!(
+      tikz.contains("\\draw[->] (q2) .. controls (1, -5) and (1, -1) .. node {$a, b$} (q2);")
+        || tikz.contains("\\draw[->] (q2) .. controls (1, -5) and (1, -1) .. node {$b, a$} (q2);")
+    );
+    assert_eq!(tikz.matches("\\node").count(), 3);
+    assert_eq!(tikz.matches("->").count(), 3);
+  }
+
+  #[test]
+  fn test_dfa_formal_definition() {
+    let definition = convert_automata_to_formal_definition(
+      &create_starts_with_ab(),
+      &[String::from("a"), String::from("b")],
+      TypeOfAutomata::DFA
+    );
+
+    assert!(definition.contains("\\item $Q = \\{q_{0}, q_{1}, q_{2}\\}$"));
+    assert!(definition.contains("\\item $\\Sigma = \\{a, b\\}$"));
+    assert!(definition.contains("\\item $q_0 = q_{0}$"));
+    assert!(definition.contains("\\item $F = \\{q_{2}\\}$"));
+    assert!(definition.contains("\\begin{tabular}{r|cc}"));
+    assert!(definition.contains("$\\rightarrow q_{0}$ & $q_{1}$ & $-$ \\\\"));
+    assert!(definition.contains("$q_{1}$ & $-$ & $q_{2}$ \\\\"));
+    assert!(definition.contains("$*q_{2}$ & $q_{2}$ & $q_{2}$ \\\\"));
+  }
+
+  #[test]
+  fn test_nfa_formal_definition() {
+    let mut state_positions = create_starts_with_ab();
+    let q0 = state_positions.get_mut("700,300").unwrap();
+    q0.add_connection("a", "300,300");
+    q0.add_connection("ϵ", "500,300");
+    q0.add_connection("#", "700,300");
+
+    // Characters missing from the workspace's alphabet are added after it
+    let definition = convert_automata_to_formal_definition(&state_positions, &[String::from("b")], TypeOfAutomata::NFA);
+
+    assert!(definition.contains("The NFA is"));
+    assert!(definition.contains("\\item $\\Sigma = \\{b, \\#, a\\}$"));
+    assert!(definition.contains("\\begin{tabular}{r|cccc}"));
+    assert!(definition.contains("$\\delta$ & $b$ & $\\#$ & $a$ & $\\varepsilon$ \\\\"));
+    // The new a transition is followed before b, so the final state is now reached second
+    assert!(definition.contains("$\\rightarrow q_{0}$ & $\\emptyset$ & $\\{q_{0}\\}$ & $\\{q_{1}, q_{2}\\}$ & $\\{q_{2}\\}$ \\\\"));
+    assert!(definition.contains("$*q_{1}$ & $\\{q_{1}\\}$ & $\\emptyset$ & $\\{q_{1}\\}$ & $\\emptyset$ \\\\"));
+  }
+
+}
@@ -0,0 +1,146 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::models::{Coordinate, State};
+mod tests;
+
+// The same spacing as create_unique_state_coordinates, so laid out automata look at home next to ones placed by hand
+const LAYOUT_ORIGIN: i32 = 300;
+const LAYER_SPACING: i32 = 200;
+const ROW_SPACING: i32 = 200;
+
+// Places states in columns by their breadth first distance from a start state, so automata read from left to right.
+// States which cannot be reached from a start state are laid out the same way, starting from the first of them in the given order.
+// Within each column, states are ordered by the average height of the states in earlier columns which connect to them,
+// which keeps most connections short and stops them crossing, and each column is centred on the tallest one
+pub fn create_layered_layout<'a>(
+  state_keys: &[&'a str],
+  start_state_keys: &HashSet<&'a str>,
+  connections: &[(&'a str, &'a str)]
+) -> HashMap<&'a str, Coordinate> {
+
+  let mut connected_state_keys: HashMap<&str, Vec<&str>> = HashMap::new();
+  let mut previous_state_keys: HashMap<&str, Vec<&str>> = HashMap::new();
+
+  for (from, to) in connections {
+    connected_state_keys.entry(from).or_default().push(to);
+    previous_state_keys.entry(to).or_default().push(from);
+  }
+
+  let known_state_keys: HashSet<&str> = state_keys.iter().copied().collect();
+  let mut depths: HashMap<&str, usize> = HashMap::new();
+  let mut layers: Vec<Vec<&str>> = vec![];
+
+  let starting_points = state_keys
+    .iter()
+    .filter(|state_key| start_state_keys.contains(*state_key))
+    .chain(state_keys.iter());
+
+  for starting_point in starting_points {
+    if depths.contains_key(starting_point) {
+      continue;
+    }
+
+    let mut queue = VecDeque::from([(*starting_point, 0)]);
+    depths.insert(starting_point, 0);
+
+    while let Some((state_key, depth)) = queue.pop_front() {
+      if layers.len() <= depth {
+        layers.push(vec![]);
+      }
+      layers[depth].push(state_key);
+
+      for connected_state_key in connected_state_keys.get(state_key).into_iter().flatten() {
+        if known_state_keys.contains(connected_state_key) && !depths.contains_key(connected_state_key) {
+          depths.insert(connected_state_key, depth + 1);
+          queue.push_back((connected_state_key, depth + 1));
+        }
+      }
+    }
+  }
+
+  let tallest_layer_size = layers.iter().map(|layer| layer.len()).max().unwrap_or_default();
+  let mut coordinates: HashMap<&str, Coordinate> = HashMap::new();
+
+  for (depth, layer) in layers.iter_mut().enumerate() {
+
+    // States with nothing connecting to them from an earlier column keep the position they were found in
+    let barycentres: HashMap<&str, f64> = layer
+      .iter()
+      .enumerate()
+      .map(|(row, state_key)| {
+        let earlier_ys: Vec<f64> = previous_state_keys
+          .get(state_key)
+          .into_iter()
+          .flatten()
+          .filter(|previous_state_key| depths.get(*previous_state_key).is_some_and(|previous_depth| *previous_depth < depth))
+          .map(|previous_state_key| coordinates[previous_state_key].y as f64)
+          .collect();
+
+        let barycentre = if earlier_ys.is_empty() {
+          (LAYOUT_ORIGIN + ROW_SPACING * row as i32) as f64
+        } else {
+          earlier_ys.iter().sum::<f64>() / earlier_ys.len() as f64
+        };
+
+        (*state_key, barycentre)
+      })
+      .collect();
+
+    layer.sort_by(|first, second| barycentres[first].total_cmp(&barycentres[second]));
+
+    let centring_offset = ROW_SPACING * (tallest_layer_size - layer.len()) as i32 / 2;
+
+    for (row, state_key) in layer.iter().enumerate() {
+      coordinates.insert(state_key, Coordinate {
+        x: LAYOUT_ORIGIN + LAYER_SPACING * depth as i32,
+        y: LAYOUT_ORIGIN + centring_offset + ROW_SPACING * row as i32
+      });
+    }
+  }
+
+  coordinates
+
+}
+
+// Moves every state to its place in a layered layout. States keep their ids, so connections are left as they are.
+// States and characters are visited in sorted order, so the same automaton is always laid out the same way
+pub fn layout_state_positions(state_positions: &HashMap<String, State>) -> HashMap<String, State> {
+
+  let mut state_keys: Vec<&str> = state_positions.keys().map(|state_key| state_key.as_str()).collect();
+  state_keys.sort();
+
+  let start_state_keys: HashSet<&str> = state_positions
+    .iter()
+    .filter(|(_, state)| state.is_start())
+    .map(|(state_key, _)| state_key.as_str())
+    .collect();
+
+  let mut connections: Vec<(&str, &str)> = vec![];
+
+  for state_key in &state_keys {
+    let sorted_connections: BTreeSet<(&String, &String)> = state_positions[*state_key]
+      .get_all_connections()
+      .iter()
+      .flat_map(|(connection_character, connected_state_keys)| {
+        connected_state_keys.iter().map(move |connected_state_key| (connection_character, connected_state_key))
+      })
+      .collect();
+
+    connections.extend(sorted_connections
+      .into_iter()
+      .map(|(_, connected_state_key)| (*state_key, connected_state_key.as_str())));
+  }
+
+  let coordinates = create_layered_layout(&state_keys, &start_state_keys, &connections);
+
+  state_positions
+    .iter()
+    .map(|(state_key, state)| {
+      let mut laid_out_state = state.to_owned();
+      laid_out_state.set_position(coordinates[state_key.as_str()]);
+
+      (state_key.to_owned(), laid_out_state)
+    })
+    .collect()
+
+}
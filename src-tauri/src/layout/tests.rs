@@ -0,0 +1,76 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::layout::layout_state_positions;
+  use crate::models::{Coordinate, State};
+  use std::collections::{BTreeSet, HashMap};
+
+  // States are created far from where the layout places them, with q0 as the start state, and are returned alongside their keys
+  fn create_state_positions(number_of_states: i32, transitions: &[(i32, &str, i32)]) -> (HashMap<String, State>, Vec<String>) {
+    let state_keys: Vec<String> = (0..number_of_states).map(|state_number| format!("{},{}", 2000 - 100 * state_number, 50)).collect();
+
+    let mut state_positions: HashMap<String, State> = state_keys
+      .iter()
+      .enumerate()
+      .map(|(state_number, state_key)| {
+        let coordinate: Coordinate = state_key.try_into().unwrap();
+        (state_key.to_owned(), State::new(coordinate, state_number == 0, false))
+      })
+      .collect();
+
+    for (from, connection_character, to) in transitions {
+      state_positions
+        .get_mut(&state_keys[*from as usize])
+        .unwrap()
+        .add_connection(connection_character, state_keys[*to as usize].to_owned());
+    }
+
+    (state_positions, state_keys)
+  }
+
+  // States keep their keys, so each one is found where it was before and only its position changes
+  fn get_laid_out_position(state_positions: &HashMap<String, State>, state_key: &str) -> String {
+    state_positions[state_key].get_position_as_string()
+  }
+
+  #[test]
+  fn test_layered_layout() {
+    // q4 cannot be reached from the start state, so it starts a column of its own alongside q0
+    let (state_positions, state_keys) = create_state_positions(5, &[(0, "a", 1), (0, "b", 2), (1, "a", 3), (2, "a", 3), (3, "b", 3)]);
+    let laid_out_state_positions = layout_state_positions(&state_positions);
+
+    assert_eq!(laid_out_state_positions.len(), 5);
+    assert_eq!(laid_out_state_positions.keys().collect::<BTreeSet<_>>(), state_positions.keys().collect());
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[0]), "300,300");
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[1]), "500,300");
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[2]), "500,500");
+    // Columns shorter than the tallest are centred
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[3]), "700,400");
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[4]), "300,500");
+
+    // Moving states leaves their connections alone
+    for (state_key, state) in &state_positions {
+      assert_eq!(laid_out_state_positions[state_key].get_all_connections(), state.get_all_connections());
+    }
+
+    // Laying out the same automaton again changes nothing
+    for (state_key, state) in layout_state_positions(&laid_out_state_positions) {
+      assert_eq!(state.get_position(), laid_out_state_positions[&state_key].get_position());
+    }
+  }
+
+  #[test]
+  fn test_layered_layout_ordering() {
+    // q4 is found before q5 when searching from q1, but q5 only connects to q1 whereas q4 also connects to q3 at the bottom,
+    // so q5 is placed above q4 to stop their connections crossing
+    let (state_positions, state_keys) = create_state_positions(6, &[(0, "a", 1), (0, "b", 2), (0, "c", 3), (1, "a", 4), (1, "b", 5), (3, "a", 4)]);
+    let laid_out_state_positions = layout_state_positions(&state_positions);
+
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[1]), "500,300");
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[2]), "500,500");
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[3]), "500,700");
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[5]), "700,400");
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[4]), "700,600");
+    assert_eq!(get_laid_out_position(&laid_out_state_positions, &state_keys[0]), "300,500");
+  }
+
+}
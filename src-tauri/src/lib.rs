@@ -3,8 +3,11 @@ extern crate dotenv;
 
 pub mod schema;
 pub mod models;
+pub mod layout;
+pub mod labels;
+mod tests;
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
 use std::{collections::HashMap, fs};
 use std::io::Write;
 use std::env;
@@ -17,7 +20,7 @@ pub fn establish_connection() -> SqliteConnection {
     .unwrap_or_else(|_| panic!("Error connecting to database"))
 }
 
-use models::{BezierCurve, Connection, Coordinate, State, User};
+use models::{join_connection_characters, BezierCurve, Connection, Coordinate, State, TypeOfAutomata, User};
 
 use magic_crypt::{MagicCrypt256, MagicCryptTrait};
 pub fn encrypt_user_data(cipher: &MagicCrypt256, email: &str, password: &str) -> [String; 2] {
@@ -111,66 +114,286 @@ pub fn create_unique_state_coordinates(state_positions: &HashSet<String>) -> Coo
   };
 }
 
+// Every character moving between the same two states shares a single connection, labelled with all of them as in "a,b".
+// Connections between two states in both directions are bent apart so they do not overlap, and self loops are placed
+// in the widest gap between the state's other connections
 pub fn create_connections_from_state_positions(state_positions: &HashMap<String, State>) -> Vec<Connection> {
 
-  let mut connections = vec![];
+  let mut characters_by_endpoints: BTreeMap<(&String, &String), BTreeSet<&String>> = BTreeMap::new();
 
-  // quite slow but okay as most graphs used will be quite sparse
   for (current_state_key, current_state) in state_positions {
-
     for (connection_character, connected_state_keys) in current_state.get_all_connections() {
       for connected_state_key in connected_state_keys {
-        let is_connected_to_self: bool = current_state_key == connected_state_key;
-
-        let end_point: Coordinate = connected_state_key
-          .try_into()
-          .expect("Could not parse given key to coordinates");
-
-        let current_position = current_state.get_position();
-
-        let new_bezier_curve = BezierCurve {
-          start_point: 
-          current_position,
-
-          control_point_one: 
-            if is_connected_to_self { 
-              Coordinate {
-              x: current_position.x - 200,
-              y: current_position.y + 200
-              } 
-            } else {
-              current_position
-            },
-
-          control_point_two:
-            if is_connected_to_self 
-            { Coordinate {
-              x: end_point.x - 200,
-              y: end_point.y - 200
-            } } else 
-            {end_point},
-          
-          end_point
-        };
-
-        let new_connection = Connection {
-          connection_character: connection_character
-            .to_owned(),
-          curve: new_bezier_curve,
-          element: String::from("Connection")
-        };
-
-        connections.push(new_connection)
+        characters_by_endpoints
+          .entry((current_state_key, connected_state_key))
+          .or_default()
+          .insert(connection_character);
       }
+    }
+  }
+
+  let mut connections = vec![];
 
+  for ((current_state_key, connected_state_key), connection_characters) in &characters_by_endpoints {
+    let (start_point, end_point) = match (state_positions.get(*current_state_key), state_positions.get(*connected_state_key)) {
+      (Some(current_state), Some(connected_state)) => (current_state.get_position(), connected_state.get_position()),
+      _ => continue
+    };
+
+    let curve = if current_state_key == connected_state_key {
+      // Every other state this state is connected to in either direction, plus the arrow marking a start state from the left
+      let mut neighbouring_positions: Vec<Coordinate> = characters_by_endpoints
+        .keys()
+        .filter(|(from, to)| from != to && (from == current_state_key || to == current_state_key))
+        .map(|(from, to)| if from == current_state_key { *to } else { *from })
+        .filter_map(|state_key| state_positions.get(state_key).map(|state| state.get_position()))
+        .collect();
+
+      if state_positions.get(*current_state_key).is_some_and(|state| state.is_start()) {
+        neighbouring_positions.push(Coordinate { x: start_point.x - 1, y: start_point.y });
+      }
+
+      create_self_loop_curve(start_point, &neighbouring_positions)
+    } else if characters_by_endpoints.contains_key(&(*connected_state_key, *current_state_key)) {
+      create_bent_curve(start_point, end_point)
+    } else {
+      BezierCurve {
+        start_point,
+        control_point_one: start_point,
+        control_point_two: end_point,
+        end_point
+      }
+    };
+
+    connections.push(Connection {
+      connection_character: join_connection_characters(connection_characters.iter().map(|c| c.as_str())),
+      curve,
+      element: String::from("Connection"),
+      start_state_id: current_state_key.to_string(),
+      end_state_id: connected_state_key.to_string()
+    });
+  }
+
+  connections
+
+}
+
+// How far a connection is pushed to one side when there is another connection going the opposite way
+const BEND_DISTANCE: f64 = 60.0;
+// The same size of loop the canvas draws, with its control points 200 pixels out and 200 pixels to either side
+const SELF_LOOP_CONTROL_DISTANCE: f64 = 282.8;
+
+// Bent to the left of the direction of travel, so the connection going the opposite way is bent to the other side
+fn create_bent_curve(start_point: Coordinate, end_point: Coordinate) -> BezierCurve {
+
+  let (dx, dy) = ((end_point.x - start_point.x) as f64, (end_point.y - start_point.y) as f64);
+  let length = (dx * dx + dy * dy).sqrt();
+
+  // States sharing a position have no direction between them to bend away from
+  if length == 0.0 {
+    return BezierCurve { start_point, control_point_one: start_point, control_point_two: end_point, end_point };
+  }
+  let (normal_x, normal_y) = (dy / length * BEND_DISTANCE, -dx / length * BEND_DISTANCE);
+
+  let point_along = |fraction: f64| Coordinate {
+    x: (start_point.x as f64 + dx * fraction + normal_x).round() as i32,
+    y: (start_point.y as f64 + dy * fraction + normal_y).round() as i32
+  };
+
+  BezierCurve {
+    start_point,
+    control_point_one: point_along(1.0 / 3.0),
+    control_point_two: point_along(2.0 / 3.0),
+    end_point
+  }
+
+}
+
+// The loop points into the middle of the widest gap between the directions of the given positions, or upwards if there are none
+fn create_self_loop_curve(position: Coordinate, neighbouring_positions: &[Coordinate]) -> BezierCurve {
+
+  let mut angles: Vec<f64> = neighbouring_positions
+    .iter()
+    .filter(|neighbouring_position| **neighbouring_position != position)
+    .map(|neighbouring_position| ((neighbouring_position.y - position.y) as f64).atan2((neighbouring_position.x - position.x) as f64))
+    .collect();
+  angles.sort_by(|first, second| first.total_cmp(second));
+
+  let direction = match angles.first() {
+    None => -std::f64::consts::FRAC_PI_2,
+    Some(first_angle) => {
+      // The gap between the last angle and the first wraps around the circle
+      let mut widest_gap = (first_angle + std::f64::consts::TAU - angles[angles.len() - 1], angles[angles.len() - 1]);
+
+      for pair in angles.windows(2) {
+        if pair[1] - pair[0] > widest_gap.0 {
+          widest_gap = (pair[1] - pair[0], pair[0]);
+        }
+      }
+
+      widest_gap.1 + widest_gap.0 / 2.0
     }
+  };
 
+  let control_point = |angle: f64| Coordinate {
+    x: (position.x as f64 + SELF_LOOP_CONTROL_DISTANCE * angle.cos()).round() as i32,
+    y: (position.y as f64 + SELF_LOOP_CONTROL_DISTANCE * angle.sin()).round() as i32
   };
 
-  return connections;
+  BezierCurve {
+    start_point: position,
+    control_point_one: control_point(direction - std::f64::consts::FRAC_PI_4),
+    control_point_two: control_point(direction + std::f64::consts::FRAC_PI_4),
+    end_point: position
+  }
 
 }
 
+// Every character used by at least one connection, excluding ϵ, in sorted order
+pub fn get_alphabet_from_state_positions(state_positions: &HashMap<String, State>) -> BTreeSet<String> {
+
+  let mut alphabet = BTreeSet::new();
+
+  for state in state_positions.values() {
+    alphabet.extend(state
+      .get_all_connections()
+      .keys()
+      .filter(|connection_character| *connection_character != "ϵ")
+      .cloned());
+  }
+
+  alphabet
+
+}
+
+// Used when importing automata from formats which do not say whether they are a DFA or NFA.
+// An automaton is only a DFA if it has at most one start state, no ϵ transitions and at most one transition per character from each state
+pub fn get_type_of_automata_from_state_positions(state_positions: &HashMap<String, State>) -> TypeOfAutomata {
+
+  let number_of_start_states = state_positions
+    .values()
+    .filter(|state| state.is_start())
+    .count();
+
+  let has_nondeterministic_connection = state_positions
+    .values()
+    .flat_map(|state| state.get_all_connections())
+    .any(|(connection_character, connected_state_keys)| {
+      (connection_character == "ϵ" && !connected_state_keys.is_empty()) || connected_state_keys.len() > 1
+    });
+
+  if number_of_start_states <= 1 && !has_nondeterministic_connection {
+    TypeOfAutomata::DFA
+  } else {
+    TypeOfAutomata::NFA
+  }
+
+}
+
+// States are numbered 0, 1, ... in the order they are reached by a breadth first search from the start states, as is usual
+// in textbooks, with any unreachable states numbered afterwards in sorted order of their keys
+pub fn create_state_numbers(state_positions: &HashMap<String, State>) -> BTreeMap<String, usize> {
+
+  let mut sorted_state_keys: Vec<&String> = state_positions.keys().collect();
+  sorted_state_keys.sort();
+
+  // Every start state is numbered before any state reachable from them, so that NFAs with several start states
+  // have them numbered q0, q1, ...
+  let start_state_keys: Vec<&String> = sorted_state_keys
+    .iter()
+    .filter(|state_key| state_positions[**state_key].is_start())
+    .copied()
+    .collect();
+  let starting_points = std::iter::once(start_state_keys)
+    .chain(sorted_state_keys.iter().map(|state_key| vec![*state_key]));
+
+  let mut state_numbers = BTreeMap::new();
+
+  for starting_point in starting_points {
+    let mut queue = VecDeque::new();
+
+    for state_key in starting_point {
+      if !state_numbers.contains_key(state_key) {
+        state_numbers.insert(state_key.to_string(), state_numbers.len());
+        queue.push_back(state_key);
+      }
+    }
+
+    while let Some(state_key) = queue.pop_front() {
+
+      // Characters are visited in sorted order so the same automaton is always numbered the same way
+      let sorted_connections: BTreeMap<&String, BTreeSet<&String>> = state_positions[state_key]
+        .get_all_connections()
+        .iter()
+        .map(|(connection_character, connected_state_keys)| (connection_character, connected_state_keys.iter().collect()))
+        .collect();
+
+      for connected_state_key in sorted_connections.values().flatten() {
+        if state_positions.contains_key(*connected_state_key) && !state_numbers.contains_key(*connected_state_key) {
+          state_numbers.insert(connected_state_key.to_string(), state_numbers.len());
+          queue.push_back(connected_state_key);
+        }
+      }
+    }
+  }
+
+  state_numbers
+
+}
+
+// States keep the names given to them by the user. The rest are named after their number as in q3,
+// with primes added should the user have already given that name to another state
+pub fn create_state_names(state_positions: &HashMap<String, State>) -> BTreeMap<String, String> {
+
+  let state_numbers = create_state_numbers(state_positions);
+  let mut taken_names: HashSet<String> = state_positions.values().filter_map(|state| state.get_name()).map(String::from).collect();
+
+  let mut states_in_number_order: Vec<(&String, &usize)> = state_numbers.iter().collect();
+  states_in_number_order.sort_by_key(|(_, state_number)| **state_number);
+
+  states_in_number_order
+    .into_iter()
+    .map(|(state_key, state_number)| {
+      let state_name = match state_positions[state_key].get_name() {
+        Some(state_name) => state_name.to_owned(),
+        None => {
+          let mut state_name = format!("q{state_number}");
+          while taken_names.contains(&state_name) {
+            state_name.push('\'');
+          }
+          taken_names.insert(state_name.to_owned());
+          state_name
+        }
+      };
+
+      (state_key.to_owned(), state_name)
+    })
+    .collect()
+
+}
+
+// Names are compared exactly, so q0 and Q0 are different names. Returns the first name used by more than one state
+pub fn find_duplicate_state_name(state_positions: &HashMap<String, State>) -> Option<String> {
+
+  let mut seen_names = HashSet::new();
+
+  let mut state_names: Vec<&str> = state_positions.values().filter_map(|state| state.get_name()).collect();
+  state_names.sort();
+
+  state_names
+    .into_iter()
+    .find(|state_name| !seen_names.insert(*state_name))
+    .map(|state_name| state_name.to_owned())
+
+}
+
+// Escapes a string so it can be placed inside a double quoted Graphviz DOT string
+pub fn escape_dot_string(string: &str) -> String {
+  string
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+}
+
 pub fn remove_all_epsilon_transitions(state_positions: &mut HashMap<String, State>) {
 
   let mut make_final;
@@ -230,14 +453,16 @@ pub fn remove_all_epsilon_transitions(state_positions: &mut HashMap<String, Stat
   }
 }
 
-// An input alphabet must consist of entirely unique characters and should be at most a single character long
+// An input alphabet must consist of entirely unique symbols, each of which may be any non empty string other than ϵ,
+// which is reserved for epsilon transitions, so symbols like 0x, if or a comma are all allowed
 // We want to preserve the order of the alphabet for ease of use thus hashsets are not an option, thus we will iterate
 // Over everything and ensure it fits our requirements
 pub fn sanitize_input_alphabet(alphabet: Vec<&str>) -> Vec<String> {
 
   let mut previously_seen_input_characters = HashSet::new();
   let sanitized_alphabet = alphabet.into_iter().filter(|input_character| {
-    if !previously_seen_input_characters.contains(input_character) && input_character.len() == 1 {
+    let is_valid_symbol = !input_character.is_empty() && *input_character != "ϵ";
+    if !previously_seen_input_characters.contains(input_character) && is_valid_symbol {
       previously_seen_input_characters.insert(input_character.to_owned());
       return true;
     }
@@ -249,4 +474,10 @@ pub fn sanitize_input_alphabet(alphabet: Vec<&str>) -> Vec<String> {
   .collect();
 
   return sanitized_alphabet;
+}
+
+// The form alphabets are stored in, see WorkspaceData::parse_alphabet
+pub fn serialize_input_alphabet(alphabet: &[String]) -> String {
+  serde_json::to_string(alphabet)
+    .expect("A list of strings should always be serializable")
 }
\ No newline at end of file
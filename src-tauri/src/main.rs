@@ -18,16 +18,44 @@ pub mod registration_funcs;
 pub mod language_determination_funcs;
 pub mod verification_funcs;
 pub mod regular_expression_funcs;
+pub mod string_generation_funcs;
+pub mod string_sampling_funcs;
+pub mod workspace_file_funcs;
+pub mod jflap_funcs;
+pub mod graphviz_funcs;
+pub mod svg_rendering_funcs;
+pub mod latex_funcs;
+pub mod transition_table_funcs;
+pub mod automaton_dsl_funcs;
+pub mod code_generation_funcs;
+pub mod alphabet_funcs;
+pub mod revision_funcs;
+#[cfg(test)]
+mod test_fixtures;
 
-use regular_expression_funcs::{build_parse_tree, test_string_regex};
+use regular_expression_funcs::{build_parse_tree, test_string_regex, test_string_regex_with_derivatives, convert_regex_to_dfa, 
+compute_regex_position_tables, convert_regex_to_position_automaton, export_parse_tree, export_parse_tree_to_dot};
 use registration_funcs::{is_correct_log_in, register_user, is_user_registered};
-use advanced_automata_funcs::{minimize_dfa, convert_nfa_to_dfa};
-use testing_automata_funcs::{test_string_dfa, test_string_nfa};
+use advanced_automata_funcs::{minimize_dfa, convert_nfa_to_dfa, auto_layout_workspace};
+use testing_automata_funcs::{test_string_dfa, test_string_nfa, test_symbols_dfa, test_symbols_nfa, tokenize_input_string};
 use saving_automata_funcs::{save_workspace, delete_workspace, retrieve_workspace_data, get_users_saved_workspaces, 
 update_workspace_name, does_workspace_name_exist, create_workspace, update_workspace_alphabet, 
-update_showing_string_traversal, update_default_connection_character, update_strict_checking, update_automata_type};
+update_showing_string_traversal, update_default_connection_character, update_strict_checking, update_automata_type, rename_state,
+update_input_separator, update_max_revisions};
 use validation_automata_funcs::verify_valid_dfa;
-use language_determination_funcs::determine_language_of_automata;
+use string_generation_funcs::{generate_strings_from_automata, generate_strings_from_regex};
+use string_sampling_funcs::sample_accepted_string;
+use workspace_file_funcs::{export_workspace, import_workspace};
+use jflap_funcs::{import_jflap_file, export_workspace_to_jflap};
+use graphviz_funcs::{export_workspace_to_dot, import_dot_file};
+use svg_rendering_funcs::{render_workspace_to_svg, export_workspace_to_svg};
+use latex_funcs::{export_workspace_to_tikz, export_workspace_formal_definition, export_workspace_to_latex_file};
+use transition_table_funcs::{export_workspace_to_transition_table, import_transition_table_file};
+use automaton_dsl_funcs::{parse_automaton_dsl, create_workspace_from_automaton_dsl, export_workspace_to_automaton_dsl};
+use code_generation_funcs::generate_matcher_from_workspace;
+use alphabet_funcs::{edit_workspace_alphabet, infer_workspace_alphabet};
+use revision_funcs::{get_workspace_revisions, preview_workspace_revision, restore_workspace_revision, diff_workspace_revisions};
+use language_determination_funcs::{determine_language_of_automata, count_language};
 use verification_funcs::{send_verification_email, is_user_verified, verify_user};
 use app::establish_connection;
 
@@ -54,7 +82,17 @@ fn main() {
     test_string_nfa, verify_valid_dfa, save_workspace, delete_workspace, retrieve_workspace_data, 
     get_users_saved_workspaces, minimize_dfa, convert_nfa_to_dfa, determine_language_of_automata, build_parse_tree, 
     test_string_regex, update_workspace_name, does_workspace_name_exist, create_workspace, update_workspace_alphabet, 
-    update_default_connection_character, update_showing_string_traversal, update_strict_checking, update_automata_type]
+    update_default_connection_character, update_showing_string_traversal, update_strict_checking, update_automata_type, rename_state,
+    test_string_regex_with_derivatives, convert_regex_to_dfa, compute_regex_position_tables, convert_regex_to_position_automaton,
+    export_parse_tree, export_parse_tree_to_dot, generate_strings_from_automata, generate_strings_from_regex,
+    sample_accepted_string, count_language, export_workspace, import_workspace,
+    import_jflap_file, export_workspace_to_jflap, export_workspace_to_dot, import_dot_file,
+    render_workspace_to_svg, export_workspace_to_svg, export_workspace_to_tikz, export_workspace_formal_definition,
+    export_workspace_to_latex_file, export_workspace_to_transition_table, import_transition_table_file,
+    parse_automaton_dsl, create_workspace_from_automaton_dsl, export_workspace_to_automaton_dsl, generate_matcher_from_workspace,
+    auto_layout_workspace, test_symbols_dfa, test_symbols_nfa, tokenize_input_string, update_input_separator,
+    edit_workspace_alphabet, infer_workspace_alphabet, update_max_revisions, get_workspace_revisions, preview_workspace_revision,
+    restore_workspace_revision, diff_workspace_revisions]
   )
   .run(tauri::generate_context!())
   .expect("error while running tauri application");
@@ -30,7 +30,9 @@ pub struct SavedWorkspace {
   pub alphabet: String,
   pub should_show_string_traversal: bool,
   pub should_strict_check: bool,
-  pub default_connection_character: String
+  pub default_connection_character: String,
+  pub input_separator: Option<String>,
+  pub max_revisions: i32
 }
 
 #[derive(Queryable, Selectable, QueryableByName, Insertable)]
@@ -42,7 +44,9 @@ pub struct SavedState {
   pub workspace_id: i32,
   pub position: String,
   pub is_start: bool,
-  pub is_final: bool
+  pub is_final: bool,
+  pub state_id: String,
+  pub name: Option<String>
 }
 
 #[derive(Queryable, Selectable, QueryableByName, Insertable)]
@@ -56,7 +60,20 @@ pub struct SavedConnection {
   pub control_point_one: String,
   pub control_point_two: String,
   pub end_point: String,
-  pub connection_character: String
+  pub connection_character: String,
+  pub start_state_id: String,
+  pub end_state_id: String
+}
+
+#[derive(Queryable, Selectable, QueryableByName, Insertable)]
+#[diesel(table_name = crate::schema::workspace_revisions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[derive(Debug)]
+pub struct SavedRevision {
+  pub id: i32,
+  pub workspace_id: i32,
+  pub date_of_revision: NaiveDateTime,
+  pub snapshot: String
 }
 
 use serde::Deserialize;
@@ -68,8 +85,29 @@ use std::hash::Hash;
 use crate::establish_connection;
 use crate::schema::saved_connections;
 use crate::schema::saved_states;
+mod tests;
+
+// States are identified by an id which stays the same however they are moved, and is what transitions refer to.
+// Every state key in a map of states is the id of the state it holds
 #[derive(Debug, Deserialize, Serialize, Clone, Eq)]
+#[serde(from = "DeserializedState")]
 pub struct State { 
+  id: String,
+  name: Option<String>,
+  position: Coordinate,
+  states_connected_to: HashMap<String, HashSet<String>>,
+  is_start: bool,
+  is_final: bool,
+  element: String
+}
+
+// States drawn on the canvas before ids were introduced are sent without one, and so are identified by their position as they always were
+#[derive(Deserialize)]
+struct DeserializedState {
+  #[serde(default)]
+  id: Option<String>,
+  #[serde(default)]
+  name: Option<String>,
   position: Coordinate,
   states_connected_to: HashMap<String, HashSet<String>>,
   is_start: bool,
@@ -77,10 +115,31 @@ pub struct State {
   element: String
 }
 
+impl From<DeserializedState> for State {
+  fn from(state: DeserializedState) -> Self {
+    State {
+      id: state.id.filter(|id| !id.is_empty()).unwrap_or_else(|| state.position.into()),
+      name: state.name,
+      position: state.position,
+      states_connected_to: state.states_connected_to,
+      is_start: state.is_start,
+      is_final: state.is_final,
+      element: state.element
+    }
+  }
+}
+
 impl State {
 
+  // The state's id is taken from the position it is first placed at, which is unique at the time it is created
   pub fn new(position: Coordinate, is_start: bool, is_final: bool) -> Self {
+    Self::with_id(position, position, is_start, is_final)
+  }
+
+  pub fn with_id(id: impl Into<String>, position: Coordinate, is_start: bool, is_final: bool) -> Self {
     return State {
+      id: id.into(),
+      name: None,
       position,
       states_connected_to: HashMap::new(),
       is_start,
@@ -141,6 +200,18 @@ impl State {
       .get(connection_character);
   }
 
+  pub fn get_id(&self) -> &str {
+    &self.id
+  }
+
+  pub fn get_name(&self) -> Option<&str> {
+    self.name.as_deref()
+  }
+
+  pub fn set_name(&mut self, name: Option<String>) {
+    self.name = name;
+  }
+
   pub fn get_position(&self) -> Coordinate {
     return self.position;
   }
@@ -149,6 +220,11 @@ impl State {
     return self.position.into();
   }
 
+  // Moving a state leaves its id, and so every transition to it, untouched
+  pub fn set_position(&mut self, position: Coordinate) {
+    self.position = position;
+  }
+
   pub fn set_all_connections(&mut self, connections: HashMap<String, HashSet<String>>) {
     self.states_connected_to = connections;
   }
@@ -190,15 +266,14 @@ impl State {
 
 impl PartialEq for State {
   fn eq(&self, other: &Self) -> bool {
-    self.position == other.position
+    self.id == other.id
   }
 }
 
-// We're able to do this as we never have two states assigned to the same position,
-// thus there is a unique position for every state that exists
+// We're able to do this as every state in an automaton has its own id, even if two states share a position
 impl Hash for State {
   fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-    self.position.hash(state);
+    self.id.hash(state);
   }
 }
 
@@ -208,14 +283,80 @@ pub struct Coordinate {
   pub y: i32
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+// The ids of the states at either end are what the connection belongs to, with the curve only saying how it is drawn.
+// Connections drawn on the canvas are sent without them, in which case the states are found from the ends of the curve
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Connection {
   pub curve: BezierCurve,
   pub connection_character: String,
-  pub element: String
+  pub element: String,
+  #[serde(default)]
+  pub start_state_id: String,
+  #[serde(default)]
+  pub end_state_id: String
+}
+
+impl Connection {
+
+  pub fn get_start_state_key(&self, state_positions: &HashMap<String, State>) -> String {
+    find_state_key(state_positions, &self.start_state_id, self.curve.start_point)
+  }
+
+  pub fn get_end_state_key(&self, state_positions: &HashMap<String, State>) -> String {
+    find_state_key(state_positions, &self.end_state_id, self.curve.end_point)
+  }
+
+}
+
+// Falls back to the state at the given end of the curve when the id is missing or unknown, and then to the position itself
+fn find_state_key(state_positions: &HashMap<String, State>, state_id: &String, position: Coordinate) -> String {
+  if state_positions.contains_key(state_id) {
+    return state_id.to_owned();
+  }
+
+  state_positions
+    .iter()
+    .find(|(_, state)| state.get_position() == position)
+    .map(|(state_key, _)| state_key.to_owned())
+    .unwrap_or_else(|| position.into())
+}
+
+// Labels list their characters separated by commas. Commas and backslashes within a character are escaped with a
+// backslash, so {"x,y", "a"} is written x\,y,a and classes such as [,;] keep their commas when splitting
+pub fn join_connection_characters<'a>(connection_characters: impl Iterator<Item = &'a str>) -> String {
+  connection_characters
+    .map(|connection_character| connection_character.replace('\\', "\\\\").replace(',', "\\,"))
+    .collect::<Vec<String>>()
+    .join(",")
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+// A label with nothing in it, as connections have while they are being drawn, is kept as a single empty character.
+// Otherwise empty characters can't label a transition and are skipped
+pub fn split_connection_label(label: &str) -> Vec<String> {
+
+  let mut connection_characters = vec![];
+  let mut connection_character = String::new();
+  let mut characters = label.chars();
+
+  while let Some(c) = characters.next() {
+    match c {
+      // A backslash at the very end escapes nothing, so is kept as it is
+      '\\' => connection_character.push(characters.next().unwrap_or('\\')),
+      ',' => connection_characters.push(std::mem::take(&mut connection_character)),
+      c => connection_character.push(c)
+    }
+  }
+  connection_characters.push(connection_character);
+
+  if connection_characters.len() > 1 {
+    connection_characters.retain(|connection_character| !connection_character.is_empty());
+  }
+
+  connection_characters
+
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct BezierCurve {
   pub start_point: Coordinate,
   pub control_point_one: Coordinate,
@@ -292,7 +433,7 @@ impl TryFrom<&String> for Coordinate {
   }
 }
 
-#[derive(Debug, Deserialize, Serialize, diesel_derive_enum::DbEnum)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, diesel_derive_enum::DbEnum)]
 #[DbValueStyle = "UPPERCASE"]
 pub enum TypeOfAutomata {
   DFA,
@@ -302,8 +443,13 @@ pub enum TypeOfAutomata {
 #[derive(Debug, Deserialize, Serialize, )]
 pub struct WorkspaceData {
   
+  // Results written to a new workspace may be saved under a different name than the one asked for if it was taken
+  workspace_name: String,
   start_state_index: Option<usize>,
+  // Despite its name this is the id of the start state, which is only its position for states which have never been moved
   start_state_position: Option<String>,
+  // NFAs may have several initial states, the first of which is the one given by start_state_index and start_state_position
+  start_state_positions: Vec<String>,
   state_positions: HashMap<String, State>,
   list_of_states: Vec<State>,
   list_of_connections: Vec<Connection>,
@@ -312,7 +458,9 @@ pub struct WorkspaceData {
   alphabet: Vec<String>,
   should_strict_check: bool,
   should_show_string_traversal: bool,
-  default_connection_character: String
+  default_connection_character: String,
+  input_separator: Option<String>,
+  max_revisions: i32
 
 }
 
@@ -324,6 +472,7 @@ impl WorkspaceData {
 
     let list_of_states = Self::get_list_of_states_from_saved_workspace(&workspace, &mut conn);
     let (start_state_index, start_state_position) = Self::get_start_state_information(&list_of_states);
+    let start_state_positions = Self::get_all_start_state_keys(&list_of_states);
     let list_of_connections = Self::get_list_of_connections_from_saved_workspace(&workspace, &mut conn);
     let state_positions = Self::get_state_positions_from_list_of_states(&list_of_states);
     let alphabet = Self::parse_alphabet(&workspace);
@@ -333,10 +482,14 @@ impl WorkspaceData {
     let should_strict_check = workspace.should_strict_check;
     let should_show_string_traversal = workspace.should_show_string_traversal;
     let default_connection_character = workspace.default_connection_character;
+    let input_separator = workspace.input_separator;
+    let max_revisions = workspace.max_revisions;
     
     return WorkspaceData {
+      workspace_name: workspace.workspace_name,
       start_state_index,
       start_state_position,
+      start_state_positions,
       state_positions,
       list_of_states,
       list_of_connections,
@@ -345,31 +498,120 @@ impl WorkspaceData {
       alphabet,
       should_strict_check,
       should_show_string_traversal,
-      default_connection_character
+      default_connection_character,
+      input_separator,
+      max_revisions
+    }
+
+  }
+
+  // The workspace as it would be with the given automaton saved over it, without saving anything. The transitions of each
+  // state are taken from the connections, which are all that is saved of them, so it matches what saving would give
+  pub fn with_automata(
+    &self,
+    state_positions: HashMap<String, State>,
+    connections: Vec<Connection>,
+    type_of_automata: TypeOfAutomata,
+    alphabet: Vec<String>
+  ) -> Self {
+
+    let mut list_of_states: Vec<State> = state_positions
+      .values()
+      .map(|state| {
+        let mut state = state.to_owned();
+        state.set_all_connections(HashMap::new());
+        state
+      })
+      .collect();
+    list_of_states.sort_by(|first_state, second_state| first_state.get_id().cmp(second_state.get_id()));
+
+    for connection in &connections {
+      let start_state_key = connection.get_start_state_key(&state_positions);
+      let end_state_key = connection.get_end_state_key(&state_positions);
+      if let Some(state) = list_of_states.iter_mut().find(|state| state.get_id() == start_state_key) {
+        for connection_character in split_connection_label(&connection.connection_character) {
+          state.add_connection(&connection_character, end_state_key.to_owned());
+        }
+      }
+    }
+
+    let (start_state_index, start_state_position) = Self::get_start_state_information(&list_of_states);
+
+    WorkspaceData {
+      workspace_name: self.workspace_name.to_owned(),
+      start_state_index,
+      start_state_position,
+      start_state_positions: Self::get_all_start_state_keys(&list_of_states),
+      state_positions: Self::get_state_positions_from_list_of_states(&list_of_states),
+      list_of_states,
+      list_of_connections: connections,
+      type_of_automata,
+      date_of_last_update: self.date_of_last_update.to_owned(),
+      alphabet,
+      should_strict_check: self.should_strict_check,
+      should_show_string_traversal: self.should_show_string_traversal,
+      default_connection_character: self.default_connection_character.to_owned(),
+      input_separator: self.input_separator.to_owned(),
+      max_revisions: self.max_revisions
     }
 
   }
 
+  pub fn get_workspace_name(&self) -> &str {
+    &self.workspace_name
+  }
+
   pub fn get_start_state_position(&self) -> &Option<String> {
     &self.start_state_position
   }
 
+  pub fn get_start_state_positions(&self) -> &Vec<String> {
+    &self.start_state_positions
+  }
+
   pub fn get_state_positions(&self) -> &HashMap<String, State> {
     &self.state_positions
   }
 
-  fn get_state_positions_from_list_of_states(list_of_states: &Vec<State>) -> HashMap<String, State> {
+  pub fn get_list_of_connections(&self) -> &Vec<Connection> {
+    &self.list_of_connections
+  }
 
-    let mut state_positions = HashMap::new();
+  pub fn get_type_of_automata(&self) -> TypeOfAutomata {
+    self.type_of_automata
+  }
 
+  pub fn get_alphabet(&self) -> &Vec<String> {
+    &self.alphabet
+  }
 
-    for state in list_of_states {
+  pub fn should_strict_check(&self) -> bool {
+    self.should_strict_check
+  }
 
-      let state_coords_as_string: String = state.position
-        .into();
+  pub fn should_show_string_traversal(&self) -> bool {
+    self.should_show_string_traversal
+  }
+
+  pub fn get_default_connection_character(&self) -> &str {
+    &self.default_connection_character
+  }
 
-      state_positions.insert(state_coords_as_string.to_owned(), state.to_owned());
+  pub fn get_input_separator(&self) -> &Option<String> {
+    &self.input_separator
+  }
+
+  pub fn get_max_revisions(&self) -> i32 {
+    self.max_revisions
+  }
 
+  fn get_state_positions_from_list_of_states(list_of_states: &Vec<State>) -> HashMap<String, State> {
+
+    let mut state_positions = HashMap::new();
+
+
+    for state in list_of_states {
+      state_positions.insert(state.get_id().to_owned(), state.to_owned());
     }
 
     return state_positions;
@@ -400,17 +642,21 @@ impl WorkspaceData {
     
     let states_connected_to_given_state: Vec<SavedConnection> = saved_connections::table
       .filter(saved_connections::workspace_id.eq(&workspace.id))
-      .filter(saved_connections::start_point.eq(&state.position))
+      .filter(saved_connections::start_state_id.eq(&state.state_id))
       .get_results::<SavedConnection>(conn)
       .expect("There was an issue getting the workspace's states");
 
     let parsed_state_position = state.position.try_into()
       .expect("The string should be castable into Coordinate form");
   
-    let mut parsed_state = State::new(parsed_state_position, state.is_start, state.is_final);
+    let mut parsed_state = State::with_id(state.state_id, parsed_state_position, state.is_start, state.is_final);
+    parsed_state.set_name(state.name);
   
+    // Characters sharing a connection are stored together in a single label
     for connected_state in states_connected_to_given_state {
-      parsed_state.add_connection(&connected_state.connection_character, connected_state.end_point);
+      for connection_character in split_connection_label(&connected_state.connection_character) {
+        parsed_state.add_connection(&connection_character, connected_state.end_state_id.to_owned());
+      }
     }
     parsed_state
   }
@@ -445,7 +691,9 @@ impl WorkspaceData {
     let parsed_connection = Connection {
       curve: parsed_curve,
       connection_character: connection.connection_character,
-      element: String::from("Connection")
+      element: String::from("Connection"),
+      start_state_id: connection.start_state_id,
+      end_state_id: connection.end_state_id
     };
 
     parsed_connection
@@ -457,7 +705,7 @@ impl WorkspaceData {
   fn get_start_state_information(list_of_states: &Vec<State>) -> (Option<usize>, Option<String>) {
     for (index, state_reference) in list_of_states.iter().enumerate() {
       if state_reference.is_start() {
-        return (Some(index), Some(state_reference.get_position_as_string()));
+        return (Some(index), Some(state_reference.get_id().to_owned()));
       }
   
     }
@@ -465,10 +713,25 @@ impl WorkspaceData {
   
   }
 
+  fn get_all_start_state_keys(list_of_states: &[State]) -> Vec<String> {
+    list_of_states
+      .iter()
+      .filter(|state| state.is_start())
+      .map(|state| state.get_id().to_owned())
+      .collect()
+  }
+
+  // Alphabets are stored as JSON arrays so that any symbol can be stored, including commas. The column's default
+  // is still the comma separated a,b it had before, and is read the old way
   fn parse_alphabet(saved_workspace: &SavedWorkspace) -> Vec<String> {
 
+    if let Ok(alphabet) = serde_json::from_str(&saved_workspace.alphabet) {
+      return alphabet;
+    }
+
     return saved_workspace.alphabet
       .split(',')
+      .filter(|s| !s.is_empty())
       .map(|s| s.to_string())
       .collect();
 
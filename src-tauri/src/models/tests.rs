@@ -0,0 +1,51 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::models::{join_connection_characters, split_connection_label, Connection, Coordinate, State};
+  use std::collections::HashMap;
+
+  #[test]
+  fn test_connection_labels() {
+    for connection_characters in [
+      vec!["a"], vec!["a", "b"], vec![","], vec![",", "a"], vec!["!", ",", "a"], vec!["a", ","], vec!["ab", "ϵ"],
+      vec!["x,y"], vec!["a,"], vec![",a"], vec!["x,y", "a,", ",a"], vec!["\\"], vec!["\\,", ",\\"], vec!["[,;]", "[\\],]"]
+    ] {
+      let label = join_connection_characters(connection_characters.iter().copied());
+      assert_eq!(split_connection_label(&label), connection_characters, "Failed on {label}");
+    }
+
+    assert_eq!(join_connection_characters(["!", ",", "a"].into_iter()), "!,\\,,a");
+    assert_eq!(join_connection_characters(["x,y", "\\"].into_iter()), "x\\,y,\\\\");
+
+    // An empty label stays a single empty character, empty characters between commas are skipped and a backslash escaping nothing is kept
+    assert_eq!(split_connection_label(""), vec![""]);
+    assert_eq!(split_connection_label("a,,b"), vec!["a", "b"]);
+    assert_eq!(split_connection_label("a\\"), vec!["a\\"]);
+  }
+
+  #[test]
+  fn test_stable_state_ids() {
+    // Two states can share a position, and moving one leaves the transitions pointing at it untouched
+    let position = Coordinate { x: 300, y: 300 };
+    let mut first = State::with_id("first", position, true, false);
+    let second = State::with_id("second", position, false, true);
+    first.add_connection("a", "second");
+    assert_ne!(first, second);
+
+    let mut state_positions = HashMap::from([(String::from("first"), first), (String::from("second"), second)]);
+    state_positions.get_mut("second").unwrap().set_position(Coordinate { x: 500, y: 300 });
+    assert_eq!(state_positions["first"].get_first_connected_state_key_by_character("a"), Some(String::from("second")));
+
+    // Connections drawn on the canvas come without ids, so their states are found from the ends of the curve
+    let drawn_connection: Connection = serde_json::from_str(r#"{"curve":{"start_point":{"x":300,"y":300},
+      "control_point_one":{"x":400,"y":300},"control_point_two":{"x":400,"y":300},"end_point":{"x":500,"y":300}},
+      "connection_character":"a","element":"Connection"}"#).unwrap();
+    assert_eq!(drawn_connection.get_start_state_key(&state_positions), "first");
+    assert_eq!(drawn_connection.get_end_state_key(&state_positions), "second");
+
+    // States sent without an id, as older versions of the frontend did, are identified by their position
+    let state: State = serde_json::from_str(r#"{"position":{"x":700,"y":300},"states_connected_to":{},"is_start":false,"is_final":false,"element":"State"}"#).unwrap();
+    assert_eq!(state.get_id(), "700,300");
+    assert_eq!(state.get_name(), None);
+  }
+
+}
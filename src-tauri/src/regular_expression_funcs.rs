@@ -1,17 +1,35 @@
-mod regex_models;
-use std::collections::HashMap;
+pub mod regex_models;
+mod derivatives;
+mod position_automaton;
+mod parse_tree_export;
+use std::collections::{BTreeSet, HashMap};
 
-use app::{create_unique_state_coordinates, remove_all_epsilon_transitions};
+use app::{create_connections_from_state_positions, create_unique_state_coordinates};
 use regex_models::{BinaryOperator, ConcatenatedExpression, KleeneOperator, Operator, OrOperator, ParsingError, Token, UnaryOperator};
+use derivatives::{build_dfa_from_parse_tree, DerivativeExpression};
+use position_automaton::PositionTables;
+use parse_tree_export::ParseTreeGraph;
 
-use app::models::{State, Coordinate};
+use app::layout::layout_state_positions;
+use app::models::{State, Coordinate, TypeOfAutomata, WorkspaceData};
 
-use crate::{advanced_automata_funcs::reconstruct_nfa_state_positions, testing_automata_funcs::test_string_nfa};
+use crate::{advanced_automata_funcs::determinize_state_positions, testing_automata_funcs::test_string_nfa};
+use crate::saving_automata_funcs::{save_automata_to_target, WorkspaceTarget};
 mod tests;
 
 #[tauri::command]
 pub fn test_string_regex(regex: &str, string_to_check: String) -> Result<bool, ParsingError> {
 
+  let (state_positions, start_state_key) = convert_regex_to_state_positions(regex)?;
+
+  Ok(test_string_nfa(state_positions, start_state_key, string_to_check).0)
+
+}
+
+// Converts the regex into an NFA via Thompson's construction, then determinizes it.
+// Returns the state positions alongside the key of the start state
+pub fn convert_regex_to_state_positions(regex: &str) -> Result<(HashMap<String, State>, String), ParsingError> {
+
   let parse_tree = build_parse_tree(regex)?;
 
   let mut state_positions = HashMap::new();
@@ -26,18 +44,88 @@ pub fn test_string_regex(regex: &str, string_to_check: String) -> Result<bool, P
 
   convert_parse_tree_to_nfa(
     &mut state_positions,
-    start_state_coords.into(),
+    start_state_coords,
     parse_tree,
     end_state_coords
   );
 
-  remove_all_epsilon_transitions(&mut state_positions);
-
   let start_state_key: String = start_state_coords.into();
 
-  let state_positions = reconstruct_nfa_state_positions(&state_positions, &start_state_key);
+  let mut state_positions = determinize_state_positions(state_positions, &start_state_key);
+
+  // The states of the NFA are never shown, so naming the DFA's states after them would mean nothing to the user
+  for state in state_positions.values_mut() {
+    state.set_name(None);
+  }
+
+  Ok((state_positions, start_state_key))
+
+}
+
+// Alternative to test_string_regex which never builds an automaton, instead repeatedly taking the
+// Brzozowski derivative of the regex with respect to each character of the string
+#[tauri::command]
+pub fn test_string_regex_with_derivatives(regex: &str, string_to_check: String) -> Result<bool, ParsingError> {
+
+  let parse_tree = build_parse_tree(regex)?;
+  let expression = DerivativeExpression::from(&parse_tree);
+
+  Ok(expression.matches(&string_to_check))
+
+}
+
+#[tauri::command]
+pub fn convert_regex_to_dfa(regex: &str, email: &str, workspace_name: &str, target: WorkspaceTarget) -> Result<WorkspaceData, ParsingError> {
+
+  let parse_tree = build_parse_tree(regex)?;
+  let alphabet = DerivativeExpression::from(&parse_tree).get_alphabet();
+
+  let (state_positions, _) = build_dfa_from_parse_tree(&parse_tree);
+  let state_positions = layout_state_positions(&state_positions);
+  let connections = create_connections_from_state_positions(&state_positions);
+
+  Ok(save_automata_to_target(
+    workspace_name,
+    email,
+    target,
+    state_positions,
+    connections,
+    Some(TypeOfAutomata::DFA),
+    Some(alphabet.iter().map(|c| c.to_string()).collect())
+  ))
+
+}
+
+#[tauri::command]
+pub fn compute_regex_position_tables(regex: &str) -> Result<PositionTables, ParsingError> {
+
+  let parse_tree = build_parse_tree(regex)?;
+
+  Ok(PositionTables::new(&parse_tree))
+
+}
+
+// Unlike the Thompson construction, the position automaton has no ϵ transitions and exactly one state per literal in the regex
+#[tauri::command]
+pub fn convert_regex_to_position_automaton(regex: &str, email: &str, workspace_name: &str, target: WorkspaceTarget) -> Result<WorkspaceData, ParsingError> {
 
-  return Ok(test_string_nfa(state_positions, start_state_coords.into(), string_to_check).0);
+  let parse_tree = build_parse_tree(regex)?;
+  let position_tables = PositionTables::new(&parse_tree);
+  let alphabet: BTreeSet<&String> = position_tables.symbols_by_position.values().collect();
+
+  let (state_positions, _) = position_tables.build_position_automaton();
+  let state_positions = layout_state_positions(&state_positions);
+  let connections = create_connections_from_state_positions(&state_positions);
+
+  Ok(save_automata_to_target(
+    workspace_name,
+    email,
+    target,
+    state_positions,
+    connections,
+    Some(TypeOfAutomata::NFA),
+    Some(alphabet.iter().map(|c| c.to_string()).collect())
+  ))
 
 }
 
@@ -49,6 +137,18 @@ pub fn build_parse_tree(regex: &str) -> Result<Token, ParsingError> {
   return Ok(parse_tree);
 }
 
+#[tauri::command]
+pub fn export_parse_tree(regex: &str) -> Result<ParseTreeGraph, ParsingError> {
+  let parse_tree = build_parse_tree(regex)?;
+  Ok(ParseTreeGraph::new(&parse_tree, regex))
+}
+
+#[tauri::command]
+pub fn export_parse_tree_to_dot(regex: &str) -> Result<String, ParsingError> {
+  let parse_tree = build_parse_tree(regex)?;
+  Ok(ParseTreeGraph::new(&parse_tree, regex).to_dot())
+}
+
 fn convert_parse_tree_to_nfa(
   state_positions: &mut HashMap<String, State>, 
   current_state_coords: Coordinate, 
@@ -66,7 +166,7 @@ fn convert_parse_tree_to_nfa(
       state_positions.insert(new_state_coords.into(), new_state.clone());
 
 
-      for c in literal[1..].chars() {
+      for c in literal.chars().skip(1) {
         let next_new_state_coords = create_unique_state_coordinates(&state_positions.keys().cloned().collect());
         new_state
           .add_connection(&c.to_string(), next_new_state_coords);
@@ -184,7 +284,7 @@ fn handle_kleene_token_to_nfa_conversion(
       let mut new_state_coords = first_new_state_coords;
       state_positions.insert(new_state_coords.into(), new_state.clone());
 
-      for c in literal[1..].chars() {
+      for c in literal.chars().skip(1) {
         let next_new_state_coords = create_unique_state_coordinates(&state_positions.keys().cloned().collect());
         new_state
           .add_connection(&c.to_string(), next_new_state_coords);
@@ -418,46 +518,46 @@ fn parse_tokens(mut tokens: Vec<Token>) -> Result<Token, ParsingError> {
     has_kleene_token = does_contain_kleene_operator(&tokens);
   };
 
-  // Very gross code will definitely be rewriten in future
-  let mut finished = false;
-  
-  while !finished {
-    let duplicate_tokens = tokens.clone();
-    finished = true;
-    for (index, token) in duplicate_tokens.into_iter().enumerate() {
-      match token {
-        Token::OrOperator(mut current_or_op) => {
-          if !current_or_op.has_empty_arg() {
-            continue;
-          }
-          finished = false;
-  
-          let left_token = tokens
-            .get(index.checked_sub(1).ok_or_else( || {ParsingError::NoneTokenProvided})?)
-            .cloned();
-    
-          let right_token = tokens
-            .get(index + 1)
-            .cloned();
-  
-          current_or_op
-            .left_insert_token(left_token)?;
-          current_or_op
-            .right_insert_token(right_token)?;
-  
-          tokens.drain(index - 1..=index + 1);
-          tokens.insert(index - 1, Token::OrOperator(current_or_op));
-          break;
-  
-        },
+  // Concatenation binds tighter than union, so every run of tokens between two unfilled or operators
+  // is concatenated into a single token first, and those tokens then become the arguments of the or operators
+  let has_unfilled_or_operator = tokens.iter().any(|token| {
+    matches!(token, Token::OrOperator(or_operator) if or_operator.has_empty_arg())
+  });
 
+  if has_unfilled_or_operator {
+    let mut union_arguments: Vec<Vec<Token>> = vec![vec![]];
+
+    for token in tokens {
+      match token {
+        Token::OrOperator(or_operator) if or_operator.has_empty_arg() => union_arguments.push(vec![]),
         Token::GroupedExpression(_) => panic!("All grouped expressions should be parsed prior to this step"),
-        _ => continue
+        token => union_arguments
+          .last_mut()
+          .expect("There should always be at least one union argument")
+          .push(token)
       }
-    
-    };
+    }
 
-  }  
+    let mut union_arguments = union_arguments
+      .into_iter()
+      .map(|argument_tokens| {
+        if argument_tokens.is_empty() {
+          Err(ParsingError::NoneTokenProvided)
+        } else {
+          Ok(concatenate_tokens(argument_tokens))
+        }
+      });
+
+    let mut union = union_arguments
+      .next()
+      .expect("There should always be at least one union argument")?;
+
+    for argument in union_arguments {
+      union = Token::OrOperator(Box::new(OrOperator::new(Some(union), Some(argument?))));
+    }
+
+    return Ok(union);
+  }
   
   return parse_tokens(tokens);
 
@@ -525,7 +625,9 @@ fn tokenize_regular_expression(regex: &str) -> Result<(Vec<Token>, Option<usize>
   let mut tokens: Vec<Token> = vec![];
   let mut current_working_index: usize = 0;
 
-  for (index, c) in regex.chars().enumerate() {
+  // Indexes are counted in characters (Unicode scalar values) so that they line up with the offsets returned for
+  // bracketed expressions, while the regex itself can only be sliced at the byte offset of a character
+  for (index, (byte_index, c)) in regex.char_indices().enumerate() {
     if index < current_working_index {
       // We do this to cleverly avoid duplicating the same tokens whenever we enter into
       // a bracketed expression. Whenever we are done grouping the data in brackets into a token
@@ -544,7 +646,7 @@ fn tokenize_regular_expression(regex: &str) -> Result<(Vec<Token>, Option<usize>
       
       // Needs a LOT of work in future
       // Currently, keeps reiterating over previously accounted for tokens
-      let (tokens_in_brackets, number_of_characters_in_brackets) = tokenize_regular_expression(&regex[index+1..])?;
+      let (tokens_in_brackets, number_of_characters_in_brackets) = tokenize_regular_expression(&regex[byte_index + c.len_utf8()..])?;
 
       tokens.push(Token::GroupedExpression(Box::new(tokens_in_brackets)));
       current_working_index += number_of_characters_in_brackets.ok_or_else(|| {
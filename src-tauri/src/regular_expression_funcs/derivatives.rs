@@ -0,0 +1,249 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use app::create_unique_state_coordinates;
+use app::models::State;
+
+use super::regex_models::{BinaryOperator, Token, UnaryOperator};
+
+// Brzozowski derivatives need to be able to express both the empty language and the empty string, neither of which
+// can be written as a Token, so the parse tree is first converted into this simpler representation before deriving
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum DerivativeExpression {
+  EmptyLanguage,
+  EmptyString,
+  Literal(String),
+  Concatenation(Box<DerivativeExpression>, Box<DerivativeExpression>),
+  // A BTreeSet is used so that unions are kept sorted and free of duplicates, which is what makes
+  // two similar derivatives such as a + b and b + a + a compare as equal
+  Union(BTreeSet<DerivativeExpression>),
+  Kleene(Box<DerivativeExpression>)
+}
+
+impl DerivativeExpression {
+
+  // The following constructors normalize expressions as they are built. Without this, repeatedly deriving
+  // an expression such as (a+b)* would generate an infinite number of distinct but equivalent expressions
+  // and the DFA construction would never terminate
+  pub fn concatenate(left: DerivativeExpression, right: DerivativeExpression) -> Self {
+    match (left, right) {
+      (DerivativeExpression::EmptyLanguage, _) | (_, DerivativeExpression::EmptyLanguage) => DerivativeExpression::EmptyLanguage,
+      (DerivativeExpression::EmptyString, expression) | (expression, DerivativeExpression::EmptyString) => expression,
+      // Concatenation is associative, so we always nest to the right to keep a single canonical form
+      (DerivativeExpression::Concatenation(first, second), third) => {
+        DerivativeExpression::concatenate(*first, DerivativeExpression::concatenate(*second, third))
+      },
+      (left, right) => DerivativeExpression::Concatenation(Box::new(left), Box::new(right))
+    }
+  }
+
+  pub fn union(left: DerivativeExpression, right: DerivativeExpression) -> Self {
+    let mut alternatives = BTreeSet::new();
+
+    for expression in [left, right] {
+      match expression {
+        DerivativeExpression::EmptyLanguage => continue,
+        DerivativeExpression::Union(inner_alternatives) => alternatives.extend(inner_alternatives),
+        expression => {
+          alternatives.insert(expression);
+        }
+      }
+    }
+
+    match alternatives.len() {
+      0 => DerivativeExpression::EmptyLanguage,
+      1 => alternatives
+        .pop_first()
+        .expect("The set should contain exactly one expression"),
+      _ => DerivativeExpression::Union(alternatives)
+    }
+  }
+
+  pub fn kleene(inner_expression: DerivativeExpression) -> Self {
+    match inner_expression {
+      DerivativeExpression::EmptyLanguage | DerivativeExpression::EmptyString => DerivativeExpression::EmptyString,
+      DerivativeExpression::Kleene(_) => inner_expression,
+      inner_expression => DerivativeExpression::Kleene(Box::new(inner_expression))
+    }
+  }
+
+  // An expression is nullable if it matches the empty string
+  pub fn is_nullable(&self) -> bool {
+    match self {
+      DerivativeExpression::EmptyLanguage | DerivativeExpression::Literal(_) => false,
+      DerivativeExpression::EmptyString | DerivativeExpression::Kleene(_) => true,
+      DerivativeExpression::Concatenation(left, right) => left.is_nullable() && right.is_nullable(),
+      DerivativeExpression::Union(alternatives) => alternatives
+        .iter()
+        .any(|alternative| alternative.is_nullable())
+    }
+  }
+
+  // The derivative of an expression with respect to a character is the expression which matches every string s
+  // such that character + s is matched by the original expression
+  pub fn derive(&self, character: &str) -> Self {
+    match self {
+      DerivativeExpression::EmptyLanguage | DerivativeExpression::EmptyString => DerivativeExpression::EmptyLanguage,
+      DerivativeExpression::Literal(literal) => {
+        if literal == character {
+          DerivativeExpression::EmptyString
+        } else {
+          DerivativeExpression::EmptyLanguage
+        }
+      },
+      DerivativeExpression::Concatenation(left, right) => {
+        let derived_through_left = DerivativeExpression::concatenate(left.derive(character), *right.to_owned());
+
+        if left.is_nullable() {
+          DerivativeExpression::union(derived_through_left, right.derive(character))
+        } else {
+          derived_through_left
+        }
+      },
+      DerivativeExpression::Union(alternatives) => alternatives
+        .iter()
+        .fold(DerivativeExpression::EmptyLanguage, |derived_union, alternative| {
+          DerivativeExpression::union(derived_union, alternative.derive(character))
+        }),
+      DerivativeExpression::Kleene(inner_expression) => {
+        DerivativeExpression::concatenate(inner_expression.derive(character), self.to_owned())
+      }
+    }
+  }
+
+  // A string is matched if deriving by each of its characters in turn leaves us with a nullable expression
+  pub fn matches(&self, string_to_check: &str) -> bool {
+    let mut current_expression = self.to_owned();
+
+    for character in string_to_check.chars() {
+      current_expression = current_expression.derive(&character.to_string());
+
+      // Nothing can be derived from the empty language, so there is no point consuming the rest of the string
+      if current_expression == DerivativeExpression::EmptyLanguage {
+        return false;
+      }
+    }
+
+    current_expression.is_nullable()
+  }
+
+  pub fn get_alphabet(&self) -> BTreeSet<String> {
+    let mut alphabet = BTreeSet::new();
+
+    match self {
+      DerivativeExpression::EmptyLanguage | DerivativeExpression::EmptyString => (),
+      DerivativeExpression::Literal(literal) => {
+        alphabet.insert(literal.to_owned());
+      },
+      DerivativeExpression::Concatenation(left, right) => {
+        alphabet.extend(left.get_alphabet());
+        alphabet.extend(right.get_alphabet());
+      },
+      DerivativeExpression::Union(alternatives) => {
+        for alternative in alternatives {
+          alphabet.extend(alternative.get_alphabet());
+        }
+      },
+      DerivativeExpression::Kleene(inner_expression) => alphabet.extend(inner_expression.get_alphabet())
+    }
+
+    alphabet
+  }
+
+}
+
+impl From<&Token> for DerivativeExpression {
+  fn from(token: &Token) -> Self {
+    // As with the NFA conversion, the parse tree is assumed to have been verified so every operator has its arguments
+    match token {
+      Token::Literal(literal) => literal
+        .chars()
+        .map(|c| DerivativeExpression::Literal(c.to_string()))
+        .fold(DerivativeExpression::EmptyString, DerivativeExpression::concatenate),
+      Token::GroupedExpression(_) => {
+        panic!("All grouped expressions should be parsed after generating the parse tree");
+      },
+      Token::ConcatenatedExpression(concatenated_expression) => DerivativeExpression::concatenate(
+        concatenated_expression
+          .get_left_argument()
+          .expect("The left argument should not have a None value")
+          .into(),
+        concatenated_expression
+          .get_right_argument()
+          .expect("The right argument should not have a None value")
+          .into()
+      ),
+      Token::OrOperator(operator) => DerivativeExpression::union(
+        operator
+          .get_left_argument()
+          .expect("The left argument should not have a None value")
+          .into(),
+        operator
+          .get_right_argument()
+          .expect("The right argument should not have a None value")
+          .into()
+      ),
+      Token::KleeneOperator(operator) => DerivativeExpression::kleene(
+        operator
+          .get_inner_argument()
+          .expect("The inner argument should not have a None value")
+          .into()
+      )
+    }
+  }
+}
+
+// Builds a DFA directly from the parse tree where every state corresponds to a unique (normalized) derivative
+// of the original expression. Returns the state positions alongside the key of the start state
+pub fn build_dfa_from_parse_tree(parse_tree: &Token) -> (HashMap<String, State>, String) {
+
+  let start_expression = DerivativeExpression::from(parse_tree);
+  let alphabet = start_expression.get_alphabet();
+
+  let mut state_positions: HashMap<String, State> = HashMap::new();
+  let mut state_keys_by_expression: HashMap<DerivativeExpression, String> = HashMap::new();
+  let mut expressions_to_visit = VecDeque::from([start_expression.to_owned()]);
+
+  let start_state_coords = create_unique_state_coordinates(&state_positions.keys().cloned().collect());
+  let start_state_key: String = start_state_coords.into();
+  state_positions.insert(start_state_key.to_owned(), State::new(start_state_coords, true, start_expression.is_nullable()));
+  state_keys_by_expression.insert(start_expression, start_state_key.to_owned());
+
+  while let Some(current_expression) = expressions_to_visit.pop_front() {
+
+    let current_state_key = state_keys_by_expression
+      .get(&current_expression)
+      .expect("Every visited expression should already have a state")
+      .to_owned();
+
+    for character in &alphabet {
+      let derived_expression = current_expression.derive(character);
+
+      // Just like in DFA minimization, transitions into the empty language are left implicit
+      if derived_expression == DerivativeExpression::EmptyLanguage {
+        continue;
+      }
+
+      let derived_state_key = match state_keys_by_expression.get(&derived_expression) {
+        Some(state_key) => state_key.to_owned(),
+        None => {
+          let new_state_coords = create_unique_state_coordinates(&state_positions.keys().cloned().collect());
+          let new_state_key: String = new_state_coords.into();
+
+          state_positions.insert(new_state_key.to_owned(), State::new(new_state_coords, false, derived_expression.is_nullable()));
+          state_keys_by_expression.insert(derived_expression.to_owned(), new_state_key.to_owned());
+          expressions_to_visit.push_back(derived_expression);
+
+          new_state_key
+        }
+      };
+
+      state_positions
+        .get_mut(&current_state_key)
+        .expect("Failed to retrieve the requested state")
+        .add_connection(character, derived_state_key);
+    }
+  }
+
+  (state_positions, start_state_key)
+
+}
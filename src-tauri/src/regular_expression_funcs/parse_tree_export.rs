@@ -0,0 +1,224 @@
+use serde::Serialize;
+
+use app::escape_dot_string;
+
+use super::regex_models::{BinaryOperator, Operator, Token, UnaryOperator};
+
+// A flattened version of the parse tree which is far easier for the front end to draw than the nested Token enum.
+// Node ids are given in pre-order so the root is always 0 and the same regex always produces the same ids
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct ParseTreeGraph {
+  pub nodes: Vec<ParseTreeNode>,
+  pub edges: Vec<ParseTreeEdge>
+}
+
+// Spans are measured in characters (not bytes) of the original regex, with span_end being exclusive
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct ParseTreeNode {
+  pub id: usize,
+  pub label: String,
+  pub node_type: String,
+  pub span_start: usize,
+  pub span_end: usize
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct ParseTreeEdge {
+  pub parent_id: usize,
+  pub child_id: usize
+}
+
+// The Token tree does not record where in the regex each token came from. However an in-order traversal of the tree
+// visits literals, or operators and kleene operators in the same order they appear in the regex, so we keep track of
+// the source indices of each kind of character and hand them out as the matching tokens are visited. The regex is split
+// into these kinds exactly as it was when tokenizing, so running out of indices means the two have drifted apart
+struct SourceIndices {
+  characters: Vec<char>,
+  literal_indices: Vec<usize>,
+  or_operator_indices: Vec<usize>,
+  kleene_operator_indices: Vec<usize>
+}
+
+impl SourceIndices {
+
+  fn new(regex: &str) -> Self {
+    let characters: Vec<char> = regex.chars().collect();
+    let mut source_indices = SourceIndices {
+      characters: characters.to_owned(),
+      literal_indices: vec![],
+      or_operator_indices: vec![],
+      kleene_operator_indices: vec![]
+    };
+
+    // Indices are stored in reverse so the next one to hand out can simply be popped
+    for (index, c) in characters.iter().enumerate().rev() {
+      match c {
+        '+' => source_indices.or_operator_indices.push(index),
+        '*' => source_indices.kleene_operator_indices.push(index),
+        '(' | ')' => (),
+        c if c.is_whitespace() => (),
+        _ => source_indices.literal_indices.push(index)
+      }
+    }
+
+    source_indices
+  }
+
+  // Widens a span to include any pair of brackets which directly surround it
+  fn include_surrounding_brackets(&self, mut span: (usize, usize)) -> (usize, usize) {
+    while span.0 > 0 && self.characters.get(span.0 - 1) == Some(&'(') && self.find_closing_bracket(span.0 - 1) == Some(span.1) {
+      span = (span.0 - 1, span.1 + 1);
+    }
+    span
+  }
+
+  fn find_closing_bracket(&self, opening_bracket_index: usize) -> Option<usize> {
+    let mut depth = 0;
+
+    for (index, c) in self.characters.iter().enumerate().skip(opening_bracket_index) {
+      match c {
+        '(' => depth += 1,
+        ')' => {
+          depth -= 1;
+          if depth == 0 {
+            return Some(index);
+          }
+        },
+        _ => ()
+      }
+    }
+
+    None
+  }
+
+}
+
+impl ParseTreeGraph {
+
+  pub fn new(parse_tree: &Token, regex: &str) -> Self {
+    let mut parse_tree_graph = ParseTreeGraph {
+      nodes: vec![],
+      edges: vec![]
+    };
+    let mut source_indices = SourceIndices::new(regex);
+
+    parse_tree_graph.add_token(parse_tree, &mut source_indices);
+    parse_tree_graph
+  }
+
+  // Adds the token and all of its children to the graph, returning the id and span of the added token
+  fn add_token(&mut self, token: &Token, source_indices: &mut SourceIndices) -> (usize, (usize, usize)) {
+
+    let id = self.nodes.len();
+    // The node is pushed straight away, as ids are assigned in pre-order, then its span is filled in once its children are known
+    self.nodes.push(ParseTreeNode {
+      id,
+      label: String::new(),
+      node_type: String::new(),
+      span_start: 0,
+      span_end: 0
+    });
+
+    let (label, node_type, span) = match token {
+      Token::Literal(literal) => {
+        let mut span: Option<(usize, usize)> = None;
+        for _ in literal.chars() {
+          let index = source_indices.literal_indices
+            .pop()
+            .expect("Every character of a literal should have come from the regex");
+          span = Some(Self::merge_spans(span, (index, index + 1)));
+        }
+        (literal.to_owned(), String::from("Literal"), span.expect("Literals should never be empty"))
+      },
+      Token::GroupedExpression(_) => {
+        panic!("All grouped expressions should be parsed after generating the parse tree");
+      },
+      Token::ConcatenatedExpression(concatenated_expression) => {
+        let left_span = self.add_child(id, concatenated_expression.get_left_argument(), source_indices);
+        let right_span = self.add_child(id, concatenated_expression.get_right_argument(), source_indices);
+        (
+          concatenated_expression.get_operator_character().to_owned(),
+          concatenated_expression.get_operator_name().to_owned(),
+          Self::merge_spans(Some(left_span), right_span)
+        )
+      },
+      Token::OrOperator(operator) => {
+        let left_span = self.add_child(id, operator.get_left_argument(), source_indices);
+        let operator_index = source_indices.or_operator_indices
+          .pop()
+          .expect("Every or operator should have come from a + in the regex");
+        let right_span = self.add_child(id, operator.get_right_argument(), source_indices);
+        (
+          operator.get_operator_character().to_owned(),
+          operator.get_operator_name().to_owned(),
+          Self::merge_spans(Some(Self::merge_spans(Some(left_span), (operator_index, operator_index + 1))), right_span)
+        )
+      },
+      Token::KleeneOperator(operator) => {
+        let inner_span = self.add_child(id, operator.get_inner_argument(), source_indices);
+        let operator_index = source_indices.kleene_operator_indices
+          .pop()
+          .expect("Every kleene operator should have come from a * in the regex");
+        (
+          operator.get_operator_character().to_owned(),
+          operator.get_operator_name().to_owned(),
+          Self::merge_spans(Some(inner_span), (operator_index, operator_index + 1))
+        )
+      }
+    };
+
+    let span = source_indices.include_surrounding_brackets(span);
+    let node = &mut self.nodes[id];
+    node.label = label;
+    node.node_type = node_type;
+    node.span_start = span.0;
+    node.span_end = span.1;
+
+    (id, span)
+
+  }
+
+  fn add_child(&mut self, parent_id: usize, child: Option<&Token>, source_indices: &mut SourceIndices) -> (usize, usize) {
+    let child = child.expect("Every operator should have its arguments, make sure to verify validity of parse tree first");
+    let (child_id, child_span) = self.add_token(child, source_indices);
+
+    self.edges.push(ParseTreeEdge {
+      parent_id,
+      child_id
+    });
+
+    child_span
+  }
+
+  fn merge_spans(first_span: Option<(usize, usize)>, second_span: (usize, usize)) -> (usize, usize) {
+    match first_span {
+      Some(first_span) => (first_span.0.min(second_span.0), first_span.1.max(second_span.1)),
+      None => second_span
+    }
+  }
+
+  pub fn to_dot(&self) -> String {
+    let mut dot = String::from("digraph parse_tree {\n  node [shape=circle];\n");
+
+    for node in &self.nodes {
+      let shape = if node.node_type == "Literal" { "box" } else { "circle" };
+      dot += &format!(
+        "  n{} [label=\"{}\", shape={}, tooltip=\"{} [{}, {})\"];\n",
+        node.id,
+        escape_dot_string(&node.label),
+        shape,
+        escape_dot_string(&node.node_type),
+        node.span_start,
+        node.span_end
+      );
+    }
+
+    for edge in &self.edges {
+      dot += &format!("  n{} -> n{};\n", edge.parent_id, edge.child_id);
+    }
+
+    dot += "}\n";
+    dot
+  }
+
+}
@@ -0,0 +1,222 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use app::create_unique_state_coordinates;
+use app::models::State;
+
+use super::regex_models::{BinaryOperator, Token, UnaryOperator};
+
+// The tables used by the Glushkov (position automaton) construction, as taught in the Dragon book.
+// Every literal character in the regex is given a position, numbered from 1 in the order they appear
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct PositionTables {
+  pub symbols_by_position: BTreeMap<usize, String>,
+  pub nullable: bool,
+  pub firstpos: BTreeSet<usize>,
+  pub lastpos: BTreeSet<usize>,
+  pub followpos: BTreeMap<usize, BTreeSet<usize>>
+}
+
+// nullable, firstpos and lastpos of a single node of the parse tree
+struct NodePositions {
+  nullable: bool,
+  firstpos: BTreeSet<usize>,
+  lastpos: BTreeSet<usize>
+}
+
+impl PositionTables {
+
+  pub fn new(parse_tree: &Token) -> Self {
+
+    let mut symbols_by_position = BTreeMap::new();
+    let mut followpos = BTreeMap::new();
+
+    let root_positions = Self::compute_node_positions(parse_tree, &mut symbols_by_position, &mut followpos);
+
+    PositionTables {
+      symbols_by_position,
+      nullable: root_positions.nullable,
+      firstpos: root_positions.firstpos,
+      lastpos: root_positions.lastpos,
+      followpos
+    }
+
+  }
+
+  // Computes nullable, firstpos and lastpos bottom up, filling in followpos as concatenation and kleene nodes are encountered
+  fn compute_node_positions(
+    token: &Token,
+    symbols_by_position: &mut BTreeMap<usize, String>,
+    followpos: &mut BTreeMap<usize, BTreeSet<usize>>) -> NodePositions {
+
+    match token {
+      Token::Literal(literal) => {
+        // A literal with several characters is just the concatenation of each of them
+        let mut literal_positions = NodePositions {
+          nullable: true,
+          firstpos: BTreeSet::new(),
+          lastpos: BTreeSet::new()
+        };
+
+        for c in literal.chars() {
+          let position = symbols_by_position.len() + 1;
+          symbols_by_position.insert(position, c.to_string());
+          followpos.insert(position, BTreeSet::new());
+
+          let character_positions = NodePositions {
+            nullable: false,
+            firstpos: BTreeSet::from([position]),
+            lastpos: BTreeSet::from([position])
+          };
+          literal_positions = Self::concatenate_node_positions(literal_positions, character_positions, followpos);
+        }
+
+        literal_positions
+      },
+      Token::GroupedExpression(_) => {
+        panic!("All grouped expressions should be parsed after generating the parse tree");
+      },
+      Token::ConcatenatedExpression(concatenated_expression) => {
+        let left_positions = Self::compute_node_positions(
+          concatenated_expression
+            .get_left_argument()
+            .expect("The left argument should not have a None value"),
+          symbols_by_position,
+          followpos
+        );
+        let right_positions = Self::compute_node_positions(
+          concatenated_expression
+            .get_right_argument()
+            .expect("The right argument should not have a None value"),
+          symbols_by_position,
+          followpos
+        );
+
+        Self::concatenate_node_positions(left_positions, right_positions, followpos)
+      },
+      Token::OrOperator(operator) => {
+        let left_positions = Self::compute_node_positions(
+          operator
+            .get_left_argument()
+            .expect("The left argument should not have a None value"),
+          symbols_by_position,
+          followpos
+        );
+        let right_positions = Self::compute_node_positions(
+          operator
+            .get_right_argument()
+            .expect("The right argument should not have a None value"),
+          symbols_by_position,
+          followpos
+        );
+
+        NodePositions {
+          nullable: left_positions.nullable || right_positions.nullable,
+          firstpos: left_positions.firstpos.union(&right_positions.firstpos).cloned().collect(),
+          lastpos: left_positions.lastpos.union(&right_positions.lastpos).cloned().collect()
+        }
+      },
+      Token::KleeneOperator(operator) => {
+        let inner_positions = Self::compute_node_positions(
+          operator
+            .get_inner_argument()
+            .expect("The inner argument should not have a None value"),
+          symbols_by_position,
+          followpos
+        );
+
+        // Any position which can end the inner expression can be followed by one which starts it again
+        for last_position in &inner_positions.lastpos {
+          followpos
+            .entry(*last_position)
+            .or_default()
+            .extend(inner_positions.firstpos.iter().cloned());
+        }
+
+        NodePositions {
+          nullable: true,
+          firstpos: inner_positions.firstpos,
+          lastpos: inner_positions.lastpos
+        }
+      }
+    }
+
+  }
+
+  fn concatenate_node_positions(
+    left_positions: NodePositions,
+    right_positions: NodePositions,
+    followpos: &mut BTreeMap<usize, BTreeSet<usize>>) -> NodePositions {
+
+    // Any position which can end the left expression can be followed by one which starts the right expression
+    for last_position in &left_positions.lastpos {
+      followpos
+        .entry(*last_position)
+        .or_default()
+        .extend(right_positions.firstpos.iter().cloned());
+    }
+
+    let firstpos = if left_positions.nullable {
+      left_positions.firstpos.union(&right_positions.firstpos).cloned().collect()
+    } else {
+      left_positions.firstpos
+    };
+
+    let lastpos = if right_positions.nullable {
+      left_positions.lastpos.union(&right_positions.lastpos).cloned().collect()
+    } else {
+      right_positions.lastpos
+    };
+
+    NodePositions {
+      nullable: left_positions.nullable && right_positions.nullable,
+      firstpos,
+      lastpos
+    }
+
+  }
+
+  // Builds the ε-free position automaton, which has a start state plus exactly one state per position.
+  // Every transition into the state of a position is labelled by the symbol at that position.
+  // Returns the state positions alongside the key of the start state
+  pub fn build_position_automaton(&self) -> (HashMap<String, State>, String) {
+
+    let mut state_positions: HashMap<String, State> = HashMap::new();
+    let mut state_keys_by_position: BTreeMap<usize, String> = BTreeMap::new();
+
+    let start_state_coords = create_unique_state_coordinates(&state_positions.keys().cloned().collect());
+    let start_state_key: String = start_state_coords.into();
+    state_positions.insert(start_state_key.to_owned(), State::new(start_state_coords, true, self.nullable));
+
+    for position in self.symbols_by_position.keys() {
+      let state_coords = create_unique_state_coordinates(&state_positions.keys().cloned().collect());
+      let state_key: String = state_coords.into();
+
+      state_positions.insert(state_key.to_owned(), State::new(state_coords, false, self.lastpos.contains(position)));
+      state_keys_by_position.insert(*position, state_key);
+    }
+
+    let start_state = state_positions
+      .get_mut(&start_state_key)
+      .expect("Failed to retrieve the requested state");
+
+    for first_position in &self.firstpos {
+      start_state.add_connection(&self.symbols_by_position[first_position], state_keys_by_position[first_position].to_owned());
+    }
+
+    for (position, following_positions) in &self.followpos {
+      let state = state_positions
+        .get_mut(&state_keys_by_position[position])
+        .expect("Failed to retrieve the requested state");
+
+      for following_position in following_positions {
+        state.add_connection(&self.symbols_by_position[following_position], state_keys_by_position[following_position].to_owned());
+      }
+    }
+
+    (state_positions, start_state_key)
+
+  }
+
+}
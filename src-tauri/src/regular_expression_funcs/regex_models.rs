@@ -50,6 +50,8 @@ pub trait Operator {
   // Just using insert for now as a test
   fn insert_token(&mut self, token_to_insert: Option<Token>) -> Result<(), ParsingError>;
   fn has_empty_arg(&self) -> bool;
+  fn get_operator_character(&self) -> &str;
+  fn get_operator_name(&self) -> &str;
 }
 
 pub trait BinaryOperator {
@@ -86,6 +88,15 @@ impl Operator for ConcatenatedExpression {
   fn has_empty_arg(&self) -> bool {
     return self.left_argument.is_none() || self.right_argument.is_none();
   }
+
+  fn get_operator_character(&self) -> &str {
+    &self.operator_character
+  }
+
+  fn get_operator_name(&self) -> &str {
+    &self.operator_name
+  }
+
 }
 
 impl BinaryOperator for ConcatenatedExpression {
@@ -254,6 +265,14 @@ impl Operator for OrOperator {
     return self.left_argument.is_none() || self.right_argument.is_none();
   }
 
+  fn get_operator_character(&self) -> &str {
+    &self.operator_character
+  }
+
+  fn get_operator_name(&self) -> &str {
+    &self.operator_name
+  }
+
 }
 
 impl Operator for KleeneOperator {
@@ -304,5 +323,13 @@ impl Operator for KleeneOperator {
     return self.inner_argument.is_none();
   }
 
+  fn get_operator_character(&self) -> &str {
+    &self.operator_character
+  }
+
+  fn get_operator_name(&self) -> &str {
+    &self.operator_name
+  }
+
 
 }
\ No newline at end of file
@@ -1,6 +1,30 @@
 #[cfg(test)]
 pub mod tests {
   use crate::regular_expression_funcs::{regex_models::ParsingError, test_string_regex, build_parse_tree};
+  use crate::regular_expression_funcs::{derivatives::build_dfa_from_parse_tree, test_string_regex_with_derivatives};
+  use crate::regular_expression_funcs::position_automaton::PositionTables;
+  use crate::regular_expression_funcs::{export_parse_tree, export_parse_tree_to_dot};
+  use crate::testing_automata_funcs::{test_string_dfa, test_string_nfa};
+  use std::collections::{BTreeMap, BTreeSet};
+
+  // Every string over the given alphabet up to and including the given length, used to compare regex backends exhaustively
+  fn generate_all_strings(alphabet: &[char], max_length: usize) -> Vec<String> {
+    let mut all_strings = vec![String::new()];
+    let mut previous_length_strings = vec![String::new()];
+
+    for _ in 0..max_length {
+      let mut current_length_strings = vec![];
+      for string in &previous_length_strings {
+        for c in alphabet {
+          current_length_strings.push(format!("{string}{c}"));
+        }
+      }
+      all_strings.extend(current_length_strings.iter().cloned());
+      previous_length_strings = current_length_strings;
+    }
+
+    all_strings
+  }
 
 
   // Commenting out parsing tests because in order for the string checking tests to function in the 
@@ -185,4 +209,194 @@ pub mod tests {
 
   }
 
-}
\ No newline at end of file
+  #[test]
+  fn test_derivatives_agree_with_thompson_construction() {
+
+    let regexes_to_test = ["a", "a+b", "a*", "(abc)*", "(a+b)*", "a(a+b)", "(a+b)a", "a(a+b)*", 
+      "(a+b)*a", "((a)(b))*a", "((a+b)c)*", "a*b", "(a+b)*abb", "ab*c+ca"];
+
+    for regex_to_test in regexes_to_test {
+
+      let (state_positions, start_state_key) = build_dfa_from_parse_tree(&build_parse_tree(regex_to_test).unwrap());
+
+      for string_to_check in generate_all_strings(&['a', 'b', 'c'], 5) {
+
+        let accepted_by_nfa = test_string_regex(regex_to_test, string_to_check.to_owned()).unwrap();
+        let accepted_by_derivatives = test_string_regex_with_derivatives(regex_to_test, string_to_check.to_owned()).unwrap();
+        let (accepted_by_dfa, _) = test_string_dfa(state_positions.clone(), start_state_key.to_owned(), string_to_check.to_owned());
+
+        assert_eq!(accepted_by_nfa, accepted_by_derivatives, "{regex_to_test} disagreed on {string_to_check:?}");
+        assert_eq!(accepted_by_nfa, accepted_by_dfa, "{regex_to_test} disagreed on {string_to_check:?}");
+      }
+    }
+
+  }
+
+  #[test]
+  fn test_unicode_literals() {
+
+    // Greek letters and emoji are several bytes long, and a combining accent is a character of its own
+    let regexes_to_test = ["α", "(α+β)*γ", "🙂(e\u{301})*", "(αβ)*🙂", "(α+e)\u{301}*🙂"];
+    let alphabet = ['α', 'β', 'γ', '🙂', 'e', '\u{301}'];
+
+    for regex_to_test in regexes_to_test {
+
+      let (state_positions, start_state_key) = build_dfa_from_parse_tree(&build_parse_tree(regex_to_test).unwrap());
+      let position_tables = PositionTables::new(&build_parse_tree(regex_to_test).unwrap());
+      let (position_state_positions, position_start_state_key) = position_tables.build_position_automaton();
+
+      for string_to_check in generate_all_strings(&alphabet, 3) {
+
+        let accepted_by_nfa = test_string_regex(regex_to_test, string_to_check.to_owned()).unwrap();
+        let accepted_by_derivatives = test_string_regex_with_derivatives(regex_to_test, string_to_check.to_owned()).unwrap();
+        let (accepted_by_dfa, _) = test_string_dfa(state_positions.clone(), start_state_key.to_owned(), string_to_check.to_owned());
+        let (accepted_by_position_automaton, _) = test_string_nfa(
+          position_state_positions.clone(),
+          position_start_state_key.to_owned(),
+          string_to_check.to_owned()
+        );
+
+        assert_eq!(accepted_by_nfa, accepted_by_derivatives, "{regex_to_test} disagreed on {string_to_check:?}");
+        assert_eq!(accepted_by_nfa, accepted_by_dfa, "{regex_to_test} disagreed on {string_to_check:?}");
+        assert_eq!(accepted_by_nfa, accepted_by_position_automaton, "{regex_to_test} disagreed on {string_to_check:?}");
+      }
+    }
+
+    assert!(test_string_regex("(α+β)*γ", "βαγ".to_owned()).unwrap());
+    assert!(test_string_regex("🙂(e\u{301})*", "🙂e\u{301}e\u{301}".to_owned()).unwrap());
+    assert!(!test_string_regex("🙂(e\u{301})*", "🙂e".to_owned()).unwrap());
+
+  }
+
+  #[test]
+  fn test_derivative_dfa_is_finite_and_small() {
+
+    // (a+b)*abb is the textbook example whose minimal DFA has exactly 4 states
+    let (state_positions, _) = build_dfa_from_parse_tree(&build_parse_tree("(a+b)*abb").unwrap());
+    assert_eq!(state_positions.len(), 4);
+
+    let (state_positions, _) = build_dfa_from_parse_tree(&build_parse_tree("(a+b)*").unwrap());
+    assert_eq!(state_positions.len(), 1);
+
+  }
+
+  #[test]
+  fn test_position_tables_match_textbook_example() {
+
+    let position_tables = PositionTables::new(&build_parse_tree("(a+b)*abb").unwrap());
+
+    assert!(!position_tables.nullable);
+    assert_eq!(position_tables.firstpos, BTreeSet::from([1, 2, 3]));
+    assert_eq!(position_tables.lastpos, BTreeSet::from([5]));
+    assert_eq!(position_tables.followpos, BTreeMap::from([
+      (1, BTreeSet::from([1, 2, 3])),
+      (2, BTreeSet::from([1, 2, 3])),
+      (3, BTreeSet::from([4])),
+      (4, BTreeSet::from([5])),
+      (5, BTreeSet::new())
+    ]));
+
+  }
+
+  #[test]
+  fn test_position_automaton_agrees_with_derivatives() {
+
+    let regexes_to_test = ["a", "a+b", "a*", "(abc)*", "(a+b)*", "a(a+b)*", "((a)(b))*a", 
+      "((a+b)c)*", "(a*b*)*c", "(a+b)*abb", "ab*c+ca"];
+
+    for regex_to_test in regexes_to_test {
+
+      let position_tables = PositionTables::new(&build_parse_tree(regex_to_test).unwrap());
+      let (state_positions, start_state_key) = position_tables.build_position_automaton();
+
+      // One state for every literal plus the start state, none of which have ϵ transitions
+      assert_eq!(state_positions.len(), position_tables.symbols_by_position.len() + 1);
+      assert!(state_positions.values().all(|state| state.get_connections_by_character("ϵ").is_none()));
+
+      for string_to_check in generate_all_strings(&['a', 'b', 'c'], 5) {
+        let accepted_by_derivatives = test_string_regex_with_derivatives(regex_to_test, string_to_check.to_owned()).unwrap();
+        let (accepted_by_position_automaton, _) = test_string_nfa(state_positions.clone(), start_state_key.to_owned(), string_to_check.to_owned());
+
+        assert_eq!(accepted_by_derivatives, accepted_by_position_automaton, "{regex_to_test} disagreed on {string_to_check:?}");
+      }
+    }
+
+  }
+
+  #[test]
+  fn test_parsing_respects_operator_precedence() {
+
+    let labels_of = |regex: &str| -> Vec<String> {
+      export_parse_tree(regex).unwrap().nodes.into_iter().map(|node| node.label).collect()
+    };
+
+    // Star binds tighter than concatenation, which binds tighter than union
+    assert_eq!(labels_of("ab*+c"), vec!["+", "⋅", "a", "*", "b", "c"]);
+    assert!(test_string_regex("ab*+c", "abbb".to_owned()).unwrap());
+    assert!(test_string_regex("ab*+c", "c".to_owned()).unwrap());
+    assert!(!test_string_regex("ab*+c", "ac".to_owned()).unwrap());
+
+    // Concatenation on either side of a union stays with its own side
+    assert_eq!(labels_of("a+bc"), vec!["+", "a", "⋅", "b", "c"]);
+    assert!(test_string_regex("a+bc", "a".to_owned()).unwrap());
+    assert!(test_string_regex("a+bc", "bc".to_owned()).unwrap());
+    assert!(!test_string_regex("a+bc", "ac".to_owned()).unwrap());
+
+    assert_eq!(labels_of("ab+c"), vec!["+", "⋅", "a", "b", "c"]);
+    assert!(test_string_regex("ab+c", "ab".to_owned()).unwrap());
+    assert!(!test_string_regex("ab+c", "ac".to_owned()).unwrap());
+
+    assert!(test_string_regex("ab+cd", "cd".to_owned()).unwrap());
+    assert!(!test_string_regex("ab+cd", "abd".to_owned()).unwrap());
+    assert!(!test_string_regex("ab+cd", "acd".to_owned()).unwrap());
+
+    // Unions are grouped from the left, and brackets still override precedence
+    assert_eq!(labels_of("a+b+c"), vec!["+", "+", "a", "b", "c"]);
+    assert_eq!(labels_of("(a+b)c"), vec!["⋅", "+", "a", "b", "c"]);
+    assert!(test_string_regex("(a+b)c", "bc".to_owned()).unwrap());
+    assert!(!test_string_regex("(a+b)c", "a".to_owned()).unwrap());
+
+    // A union missing an argument is still an error
+    assert_eq!(build_parse_tree("a+").unwrap_err(), ParsingError::NoneTokenProvided);
+    assert_eq!(build_parse_tree("+a").unwrap_err(), ParsingError::NoneTokenProvided);
+
+  }
+
+  #[test]
+  fn test_parse_tree_export() {
+
+    let parse_tree = export_parse_tree("(a+b)*c").unwrap();
+
+    let spans: Vec<(&str, usize, usize)> = parse_tree.nodes
+      .iter()
+      .map(|node| (node.label.as_str(), node.span_start, node.span_end))
+      .collect();
+
+    assert_eq!(spans, vec![("⋅", 0, 7), ("*", 0, 6), ("+", 0, 5), ("a", 1, 2), ("b", 3, 4), ("c", 6, 7)]);
+
+    let edges: Vec<(usize, usize)> = parse_tree.edges
+      .iter()
+      .map(|edge| (edge.parent_id, edge.child_id))
+      .collect();
+
+    assert_eq!(edges.len(), parse_tree.nodes.len() - 1);
+    assert!(edges.contains(&(0, 1)) && edges.contains(&(0, 5)) && edges.contains(&(1, 2)));
+    assert!(edges.contains(&(2, 3)) && edges.contains(&(2, 4)));
+
+    let dot = export_parse_tree_to_dot("(a+b)*c").unwrap();
+
+    assert!(dot.starts_with("digraph parse_tree {"));
+    assert!(dot.contains("n2 [label=\"+\""));
+    assert!(dot.contains("n1 -> n2;"));
+
+    // Every character, operator and bracket is accounted for, so each node's span lies within the regex
+    for regex in ["a**", "((a))b", "(ab)*+c", "a + b c", "ϵ+(ab)*", "((a+b)*c)*"] {
+      let parse_tree = export_parse_tree(regex).unwrap();
+      let root = &parse_tree.nodes[0];
+      assert_eq!((root.span_start, root.span_end), (0, regex.chars().count()), "{regex}");
+      assert!(parse_tree.nodes.iter().all(|node| node.span_start < node.span_end && node.span_end <= regex.chars().count()));
+    }
+
+  }
+
+}
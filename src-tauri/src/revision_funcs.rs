@@ -0,0 +1,159 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use app::establish_connection;
+use app::models::{SavedRevision, State, WorkspaceData};
+use app::schema::workspace_revisions;
+
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+
+use crate::saving_automata_funcs::{get_workspace_id, retrieve_workspace_data, save_automata_to_target, WorkspaceTarget};
+use crate::workspace_file_funcs::WorkspaceFile;
+mod tests;
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum RevisionError {
+  UnknownRevision(i32),
+  InvalidSnapshot
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct RevisionSummary {
+  pub id: i32,
+  pub date_of_revision: String
+}
+
+// States are given by their ids and transitions as (from, symbol, to), all sorted so the same two revisions always give the same diff
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct RevisionDiff {
+  pub added_states: Vec<String>,
+  pub removed_states: Vec<String>,
+  pub added_transitions: Vec<(String, String, String)>,
+  pub removed_transitions: Vec<(String, String, String)>
+}
+
+// Newest first, as revisions are numbered in the order they were saved
+#[tauri::command]
+pub fn get_workspace_revisions(workspace_name: &str, email: &str) -> Vec<RevisionSummary> {
+
+  let mut conn = establish_connection();
+  let workspace_id = get_workspace_id(workspace_name, email);
+
+  let saved_revisions: Vec<SavedRevision> = workspace_revisions::table
+    .filter(workspace_revisions::workspace_id.eq(workspace_id))
+    .order(workspace_revisions::id.desc())
+    .get_results(&mut conn)
+    .expect("There was an error retrieving the workspace's revisions");
+
+  saved_revisions
+    .into_iter()
+    .map(|saved_revision| RevisionSummary {
+      id: saved_revision.id,
+      date_of_revision: saved_revision.date_of_revision.format("%Y-%m-%d %H:%M:%S").to_string()
+    })
+    .collect()
+
+}
+
+// The workspace as it was at the given revision, without changing what is saved
+#[tauri::command]
+pub fn preview_workspace_revision(workspace_name: &str, email: &str, revision_id: i32) -> Result<WorkspaceData, RevisionError> {
+
+  let workspace_file = get_revision_snapshot(workspace_name, email, revision_id)?;
+
+  Ok(retrieve_workspace_data(workspace_name, email).with_automata(
+    workspace_file.get_state_positions().to_owned(),
+    workspace_file.get_connections().to_owned(),
+    workspace_file.get_type_of_automata(),
+    workspace_file.get_alphabet().to_owned()
+  ))
+
+}
+
+// Restoring is itself a save, so it adds a revision of its own and can be undone by restoring the one before it
+#[tauri::command]
+pub fn restore_workspace_revision(workspace_name: &str, email: &str, revision_id: i32) -> Result<WorkspaceData, RevisionError> {
+
+  let workspace_file = get_revision_snapshot(workspace_name, email, revision_id)?;
+
+  Ok(save_automata_to_target(
+    workspace_name,
+    email,
+    WorkspaceTarget::Overwrite,
+    workspace_file.get_state_positions().to_owned(),
+    workspace_file.get_connections().to_owned(),
+    Some(workspace_file.get_type_of_automata()),
+    Some(workspace_file.get_alphabet().to_owned())
+  ))
+
+}
+
+// What changed going from the first revision to the second
+#[tauri::command]
+pub fn diff_workspace_revisions(
+  workspace_name: &str,
+  email: &str,
+  first_revision_id: i32,
+  second_revision_id: i32
+) -> Result<RevisionDiff, RevisionError> {
+
+  let first_workspace_file = get_revision_snapshot(workspace_name, email, first_revision_id)?;
+  let second_workspace_file = get_revision_snapshot(workspace_name, email, second_revision_id)?;
+
+  Ok(diff_state_positions(first_workspace_file.get_state_positions(), second_workspace_file.get_state_positions()))
+
+}
+
+pub fn diff_state_positions(
+  old_state_positions: &HashMap<String, State>,
+  new_state_positions: &HashMap<String, State>
+) -> RevisionDiff {
+
+  let old_state_keys: BTreeSet<&String> = old_state_positions.keys().collect();
+  let new_state_keys: BTreeSet<&String> = new_state_positions.keys().collect();
+
+  let old_transitions = get_transitions(old_state_positions);
+  let new_transitions = get_transitions(new_state_positions);
+
+  RevisionDiff {
+    added_states: new_state_keys.difference(&old_state_keys).map(|state_key| state_key.to_string()).collect(),
+    removed_states: old_state_keys.difference(&new_state_keys).map(|state_key| state_key.to_string()).collect(),
+    added_transitions: new_transitions.difference(&old_transitions).cloned().collect(),
+    removed_transitions: old_transitions.difference(&new_transitions).cloned().collect()
+  }
+
+}
+
+fn get_transitions(state_positions: &HashMap<String, State>) -> BTreeSet<(String, String, String)> {
+
+  let mut transitions = BTreeSet::new();
+
+  for (state_key, state) in state_positions {
+    for (connection_character, connected_state_keys) in state.get_all_connections() {
+      for connected_state_key in connected_state_keys {
+        transitions.insert((state_key.to_owned(), connection_character.to_owned(), connected_state_key.to_owned()));
+      }
+    }
+  }
+
+  transitions
+
+}
+
+// Revisions are only looked up among those of the given workspace, so one workspace's revisions can't be restored into another
+fn get_revision_snapshot(workspace_name: &str, email: &str, revision_id: i32) -> Result<WorkspaceFile, RevisionError> {
+
+  let mut conn = establish_connection();
+  let workspace_id = get_workspace_id(workspace_name, email);
+
+  let saved_revision: SavedRevision = workspace_revisions::table
+    .filter(workspace_revisions::workspace_id.eq(workspace_id))
+    .filter(workspace_revisions::id.eq(revision_id))
+    .get_result(&mut conn)
+    .map_err(|_| RevisionError::UnknownRevision(revision_id))?;
+
+  WorkspaceFile::from_json(&saved_revision.snapshot)
+    .map_err(|_| RevisionError::InvalidSnapshot)
+
+}
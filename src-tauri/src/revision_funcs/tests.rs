@@ -0,0 +1,80 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::revision_funcs::{diff_state_positions, RevisionDiff};
+  use crate::workspace_file_funcs::WorkspaceFile;
+  use app::models::{Coordinate, State};
+  use std::collections::HashMap;
+
+  fn transition(from: &str, symbol: &str, to: &str) -> (String, String, String) {
+    (from.to_owned(), symbol.to_owned(), to.to_owned())
+  }
+
+  #[test]
+  fn test_diffing_revisions() {
+    let mut q0 = State::with_id("q0", Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::with_id("q1", Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection("a", "q1");
+    q1.add_connection("b", "q1");
+
+    let old_state_positions = HashMap::from([
+      (String::from("q0"), q0.clone()),
+      (String::from("q1"), q1)
+    ]);
+
+    // The second revision moves b onto a new state and adds a transition on b to q0, while q0 itself only moves
+    let mut q0 = q0;
+    q0.set_position(Coordinate { x: 100, y: 100 });
+    q0.add_connection("b", "q0");
+    let mut q2 = State::with_id("q2", Coordinate { x: 700, y: 300 }, false, true);
+    q2.add_connection("b", "q2");
+    q0.remove_all_connections_by_character("a");
+    q0.add_connection("a", "q2");
+
+    let new_state_positions = HashMap::from([
+      (String::from("q0"), q0),
+      (String::from("q2"), q2)
+    ]);
+
+    assert_eq!(diff_state_positions(&old_state_positions, &new_state_positions), RevisionDiff {
+      added_states: vec![String::from("q2")],
+      removed_states: vec![String::from("q1")],
+      added_transitions: vec![transition("q0", "a", "q2"), transition("q0", "b", "q0"), transition("q2", "b", "q2")],
+      removed_transitions: vec![transition("q0", "a", "q1"), transition("q1", "b", "q1")]
+    });
+
+    assert_eq!(diff_state_positions(&new_state_positions, &new_state_positions), RevisionDiff::default());
+  }
+
+  #[test]
+  fn test_revision_snapshots() {
+    // Snapshots are stored as workspace files, so older ones have to keep reading back the same states
+    let snapshot = r#"{
+      "format": "inner-machinations-workspace",
+      "version": 1,
+      "workspace_name": "Ends in a",
+      "type_of_automata": "DFA",
+      "alphabet": ["a", "b"],
+      "should_strict_check": false,
+      "should_show_string_traversal": false,
+      "default_connection_character": "a",
+      "state_positions": {
+        "q0": { "id": "q0", "position": { "x": 300, "y": 300 }, "states_connected_to": { "a": ["q1"], "b": ["q0"] },
+          "is_start": true, "is_final": false, "element": "State" },
+        "q1": { "id": "q1", "position": { "x": 500, "y": 300 }, "states_connected_to": { "a": ["q1"], "b": ["q0"] },
+          "is_start": false, "is_final": true, "element": "State" }
+      },
+      "connections": []
+    }"#;
+
+    let workspace_file = WorkspaceFile::from_json(snapshot).unwrap();
+    assert_eq!(workspace_file.get_alphabet(), &[String::from("a"), String::from("b")]);
+    assert_eq!(workspace_file.get_state_positions().len(), 2);
+    // The snapshot has no connections, so they are drawn again from the states
+    assert_eq!(workspace_file.get_connections().len(), 4);
+
+    let diff = diff_state_positions(workspace_file.get_state_positions(), &HashMap::new());
+    assert_eq!(diff.removed_states, [String::from("q0"), String::from("q1")]);
+    assert_eq!(diff.removed_transitions.len(), 4);
+  }
+
+}
@@ -1,14 +1,32 @@
 use std::collections::HashMap;
 
-use app::{encrypt_user_data, sanitize_input_alphabet, establish_connection, models::State};
+use app::{encrypt_user_data, find_duplicate_state_name, sanitize_input_alphabet, serialize_input_alphabet, establish_connection, models::State};
 use app::models::{Connection, SavedWorkspace, TypeOfAutomata, User, WorkspaceData};
-use app::schema::{saved_connections, users, saved_states, saved_workspaces};
+use app::schema::{saved_connections, users, saved_states, saved_workspaces, workspace_revisions};
 
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
 use diesel::{ExpressionMethods, SqliteConnection};
 use magic_crypt::new_magic_crypt;
 use crate::diesel::QueryDsl;
 use crate::diesel::RunQueryDsl;
+use crate::workspace_file_funcs::WorkspaceFile;
+mod tests;
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum StateNameError {
+  UnknownState,
+  DuplicateName(String)
+}
+
+// Where the result of a command transforming the automaton goes. Previews are returned without being saved,
+// so the result can be compared with the original before deciding whether to keep it
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub enum WorkspaceTarget {
+  Overwrite,
+  NewWorkspace(String),
+  Preview
+}
 
 #[tauri::command]
 pub fn create_workspace(email: &str, workspace_name: &str) {
@@ -19,7 +37,8 @@ pub fn create_workspace(email: &str, workspace_name: &str) {
   let new_saved_automata = (
     saved_workspaces::user_id.eq(&user_id), 
     saved_workspaces::workspace_name.eq(&workspace_name),
-    saved_workspaces::type_of_automata.eq(&TypeOfAutomata::DFA)
+    saved_workspaces::type_of_automata.eq(&TypeOfAutomata::DFA),
+    saved_workspaces::alphabet.eq(serialize_input_alphabet(&[String::from("a"), String::from("b")]))
   );
 
   diesel::insert_into(saved_workspaces::table)
@@ -47,7 +66,7 @@ pub fn update_workspace_alphabet(workspace_name: &str, email: &str, alphabet: Ve
   let sanitized_input_alphabet = sanitize_input_alphabet(alphabet); 
   
   diesel::update(&workspace)
-    .set(saved_workspaces::alphabet.eq(sanitized_input_alphabet.join(",")))
+    .set(saved_workspaces::alphabet.eq(serialize_input_alphabet(&sanitized_input_alphabet)))
     .execute(&mut conn)
     .expect("There was an error updating the workspace's alphabet");
 
@@ -55,13 +74,18 @@ pub fn update_workspace_alphabet(workspace_name: &str, email: &str, alphabet: Ve
 
 }
 
+// Nothing is saved if two states share a name, as every export and the canvas tell states apart by their names
 #[tauri::command]
 pub fn save_workspace(
   workspace_name: &str, 
   states: HashMap<String, State>, 
   email: &str, 
   connections: Vec<Connection>
-) {
+) -> Result<(), StateNameError> {
+
+  if let Some(state_name) = find_duplicate_state_name(&states) {
+    return Err(StateNameError::DuplicateName(state_name));
+  }
 
   let mut conn = establish_connection();
   let user_id = get_user_id(&email, &mut conn);
@@ -71,14 +95,19 @@ pub fn save_workspace(
   save_states_to_db(&workspace.id, &states, &mut conn)
     .expect("There was an error saving the states to the database");
 
-  save_connections_to_db(&workspace.id, &connections, &mut conn)
+  save_connections_to_db(&workspace.id, &connections, &states, &mut conn)
     .expect("There was an error saving the connections to the database");
 
   set_current_time(&workspace.id, &mut conn)
     .expect("There was an error updating the last modified time of the workspace");
 
+  save_revision_to_db(&workspace, &mut conn)
+    .expect("There was an error saving a revision of the workspace");
+
   println!("Saved!");
 
+  Ok(())
+
 }
 
 #[tauri::command]
@@ -99,6 +128,11 @@ pub fn delete_workspace(workspace_name: &str, email: &str) {
     .execute(&mut conn)
     .expect("There was an error deleting the old workspace's previous connections");
 
+  diesel::delete(workspace_revisions::table)
+    .filter(workspace_revisions::workspace_id.eq(workspace.id))
+    .execute(&mut conn)
+    .expect("There was an error deleting the old workspace's revisions");
+
   diesel::delete(saved_workspaces::table)
     .filter(saved_workspaces::user_id.eq(&user_id))
     .filter(saved_workspaces::workspace_name.eq(&workspace_name))
@@ -115,6 +149,88 @@ pub fn does_workspace_name_exist(workspace_name: &str, email: &str) -> bool {
   return get_workspace(&workspace_name, &user_id, &mut conn).is_ok()
 }
 
+// Finds a name for a new workspace which does not clash with any of the user's existing workspaces,
+// by appending (1), (2), etc. to the requested name until one is free
+pub fn get_available_workspace_name(workspace_name: &str, email: &str) -> String {
+
+  let mut available_workspace_name = workspace_name.to_owned();
+  let mut suffix = 1;
+
+  while does_workspace_name_exist(&available_workspace_name, email) {
+    available_workspace_name = format!("{workspace_name} ({suffix})");
+    suffix += 1;
+  }
+
+  available_workspace_name
+
+}
+
+// Creates a brand new workspace holding the given automaton, which is how every importer adds workspaces.
+// Returns the name the workspace was actually created under, which differs from the one requested if it was taken
+pub fn create_workspace_with_automata(
+  email: &str,
+  workspace_name: &str,
+  type_of_automata: TypeOfAutomata,
+  alphabet: Vec<&str>,
+  state_positions: HashMap<String, State>,
+  connections: Vec<Connection>
+) -> String {
+
+  let workspace_name = get_available_workspace_name(workspace_name, email);
+
+  create_workspace(email, &workspace_name);
+  update_automata_type(&workspace_name, email, type_of_automata);
+  update_workspace_alphabet(&workspace_name, email, alphabet);
+  save_workspace(&workspace_name, state_positions, email, connections)
+    .expect("Imported states should have been checked to have unique names");
+
+  workspace_name
+
+}
+
+// Every transforming command hands its result here. The type and alphabet of the workspace it was run on are kept
+// unless new ones are given, and a new workspace is named as asked unless that name is taken
+pub fn save_automata_to_target(
+  workspace_name: &str,
+  email: &str,
+  target: WorkspaceTarget,
+  state_positions: HashMap<String, State>,
+  connections: Vec<Connection>,
+  type_of_automata: Option<TypeOfAutomata>,
+  alphabet: Option<Vec<String>>
+) -> WorkspaceData {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+  let type_of_automata = type_of_automata.unwrap_or(workspace_data.get_type_of_automata());
+  let alphabet = alphabet.unwrap_or_else(|| workspace_data.get_alphabet().to_owned());
+
+  match target {
+    WorkspaceTarget::Overwrite => {
+      // Saving stores a revision of the workspace, so the type and alphabet it is saved with have to be set first
+      update_automata_type(workspace_name, email, type_of_automata);
+      update_workspace_alphabet(workspace_name, email, alphabet.iter().map(|c| c.as_str()).collect());
+      save_workspace(workspace_name, state_positions, email, connections)
+        .expect("The states of a transformed automaton should have unique names");
+
+      retrieve_workspace_data(workspace_name, email)
+    },
+    WorkspaceTarget::NewWorkspace(new_workspace_name) => {
+      let new_workspace_name = create_workspace_with_automata(
+        email,
+        &new_workspace_name,
+        type_of_automata,
+        alphabet.iter().map(|c| c.as_str()).collect(),
+        state_positions,
+        connections
+      );
+
+      retrieve_workspace_data(&new_workspace_name, email)
+    },
+    WorkspaceTarget::Preview => workspace_data.with_automata(state_positions, connections, type_of_automata, alphabet)
+  }
+
+}
+
 #[tauri::command]
 pub fn update_workspace_name(original_workspace_name: &str, email: &str, new_workspace_name: &str) {
   let mut conn: SqliteConnection = establish_connection();
@@ -174,6 +290,47 @@ pub fn update_showing_string_traversal(workspace_name: &str, email: &str, should
 
 }
 
+// Input strings are split into symbols at the separator when one is given, and otherwise by taking the longest symbol
+// of the alphabet at each point. An empty separator is the same as not having one
+#[tauri::command]
+pub fn update_input_separator(workspace_name: &str, email: &str, input_separator: Option<String>) {
+  let mut conn: SqliteConnection = establish_connection();
+  let user_id = get_user_id(email, &mut conn);
+
+  let workspace = get_workspace(workspace_name, &user_id, &mut conn)
+    .expect("Could not retrieve the requested workspace");
+
+  let input_separator = input_separator.filter(|separator| !separator.is_empty());
+
+  diesel::update(&workspace)
+    .set(saved_workspaces::input_separator.eq(input_separator))
+    .execute(&mut conn)
+    .expect("Could not update the input separator for the workspace");
+
+}
+
+// Saving only keeps this many of the workspace's most recent revisions, so lowering it removes the oldest ones straight away.
+// Setting it to 0 stops revisions from being kept at all
+#[tauri::command]
+pub fn update_max_revisions(workspace_name: &str, email: &str, max_revisions: i32) {
+  let mut conn: SqliteConnection = establish_connection();
+  let user_id = get_user_id(email, &mut conn);
+
+  let workspace = get_workspace(workspace_name, &user_id, &mut conn)
+    .expect("Could not retrieve the requested workspace");
+
+  let max_revisions = max_revisions.max(0);
+
+  diesel::update(&workspace)
+    .set(saved_workspaces::max_revisions.eq(max_revisions))
+    .execute(&mut conn)
+    .expect("Could not update the maximum number of revisions for the workspace");
+
+  remove_old_revisions_from_db(&workspace.id, max_revisions, &mut conn)
+    .expect("There was an error removing the workspace's old revisions");
+
+}
+
 #[tauri::command]
 pub fn update_default_connection_character(workspace_name: &str, email: &str, default_connection_character: String) {
   // Should sanitize that string passed in is single character
@@ -192,6 +349,53 @@ pub fn update_default_connection_character(workspace_name: &str, email: &str, de
 
 }
 
+// Names are what states are shown under in the canvas and every export, so no two states in a workspace may share one.
+// Giving a state an empty name removes its name, so it is shown as q followed by its number again
+#[tauri::command]
+pub fn rename_state(workspace_name: &str, email: &str, state_key: &str, name: &str) -> Result<WorkspaceData, StateNameError> {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+  let mut state_positions = workspace_data.get_state_positions().to_owned();
+
+  let name = name.trim();
+  let name = if name.is_empty() { None } else { Some(name.to_owned()) };
+
+  validate_state_name(&state_positions, state_key, name.as_deref())?;
+
+  state_positions
+    .get_mut(state_key)
+    .expect("The state should have been checked to exist")
+    .set_name(name);
+
+  save_workspace(workspace_name, state_positions, email, workspace_data.get_list_of_connections().to_owned())?;
+
+  Ok(retrieve_workspace_data(workspace_name, email))
+
+}
+
+pub fn validate_state_name(state_positions: &HashMap<String, State>, state_key: &str, name: Option<&str>) -> Result<(), StateNameError> {
+
+  if !state_positions.contains_key(state_key) {
+    return Err(StateNameError::UnknownState);
+  }
+
+  let name = match name {
+    Some(name) => name,
+    None => return Ok(())
+  };
+
+  let is_name_taken = state_positions
+    .iter()
+    .any(|(other_state_key, other_state)| other_state_key != state_key && other_state.get_name() == Some(name));
+
+  if is_name_taken {
+    return Err(StateNameError::DuplicateName(name.to_owned()));
+  }
+
+  Ok(())
+
+}
+
 #[tauri::command]
 pub fn retrieve_workspace_data(workspace_name: &str, email: &str) -> WorkspaceData {
     
@@ -226,6 +430,17 @@ pub fn get_users_saved_workspaces(email: &str) -> Vec<String> {
 
 }
 
+pub fn get_workspace_id(workspace_name: &str, email: &str) -> i32 {
+
+  let mut conn: SqliteConnection = establish_connection();
+  let user_id = get_user_id(email, &mut conn);
+
+  get_workspace(workspace_name, &user_id, &mut conn)
+    .expect("Could not retrieve the requested workspace")
+    .id
+
+}
+
 fn get_workspace(workspace_name: &str, user_id: &i32, conn: &mut SqliteConnection) -> Result<SavedWorkspace, diesel::result::Error> {
   
   saved_workspaces::table
@@ -248,13 +463,15 @@ fn save_states_to_db(workspace_id: &i32, states: &HashMap<String, State>, conn:
 
   let mut states_to_be_inserted =  vec![];
 
-  for (state_pos_key, state) in states {
+  for (state_key, state) in states {
     // Inserting state in the case a state has no connections
     states_to_be_inserted.push((
       saved_states::workspace_id.eq(workspace_id),
-      saved_states::position.eq(state_pos_key),
+      saved_states::position.eq(state.get_position_as_string()),
       saved_states::is_start.eq(state.is_start()),
       saved_states::is_final.eq(state.is_final()),
+      saved_states::state_id.eq(state_key),
+      saved_states::name.eq(state.get_name())
     ));
   }
 
@@ -283,7 +500,13 @@ fn get_user_id(email: &str, conn: &mut SqliteConnection) -> i32 {
   user.id
 }
 
-fn save_connections_to_db(workspace_id: &i32, connections: &Vec<Connection>, conn: &mut SqliteConnection) -> Result<(), diesel::result::Error> {
+// Connections drawn on the canvas do not know the ids of the states they join, so they are found from the ends of the curve instead
+fn save_connections_to_db(
+  workspace_id: &i32,
+  connections: &Vec<Connection>,
+  states: &HashMap<String, State>,
+  conn: &mut SqliteConnection
+) -> Result<(), diesel::result::Error> {
 
   // First delete all existing connections relating to the current automata
   diesel::delete(saved_connections::table)
@@ -299,7 +522,9 @@ fn save_connections_to_db(workspace_id: &i32, connections: &Vec<Connection>, con
       saved_connections::start_point.eq::<String>(connection.curve.start_point.into()),
       saved_connections::control_point_one.eq::<String>(connection.curve.control_point_one.into()),
       saved_connections::control_point_two.eq::<String>(connection.curve.control_point_two.into()),
-      saved_connections::end_point.eq::<String>(connection.curve.end_point.into())
+      saved_connections::end_point.eq::<String>(connection.curve.end_point.into()),
+      saved_connections::start_state_id.eq(connection.get_start_state_key(states)),
+      saved_connections::end_state_id.eq(connection.get_end_state_key(states))
     );
     connections_to_be_inserted.push(connection_to_be_inserted);
   }
@@ -314,6 +539,47 @@ fn save_connections_to_db(workspace_id: &i32, connections: &Vec<Connection>, con
 
 }
 
+// Revisions are snapshots of the workspace as it was just saved, in the layout it is exported in
+fn save_revision_to_db(workspace: &SavedWorkspace, conn: &mut SqliteConnection) -> Result<(), diesel::result::Error> {
+
+  if workspace.max_revisions <= 0 {
+    return Ok(());
+  }
+
+  let saved_workspace = saved_workspaces::table
+    .filter(saved_workspaces::id.eq(workspace.id))
+    .get_result::<SavedWorkspace>(conn)?;
+  let workspace_file = WorkspaceFile::new(&workspace.workspace_name, &WorkspaceData::new(saved_workspace));
+
+  diesel::insert_into(workspace_revisions::table)
+    .values((
+      workspace_revisions::workspace_id.eq(workspace.id),
+      workspace_revisions::date_of_revision.eq::<NaiveDateTime>(chrono::offset::Local::now().naive_local()),
+      workspace_revisions::snapshot.eq(workspace_file.to_json())
+    ))
+    .execute(conn)?;
+
+  remove_old_revisions_from_db(&workspace.id, workspace.max_revisions, conn)
+
+}
+
+fn remove_old_revisions_from_db(workspace_id: &i32, max_revisions: i32, conn: &mut SqliteConnection) -> Result<(), diesel::result::Error> {
+
+  let old_revision_ids: Vec<i32> = workspace_revisions::table
+    .filter(workspace_revisions::workspace_id.eq(workspace_id))
+    .order(workspace_revisions::id.desc())
+    .offset(max_revisions.max(0) as i64)
+    .select(workspace_revisions::id)
+    .load(conn)?;
+
+  diesel::delete(workspace_revisions::table)
+    .filter(workspace_revisions::id.eq_any(old_revision_ids))
+    .execute(conn)?;
+
+  Ok(())
+
+}
+
 fn set_current_time(workspace_id: &i32, conn: &mut SqliteConnection) -> Result<(), diesel::result::Error> {
 
   // Sets time of last update to current time
@@ -0,0 +1,139 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::saving_automata_funcs::{create_workspace, get_users_saved_workspaces, retrieve_workspace_data, save_automata_to_target,
+  save_workspace, validate_state_name, StateNameError, WorkspaceTarget};
+  use crate::test_fixtures::set_up_test_user;
+  use app::create_connections_from_state_positions;
+  use app::models::{Connection, Coordinate, State, TypeOfAutomata, WorkspaceData};
+  use std::collections::{BTreeSet, HashMap};
+
+  fn create_named_state(coordinate: Coordinate, name: &str) -> State {
+    let mut state = State::new(coordinate, false, false);
+    state.set_name(Some(name.to_owned()));
+    state
+  }
+
+  #[test]
+  fn test_saving_duplicate_state_names() {
+    let mut q0 = create_named_state(Coordinate { x: 300, y: 300 }, "start");
+    q0.add_connection("a", "500,300");
+    let state_positions = HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("500,300"), create_named_state(Coordinate { x: 500, y: 300 }, "start"))
+    ]);
+    let connections = create_connections_from_state_positions(&state_positions);
+
+    // The names are checked before the database is touched, so no workspace is needed
+    assert_eq!(
+      save_workspace("Duplicate names", state_positions, "nobody@example.com", connections),
+      Err(StateNameError::DuplicateName(String::from("start")))
+    );
+  }
+
+  #[test]
+  fn test_validating_state_names() {
+    let state_positions = HashMap::from([
+      (String::from("300,300"), create_named_state(Coordinate { x: 300, y: 300 }, "start")),
+      (String::from("500,300"), State::new(Coordinate { x: 500, y: 300 }, false, true))
+    ]);
+
+    assert_eq!(validate_state_name(&state_positions, "500,300", Some("start")), Err(StateNameError::DuplicateName(String::from("start"))));
+    assert_eq!(validate_state_name(&state_positions, "300,300", Some("start")), Ok(()));
+    assert_eq!(validate_state_name(&state_positions, "500,300", None), Ok(()));
+    assert_eq!(validate_state_name(&state_positions, "700,300", Some("end")), Err(StateNameError::UnknownState));
+  }
+
+  // q0 reads a into q1, which is final
+  fn create_automaton() -> (HashMap<String, State>, Vec<Connection>) {
+    let mut q0 = State::with_id("q0", Coordinate { x: 300, y: 300 }, true, false);
+    let q1 = State::with_id("q1", Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection("a", "q1");
+
+    let state_positions = HashMap::from([(String::from("q0"), q0), (String::from("q1"), q1)]);
+    let connections = create_connections_from_state_positions(&state_positions);
+    (state_positions, connections)
+  }
+
+  fn assert_holds_automaton(workspace_data: &WorkspaceData, state_positions: &HashMap<String, State>) {
+    assert_eq!(workspace_data.get_state_positions().len(), state_positions.len());
+    for (state_key, state) in state_positions {
+      assert_eq!(workspace_data.get_state_positions()[state_key].get_all_connections(), state.get_all_connections());
+    }
+  }
+
+  #[test]
+  fn test_saving_to_targets() {
+    let email = "targets@example.com";
+    let _database = set_up_test_user(email);
+    create_workspace(email, "Targets");
+    let (state_positions, connections) = create_automaton();
+
+    // Previews carry the given type and alphabet without anything being saved
+    let preview = save_automata_to_target(
+      "Targets",
+      email,
+      WorkspaceTarget::Preview,
+      state_positions.clone(),
+      connections.clone(),
+      Some(TypeOfAutomata::NFA),
+      Some(vec![String::from("a"), String::from("c")])
+    );
+    assert_eq!(preview.get_workspace_name(), "Targets");
+    assert_eq!(preview.get_type_of_automata(), TypeOfAutomata::NFA);
+    assert_eq!(preview.get_alphabet(), &[String::from("a"), String::from("c")]);
+    assert_holds_automaton(&preview, &state_positions);
+
+    let unchanged = retrieve_workspace_data("Targets", email);
+    assert!(unchanged.get_state_positions().is_empty());
+    assert_eq!(unchanged.get_type_of_automata(), TypeOfAutomata::DFA);
+
+    // Overwriting keeps the type and alphabet of the workspace when no others are given
+    let overwritten = save_automata_to_target("Targets", email, WorkspaceTarget::Overwrite, state_positions.clone(), connections.clone(), None, None);
+    assert_eq!(overwritten.get_type_of_automata(), TypeOfAutomata::DFA);
+    assert_eq!(overwritten.get_alphabet(), &[String::from("a"), String::from("b")]);
+    assert_holds_automaton(&overwritten, &state_positions);
+    assert_holds_automaton(&retrieve_workspace_data("Targets", email), &state_positions);
+
+    // A new workspace is given the automaton and leaves the one it came from as it was
+    let new_workspace = save_automata_to_target(
+      "Targets",
+      email,
+      WorkspaceTarget::NewWorkspace(String::from("Targets as an NFA")),
+      HashMap::new(),
+      vec![],
+      Some(TypeOfAutomata::NFA),
+      None
+    );
+    assert_eq!(new_workspace.get_workspace_name(), "Targets as an NFA");
+    assert_eq!(new_workspace.get_type_of_automata(), TypeOfAutomata::NFA);
+    assert_eq!(new_workspace.get_alphabet(), &[String::from("a"), String::from("b")]);
+    assert!(new_workspace.get_state_positions().is_empty());
+    assert_holds_automaton(&retrieve_workspace_data("Targets", email), &state_positions);
+  }
+
+  #[test]
+  fn test_naming_new_workspaces() {
+    let email = "new_workspaces@example.com";
+    let _database = set_up_test_user(email);
+    create_workspace(email, "Copies");
+    let (state_positions, connections) = create_automaton();
+
+    let save_copy = |new_workspace_name: &str| {
+      let target = WorkspaceTarget::NewWorkspace(new_workspace_name.to_owned());
+      save_automata_to_target("Copies", email, target, state_positions.clone(), connections.clone(), None, None)
+    };
+
+    // Names already taken have the first free number appended
+    assert_eq!(save_copy("Copy").get_workspace_name(), "Copy");
+    assert_eq!(save_copy("Copy").get_workspace_name(), "Copy (1)");
+    assert_eq!(save_copy("Copy").get_workspace_name(), "Copy (2)");
+    assert_eq!(save_copy("Copies").get_workspace_name(), "Copies (1)");
+
+    assert_holds_automaton(&retrieve_workspace_data("Copy (2)", email), &state_positions);
+    assert_eq!(
+      get_users_saved_workspaces(email).into_iter().collect::<BTreeSet<String>>(),
+      BTreeSet::from(["Copies", "Copies (1)", "Copy", "Copy (1)", "Copy (2)"].map(String::from))
+    );
+  }
+
+}
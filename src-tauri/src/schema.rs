@@ -9,6 +9,8 @@ diesel::table! {
         control_point_two -> Text,
         end_point -> Text,
         connection_character -> Text,
+        start_state_id -> Text,
+        end_state_id -> Text,
     }
 }
 
@@ -19,6 +21,8 @@ diesel::table! {
         position -> Text,
         is_start -> Bool,
         is_final -> Bool,
+        state_id -> Text,
+        name -> Nullable<Text>,
     }
 }
 
@@ -33,6 +37,8 @@ diesel::table! {
         should_show_string_traversal -> Bool,
         should_strict_check -> Bool,
         default_connection_character -> Text,
+        input_separator -> Nullable<Text>,
+        max_revisions -> Integer,
     }
 }
 
@@ -46,13 +52,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    workspace_revisions (id) {
+        id -> Integer,
+        workspace_id -> Integer,
+        date_of_revision -> Timestamp,
+        snapshot -> Text,
+    }
+}
+
 diesel::joinable!(saved_connections -> saved_workspaces (workspace_id));
 diesel::joinable!(saved_states -> saved_workspaces (workspace_id));
 diesel::joinable!(saved_workspaces -> users (user_id));
+diesel::joinable!(workspace_revisions -> saved_workspaces (workspace_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     saved_connections,
     saved_states,
     saved_workspaces,
     users,
+    workspace_revisions,
 );
@@ -0,0 +1,174 @@
+use std::collections::{HashMap, HashSet};
+
+use app::get_alphabet_from_state_positions;
+use app::labels::expand_symbolic_labels;
+use app::models::State;
+
+use crate::advanced_automata_funcs::determinize_state_positions;
+use crate::regular_expression_funcs::{convert_regex_to_state_positions, regex_models::ParsingError};
+mod tests;
+
+#[tauri::command]
+pub fn generate_strings_from_automata(
+  mut state_positions: HashMap<String, State>,
+  start_state_key: &str,
+  input_alphabet: Vec<String>,
+  maximum_count: usize,
+  maximum_length: usize,
+  should_generate_rejected: bool
+) -> Vec<String> {
+
+  if !state_positions.contains_key(start_state_key) {
+    return vec![];
+  }
+
+  // Strings are built one symbol at a time, so classes and [*] are first turned into the symbols of the alphabet they match
+  expand_symbolic_labels(&mut state_positions, &input_alphabet);
+
+  // Enumerating over an NFA directly would produce the same string once for every path which accepts it,
+  // so we determinize first to guarantee every string is only ever visited once
+  let state_positions = determinize_state_positions(state_positions, start_state_key);
+
+  enumerate_strings_in_shortlex_order(
+    &state_positions,
+    start_state_key,
+    input_alphabet,
+    maximum_count,
+    maximum_length,
+    should_generate_rejected
+  )
+
+}
+
+#[tauri::command]
+pub fn generate_strings_from_regex(
+  regex: &str,
+  maximum_count: usize,
+  maximum_length: usize,
+  should_generate_rejected: bool
+) -> Result<Vec<String>, ParsingError> {
+
+  let (state_positions, start_state_key) = convert_regex_to_state_positions(regex)?;
+
+  Ok(enumerate_strings_in_shortlex_order(
+    &state_positions,
+    &start_state_key,
+    vec![],
+    maximum_count,
+    maximum_length,
+    should_generate_rejected
+  ))
+
+}
+
+// Shortlex order lists strings by length first, and then alphabetically for strings of the same length.
+// We walk the DFA one length at a time, so every string of length n is produced before any string of length n + 1.
+// The alphabet used is the given input alphabet combined with every character actually used in a transition
+fn enumerate_strings_in_shortlex_order(
+  state_positions: &HashMap<String, State>,
+  start_state_key: &str,
+  input_alphabet: Vec<String>,
+  maximum_count: usize,
+  maximum_length: usize,
+  should_generate_rejected: bool
+) -> Vec<String> {
+
+  let mut alphabet = get_alphabet_from_state_positions(state_positions);
+  alphabet.extend(input_alphabet);
+
+  let state_keys_which_reach_final_state = get_state_keys_which_reach_final_state(state_positions);
+
+  let mut generated_strings = vec![];
+
+  if maximum_count == 0 {
+    return generated_strings;
+  }
+
+  // A state key of None means the string has taken a transition which is not specified in the DFA,
+  // and thus is in the implicit "vortex" state which can never accept
+  let mut current_length_strings: Vec<(String, Option<String>)> = vec![(String::new(), Some(start_state_key.to_owned()))];
+
+  for length in 0..=maximum_length {
+
+    for (string, state_key) in &current_length_strings {
+
+      let is_accepted = state_key
+        .as_ref()
+        .and_then(|state_key| state_positions.get(state_key))
+        .is_some_and(|state| state.is_final());
+
+      if is_accepted != should_generate_rejected {
+        generated_strings.push(string.to_owned());
+
+        if generated_strings.len() >= maximum_count {
+          return generated_strings;
+        }
+      }
+    }
+
+    if length == maximum_length {
+      break;
+    }
+
+    let mut next_length_strings = vec![];
+
+    for (string, state_key) in current_length_strings {
+      for character in &alphabet {
+
+        let next_state_key = state_key
+          .as_ref()
+          .and_then(|state_key| state_positions.get(state_key))
+          .and_then(|state| state.get_first_connected_state_key_by_character(character));
+
+        // When generating accepted strings there is no point extending a string which can never reach a final state
+        let can_be_accepted = next_state_key
+          .as_ref()
+          .is_some_and(|next_state_key| state_keys_which_reach_final_state.contains(next_state_key));
+
+        if should_generate_rejected || can_be_accepted {
+          next_length_strings.push((string.to_owned() + character, next_state_key));
+        }
+      }
+    }
+
+    current_length_strings = next_length_strings;
+  }
+
+  generated_strings
+
+}
+
+// A state reaches a final state if it is final itself, or if it has a connection to a state which reaches a final state
+pub fn get_state_keys_which_reach_final_state(state_positions: &HashMap<String, State>) -> HashSet<String> {
+
+  let mut state_keys_which_reach_final_state: HashSet<String> = state_positions
+    .iter()
+    .filter(|(_, state)| state.is_final())
+    .map(|(state_key, _)| state_key.to_owned())
+    .collect();
+
+  let mut finished = false;
+
+  while !finished {
+    finished = true;
+
+    for (state_key, state) in state_positions {
+      if state_keys_which_reach_final_state.contains(state_key) {
+        continue;
+      }
+
+      let reaches_final_state = state
+        .get_all_connected_state_keys()
+        .iter()
+        .any(|connected_state_key| state_keys_which_reach_final_state.contains(*connected_state_key));
+
+      if reaches_final_state {
+        state_keys_which_reach_final_state.insert(state_key.to_owned());
+        finished = false;
+      }
+    }
+  }
+
+  state_keys_which_reach_final_state
+
+}
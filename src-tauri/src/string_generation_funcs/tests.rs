@@ -0,0 +1,75 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::string_generation_funcs::{generate_strings_from_automata, generate_strings_from_regex};
+  use crate::string_sampling_funcs::sample_accepted_string;
+  use crate::test_fixtures::create_state_positions_with_classes;
+  use app::models::{Coordinate, State};
+  use std::collections::HashMap;
+
+  fn symbols(symbols: &[&str]) -> Vec<String> {
+    symbols.iter().map(|symbol| symbol.to_string()).collect()
+  }
+
+  #[test]
+  fn test_generating_strings_from_regex() {
+
+    let accepted_strings = generate_strings_from_regex("(a+b)*abb", 4, 10, false).unwrap();
+    assert_eq!(accepted_strings, vec!["abb", "aabb", "babb", "aaabb"]);
+
+    let accepted_strings = generate_strings_from_regex("ab+ba", 10, 10, false).unwrap();
+    assert_eq!(accepted_strings, vec!["ab", "ba"]);
+
+    // Rejected strings are enumerated over the characters used in the regex
+    let rejected_strings = generate_strings_from_regex("a*b", 5, 2, true).unwrap();
+    assert_eq!(rejected_strings, vec!["", "a", "aa", "ba", "bb"]);
+
+  }
+
+  #[test]
+  fn test_generating_rejected_strings_over_alphabet() {
+    // Accepts only a, over an alphabet which also has c even though no transition reads it
+    let mut q0 = State::with_id("q0", Coordinate { x: 300, y: 300 }, true, false);
+    let q1 = State::with_id("q1", Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection("a", "q1");
+    let state_positions = HashMap::from([(String::from("q0"), q0), (String::from("q1"), q1)]);
+
+    // Shortlex order puts shorter strings first and sorts strings of the same length, whatever order the alphabet is in
+    assert_eq!(
+      generate_strings_from_automata(state_positions, "q0", symbols(&["c", "a", "b"]), 8, 2, true),
+      symbols(&["", "b", "c", "aa", "ab", "ac", "ba", "bb"])
+    );
+  }
+
+  #[test]
+  fn test_generating_strings_with_classes() {
+    let alphabet = symbols(&["a", "b", "7", "if"]);
+
+    assert_eq!(
+      generate_strings_from_automata(create_state_positions_with_classes(), "q0", alphabet.clone(), 10, 3, false),
+      symbols(&["a", "b", "77", "if7"])
+    );
+    // Rejected strings are built from the symbols of the alphabet rather than from the labels
+    let rejected_strings = generate_strings_from_automata(create_state_positions_with_classes(), "q0", alphabet, 6, 1, true);
+    assert_eq!(rejected_strings, symbols(&["", "7", "if"]));
+  }
+
+  #[test]
+  fn test_generating_no_strings() {
+    // The empty string is the first string in shortlex order, but asking for no strings still gives none
+    assert_eq!(generate_strings_from_regex("a*", 0, 3, false).unwrap(), Vec::<String>::new());
+    assert_eq!(generate_strings_from_regex("a*", 1, 3, false).unwrap(), symbols(&[""]));
+    assert!(generate_strings_from_automata(create_state_positions_with_classes(), "q0", symbols(&["a"]), 0, 3, true).is_empty());
+  }
+
+  #[test]
+  fn test_sampling_strings_with_classes() {
+    let alphabet = symbols(&["a", "b", "7", "if"]);
+
+    for seed in 0..10 {
+      let sampled_string = sample_accepted_string(create_state_positions_with_classes(), "q0", alphabet.clone(), 2, Some(seed)).unwrap();
+      assert!(matches!(sampled_string.as_deref(), Some("77" | "if7")), "{sampled_string:?}");
+    }
+    assert_eq!(sample_accepted_string(create_state_positions_with_classes(), "q0", alphabet, 3, Some(0)), Ok(None));
+  }
+
+}
@@ -0,0 +1,122 @@
+use std::collections::{BTreeSet, HashMap};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Serialize;
+
+use app::get_alphabet_from_state_positions;
+use app::labels::expand_symbolic_labels;
+use app::models::State;
+
+use crate::advanced_automata_funcs::determinize_state_positions;
+mod tests;
+
+#[derive(PartialEq, Eq, Debug, Serialize)]
+pub enum SamplingError {
+  // The number of accepted strings does not fit into a u64
+  TooManyStrings
+}
+
+// Picks an accepted string of the given length uniformly at random, or returns None if no accepted string of that length exists.
+// Supplying a seed makes the choice reproducible, which is mostly useful for testing
+#[tauri::command]
+pub fn sample_accepted_string(
+  mut state_positions: HashMap<String, State>,
+  start_state_key: &str,
+  input_alphabet: Vec<String>,
+  length: usize,
+  seed: Option<u64>
+) -> Result<Option<String>, SamplingError> {
+
+  if !state_positions.contains_key(start_state_key) {
+    return Ok(None);
+  }
+
+  // Classes and [*] stand for every symbol of the alphabet they match, each of which is counted as a string of its own
+  expand_symbolic_labels(&mut state_positions, &input_alphabet);
+
+  // Counting paths in an NFA would count a string once for every path which accepts it, and so
+  // strings with many accepting paths would be picked more often. Determinizing first avoids this
+  let state_positions = determinize_state_positions(state_positions, start_state_key);
+  let alphabet = get_alphabet_from_state_positions(&state_positions);
+
+  let accepted_string_counts = count_accepted_strings_by_length(&state_positions, &alphabet, length)?;
+
+  if accepted_string_counts[length][start_state_key] == 0 {
+    return Ok(None);
+  }
+
+  let mut rng = match seed {
+    Some(seed) => StdRng::seed_from_u64(seed),
+    None => StdRng::from_entropy()
+  };
+
+  let mut sampled_string = String::new();
+  let mut current_state_key = start_state_key.to_owned();
+
+  for remaining_length in (1..=length).rev() {
+
+    // Each character is chosen with probability proportional to the number of accepted strings which begin with it,
+    // which is exactly what makes every accepted string of the given length equally likely
+    let mut choice = rng.gen_range(0..accepted_string_counts[remaining_length][&current_state_key]);
+
+    for character in &alphabet {
+      let next_state_key = match state_positions[&current_state_key].get_first_connected_state_key_by_character(character) {
+        Some(next_state_key) => next_state_key,
+        None => continue
+      };
+
+      let number_of_strings_through_character = accepted_string_counts[remaining_length - 1][&next_state_key];
+
+      if choice < number_of_strings_through_character {
+        sampled_string += character;
+        current_state_key = next_state_key;
+        break;
+      }
+
+      choice -= number_of_strings_through_character;
+    }
+  }
+
+  Ok(Some(sampled_string))
+
+}
+
+// The returned vector is indexed by length, and maps every state key to the number of strings of that length
+// which are accepted when starting from that state. Missing transitions are treated as leading to rejection
+pub fn count_accepted_strings_by_length(
+  state_positions: &HashMap<String, State>,
+  alphabet: &BTreeSet<String>,
+  maximum_length: usize
+) -> Result<Vec<HashMap<String, u64>>, SamplingError> {
+
+  // The only string of length 0 is the empty string, which is accepted exactly when the state is final
+  let mut accepted_string_counts: Vec<HashMap<String, u64>> = vec![state_positions
+    .iter()
+    .map(|(state_key, state)| (state_key.to_owned(), if state.is_final() { 1 } else { 0 }))
+    .collect()];
+
+  for length in 1..=maximum_length {
+
+    let mut current_length_counts = HashMap::new();
+
+    for (state_key, state) in state_positions {
+
+      let mut count: u64 = 0;
+
+      for character in alphabet {
+        if let Some(next_state_key) = state.get_first_connected_state_key_by_character(character) {
+          count = count
+            .checked_add(accepted_string_counts[length - 1][&next_state_key])
+            .ok_or(SamplingError::TooManyStrings)?;
+        }
+      }
+
+      current_length_counts.insert(state_key.to_owned(), count);
+    }
+
+    accepted_string_counts.push(current_length_counts);
+  }
+
+  Ok(accepted_string_counts)
+
+}
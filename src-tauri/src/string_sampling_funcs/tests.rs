@@ -0,0 +1,62 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::string_sampling_funcs::{sample_accepted_string, SamplingError};
+  use app::models::{Coordinate, State};
+  use std::collections::{BTreeMap, HashMap};
+
+  fn symbols(symbols: &[&str]) -> Vec<String> {
+    symbols.iter().map(|symbol| symbol.to_string()).collect()
+  }
+
+  // An NFA over {a, b} accepting the strings containing an a, which is reached by as many paths as the string has a's
+  fn create_state_positions() -> HashMap<String, State> {
+    let mut q0 = State::with_id("q0", Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::with_id("q1", Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection("a", "q0");
+    q0.add_connection("b", "q0");
+    q0.add_connection("a", "q1");
+    q1.add_connection("a", "q1");
+    q1.add_connection("b", "q1");
+
+    HashMap::from([
+      (String::from("q0"), q0),
+      (String::from("q1"), q1)
+    ])
+  }
+
+  fn sample(length: usize, seed: u64) -> Result<Option<String>, SamplingError> {
+    sample_accepted_string(create_state_positions(), "q0", symbols(&["a", "b"]), length, Some(seed))
+  }
+
+  #[test]
+  fn test_sampling_with_a_seed() {
+    for seed in 0..20 {
+      assert_eq!(sample(5, seed), sample(5, seed));
+    }
+  }
+
+  #[test]
+  fn test_sampling_uniformly() {
+    // Every string of length 3 but bbb is accepted, and each should be picked about as often as the others even though
+    // aaa is accepted along three paths and bba along one
+    let mut frequencies: BTreeMap<String, usize> = BTreeMap::new();
+    for seed in 0..7000 {
+      *frequencies.entry(sample(3, seed).unwrap().unwrap()).or_default() += 1;
+    }
+
+    assert_eq!(frequencies.keys().cloned().collect::<Vec<String>>(), symbols(&["aaa", "aab", "aba", "abb", "baa", "bab", "bba"]));
+    for (sampled_string, frequency) in frequencies {
+      assert!((800..1200).contains(&frequency), "{sampled_string} was sampled {frequency} times");
+    }
+  }
+
+  #[test]
+  fn test_sampling_no_strings() {
+    // The empty string has no a in it, and a missing start state accepts nothing
+    assert_eq!(sample(0, 0), Ok(None));
+    assert_eq!(sample_accepted_string(create_state_positions(), "q2", symbols(&["a", "b"]), 3, Some(0)), Ok(None));
+
+    assert_eq!(sample(70, 0), Err(SamplingError::TooManyStrings));
+  }
+
+}
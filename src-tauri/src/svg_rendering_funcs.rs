@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use std::fs;
+
+use serde::Serialize;
+
+use app::models::{BezierCurve, Connection, Coordinate, State, TypeOfAutomata};
+
+use crate::saving_automata_funcs::retrieve_workspace_data;
+use crate::testing_automata_funcs::{test_string_dfa, test_string_nfa};
+mod tests;
+
+// Sizes are the same as those used when drawing on the canvas, so an exported image looks like the workspace it came from
+const STATE_RADIUS: f64 = 35.0;
+const FINAL_STATE_OUTER_RADIUS: f64 = 42.0;
+const ARROW_HEAD_SIZE: f64 = 30.0;
+const LABEL_OFFSET: f64 = 50.0;
+const START_ARROW_LENGTH: f64 = 80.0;
+const MARGIN: f64 = 20.0;
+
+// The same colour used to highlight states while a string is being traversed on the canvas
+const HIGHLIGHT_COLOUR: &str = "rgb(218,112,214)";
+
+type Point = (f64, f64);
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum SvgExportError {
+  UnableToWriteFile
+}
+
+// If a string to check is given, the states and connections visited while checking it are highlighted
+#[tauri::command]
+pub fn render_workspace_to_svg(workspace_name: &str, email: &str, string_to_check: Option<String>) -> String {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+  let state_positions = workspace_data.get_state_positions();
+
+  let states_visited = match (string_to_check, workspace_data.get_start_state_position()) {
+    (Some(string_to_check), Some(start_state_key)) => {
+      let test_string = match workspace_data.get_type_of_automata() {
+        TypeOfAutomata::DFA => test_string_dfa,
+        TypeOfAutomata::NFA => test_string_nfa
+      };
+      test_string(state_positions.to_owned(), start_state_key.to_owned(), string_to_check).1
+    },
+    _ => vec![]
+  };
+
+  render_automata_to_svg(state_positions, workspace_data.get_list_of_connections(), &states_visited)
+
+}
+
+#[tauri::command]
+pub fn export_workspace_to_svg(
+  workspace_name: &str,
+  email: &str,
+  string_to_check: Option<String>,
+  path: &str
+) -> Result<(), SvgExportError> {
+
+  let svg = render_workspace_to_svg(workspace_name, email, string_to_check);
+
+  fs::write(path, svg)
+    .map_err(|_| SvgExportError::UnableToWriteFile)
+
+}
+
+// Connections are drawn first so the states sit on top of them. Each connection is stored running from the centre of
+// one state to the centre of another, so it is trimmed to the edges of both states to leave its arrow head visible
+pub fn render_automata_to_svg(
+  state_positions: &HashMap<String, State>,
+  connections: &[Connection],
+  states_visited: &[State]
+) -> String {
+
+  let visited_positions: Vec<Coordinate> = states_visited
+    .iter()
+    .map(|state| state.get_position())
+    .collect();
+
+  let mut bounds = Bounds::new();
+  let mut connection_elements = String::new();
+  let mut state_elements = String::new();
+
+  for connection in connections {
+    let curve = &connection.curve;
+
+    // A connection is on the path if the string moved from its start state directly to its end state at some point
+    let is_highlighted = visited_positions
+      .windows(2)
+      .any(|step| step[0] == curve.start_point && step[1] == curve.end_point);
+    let colour = if is_highlighted { HIGHLIGHT_COLOUR } else { "black" };
+
+    let start_t = find_curve_parameter_outside_state(curve, curve.start_point, 0.0, 1.0);
+    let end_t = find_curve_parameter_outside_state(curve, curve.end_point, 1.0, start_t);
+    let [start_point, control_point_one, control_point_two, end_point] = get_sub_curve(curve, start_t, end_t);
+
+    // The arrow head points along the curve's direction at the point it meets the end state
+    let angle = (end_point.1 - control_point_two.1).atan2(end_point.0 - control_point_two.0);
+    let arrow_point_one = (
+      end_point.0 - ARROW_HEAD_SIZE * (angle - std::f64::consts::FRAC_PI_6).cos(),
+      end_point.1 - ARROW_HEAD_SIZE * (angle - std::f64::consts::FRAC_PI_6).sin()
+    );
+    let arrow_point_two = (
+      end_point.0 - ARROW_HEAD_SIZE * (angle + std::f64::consts::FRAC_PI_6).cos(),
+      end_point.1 - ARROW_HEAD_SIZE * (angle + std::f64::consts::FRAC_PI_6).sin()
+    );
+
+    // Labels are placed beside the middle of the curve, offset the same way as on the canvas
+    let halfway_point = get_point_on_curve(curve, 0.5);
+    let label_position = (
+      halfway_point.0 + LABEL_OFFSET * angle.sin(),
+      halfway_point.1 - LABEL_OFFSET * angle.cos()
+    );
+
+    connection_elements += &format!(
+      "  <path d=\"M {} {} C {} {}, {} {}, {} {}\" fill=\"none\" stroke=\"{colour}\" stroke-width=\"5\" stroke-linecap=\"round\"/>\n",
+      format_number(start_point.0), format_number(start_point.1),
+      format_number(control_point_one.0), format_number(control_point_one.1),
+      format_number(control_point_two.0), format_number(control_point_two.1),
+      format_number(end_point.0), format_number(end_point.1)
+    );
+    connection_elements += &format!(
+      "  <polygon points=\"{},{} {},{} {},{}\" fill=\"{colour}\"/>\n",
+      format_number(end_point.0), format_number(end_point.1),
+      format_number(arrow_point_one.0), format_number(arrow_point_one.1),
+      format_number(arrow_point_two.0), format_number(arrow_point_two.1)
+    );
+    connection_elements += &format!(
+      "  <text x=\"{}\" y=\"{}\" font-family=\"Arial\" font-size=\"40\" text-anchor=\"middle\" dominant-baseline=\"middle\" fill=\"{colour}\">{}</text>\n",
+      format_number(label_position.0),
+      format_number(label_position.1),
+      escape_xml_string(&connection.connection_character)
+    );
+
+    for point in [curve.start_point, curve.control_point_one, curve.control_point_two, curve.end_point] {
+      bounds.include(point.x as f64, point.y as f64, 0.0);
+    }
+    bounds.include(label_position.0, label_position.1, LABEL_OFFSET / 2.0);
+  }
+
+  // States are drawn in sorted order of their keys so the same workspace always produces the same image
+  let mut sorted_state_positions: Vec<(&String, &State)> = state_positions.iter().collect();
+  sorted_state_positions.sort_by_key(|(state_key, _)| *state_key);
+
+  for (_, state) in sorted_state_positions {
+    let position = state.get_position();
+    let (x, y) = (position.x as f64, position.y as f64);
+    let fill = if visited_positions.contains(&position) { HIGHLIGHT_COLOUR } else { "white" };
+
+    if state.is_final() {
+      state_elements += &format!(
+        "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"3\"/>\n",
+        format_number(x), format_number(y), format_number(FINAL_STATE_OUTER_RADIUS)
+      );
+    }
+
+    state_elements += &format!(
+      "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{fill}\" stroke=\"black\" stroke-width=\"3\"/>\n",
+      format_number(x), format_number(y), format_number(STATE_RADIUS)
+    );
+
+    // Start states are marked with an arrow coming in from the left
+    if state.is_start() {
+      let tip = x - FINAL_STATE_OUTER_RADIUS - 2.0;
+      state_elements += &format!(
+        "  <path d=\"M {} {} L {} {}\" stroke=\"black\" stroke-width=\"5\" stroke-linecap=\"round\"/>\n",
+        format_number(tip - START_ARROW_LENGTH), format_number(y), format_number(tip), format_number(y)
+      );
+      state_elements += &format!(
+        "  <polygon points=\"{},{} {},{} {},{}\" fill=\"black\"/>\n",
+        format_number(tip), format_number(y),
+        format_number(tip - ARROW_HEAD_SIZE * 0.87), format_number(y - ARROW_HEAD_SIZE / 2.0),
+        format_number(tip - ARROW_HEAD_SIZE * 0.87), format_number(y + ARROW_HEAD_SIZE / 2.0)
+      );
+      bounds.include(tip - START_ARROW_LENGTH, y, 0.0);
+    }
+
+    bounds.include(x, y, FINAL_STATE_OUTER_RADIUS);
+  }
+
+  let (min_x, min_y, width, height) = bounds.get_view_box();
+
+  format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}\" height=\"{}\">\n{}{}</svg>\n",
+    format_number(min_x), format_number(min_y), format_number(width), format_number(height),
+    format_number(width), format_number(height),
+    connection_elements,
+    state_elements
+  )
+
+}
+
+// The smallest rectangle containing everything drawn, so the image can be cropped tightly around the automaton
+struct Bounds {
+  min_x: f64,
+  min_y: f64,
+  max_x: f64,
+  max_y: f64
+}
+
+impl Bounds {
+
+  fn new() -> Self {
+    Bounds {
+      min_x: f64::INFINITY,
+      min_y: f64::INFINITY,
+      max_x: f64::NEG_INFINITY,
+      max_y: f64::NEG_INFINITY
+    }
+  }
+
+  fn include(&mut self, x: f64, y: f64, radius: f64) {
+    self.min_x = self.min_x.min(x - radius);
+    self.min_y = self.min_y.min(y - radius);
+    self.max_x = self.max_x.max(x + radius);
+    self.max_y = self.max_y.max(y + radius);
+  }
+
+  // An empty workspace still produces a valid, if empty, image
+  fn get_view_box(&self) -> (f64, f64, f64, f64) {
+    if self.min_x > self.max_x {
+      return (0.0, 0.0, 2.0 * MARGIN, 2.0 * MARGIN);
+    }
+
+    (
+      self.min_x - MARGIN,
+      self.min_y - MARGIN,
+      self.max_x - self.min_x + 2.0 * MARGIN,
+      self.max_y - self.min_y + 2.0 * MARGIN
+    )
+  }
+
+}
+
+fn get_point_on_curve(curve: &BezierCurve, t: f64) -> Point {
+  let points = [curve.start_point, curve.control_point_one, curve.control_point_two, curve.end_point];
+  let weights = [(1.0 - t).powi(3), 3.0 * (1.0 - t).powi(2) * t, 3.0 * (1.0 - t) * t.powi(2), t.powi(3)];
+
+  points
+    .iter()
+    .zip(weights)
+    .fold((0.0, 0.0), |(x, y), (point, weight)| (x + weight * point.x as f64, y + weight * point.y as f64))
+}
+
+// Walks along the curve from the given parameter towards the other end, returning the first parameter at which the curve
+// is outside of the state at the given centre. Binary search isn't used as self loops leave and re-enter the same state
+fn find_curve_parameter_outside_state(curve: &BezierCurve, centre: Coordinate, from_t: f64, towards_t: f64) -> f64 {
+  const STEPS: usize = 200;
+
+  for step in 0..=STEPS {
+    let t = from_t + (towards_t - from_t) * step as f64 / STEPS as f64;
+    let (x, y) = get_point_on_curve(curve, t);
+
+    if (x - centre.x as f64).hypot(y - centre.y as f64) >= STATE_RADIUS {
+      return t;
+    }
+  }
+
+  from_t
+}
+
+// The control points of the part of the curve between the two parameters, found using de Casteljau's algorithm
+fn get_sub_curve(curve: &BezierCurve, start_t: f64, end_t: f64) -> [Point; 4] {
+  let points = [curve.start_point, curve.control_point_one, curve.control_point_two, curve.end_point]
+    .map(|point| (point.x as f64, point.y as f64));
+
+  // The curve after start_t is split off first, and then the part of that before end_t
+  let after_start = split_curve(points, start_t).1;
+  let remaining_end_t = if start_t < 1.0 { (end_t - start_t) / (1.0 - start_t) } else { 1.0 };
+
+  split_curve(after_start, remaining_end_t.clamp(0.0, 1.0)).0
+}
+
+fn split_curve(points: [Point; 4], t: f64) -> ([Point; 4], [Point; 4]) {
+  let interpolate = |a: Point, b: Point| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+
+  let p01 = interpolate(points[0], points[1]);
+  let p12 = interpolate(points[1], points[2]);
+  let p23 = interpolate(points[2], points[3]);
+  let p012 = interpolate(p01, p12);
+  let p123 = interpolate(p12, p23);
+  let p0123 = interpolate(p012, p123);
+
+  ([points[0], p01, p012, p0123], [p0123, p123, p23, points[3]])
+}
+
+// Rounded to two decimal places, with any trailing zeros removed, to keep the file small
+fn format_number(number: f64) -> String {
+  let formatted = format!("{:.2}", number);
+  let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+  if formatted == "-0" { String::from("0") } else { formatted.to_owned() }
+}
+
+fn escape_xml_string(string: &str) -> String {
+  string
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
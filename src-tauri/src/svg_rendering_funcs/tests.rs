@@ -0,0 +1,87 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::svg_rendering_funcs::{render_automata_to_svg, HIGHLIGHT_COLOUR};
+  use crate::regular_expression_funcs::convert_regex_to_state_positions;
+  use crate::testing_automata_funcs::test_string_dfa;
+  use app::create_connections_from_state_positions;
+  use quick_xml::events::Event;
+  use quick_xml::Reader;
+  use std::collections::HashMap;
+
+  // Returns the name of every element in the document, failing the test if the document is not well formed
+  fn get_element_names(svg: &str) -> Vec<String> {
+    let mut reader = Reader::from_str(svg);
+    let mut element_names = vec![];
+
+    loop {
+      match reader.read_event().expect("The SVG should be well formed XML") {
+        Event::Start(element) | Event::Empty(element) => {
+          element_names.push(String::from_utf8(element.name().as_ref().to_vec()).unwrap());
+        },
+        Event::Eof => break,
+        _ => ()
+      }
+    }
+
+    element_names
+  }
+
+  fn count_elements(element_names: &[String], name: &str) -> usize {
+    element_names.iter().filter(|element_name| *element_name == name).count()
+  }
+
+  #[test]
+  fn test_svg_rendering() {
+    let (state_positions, _) = convert_regex_to_state_positions("(a+b)*abb").unwrap();
+    let connections = create_connections_from_state_positions(&state_positions);
+
+    let svg = render_automata_to_svg(&state_positions, &connections, &[]);
+    let element_names = get_element_names(&svg);
+
+    let number_of_final_states = state_positions.values().filter(|state| state.is_final()).count();
+
+    assert_eq!(element_names[0], "svg");
+    // Final states are drawn with a second, outer circle
+    assert_eq!(count_elements(&element_names, "circle"), state_positions.len() + number_of_final_states);
+    assert_eq!(count_elements(&element_names, "text"), connections.len());
+    // Every connection and the start arrow have an arrow head
+    assert_eq!(count_elements(&element_names, "polygon"), connections.len() + 1);
+    assert!(!svg.contains(HIGHLIGHT_COLOUR));
+  }
+
+  #[test]
+  fn test_svg_rendering_highlights_path() {
+    let (state_positions, start_state_key) = convert_regex_to_state_positions("abc").unwrap();
+    let connections = create_connections_from_state_positions(&state_positions);
+
+    let (_, states_visited) = test_string_dfa(state_positions.to_owned(), start_state_key, String::from("ab"));
+    let svg = render_automata_to_svg(&state_positions, &connections, &states_visited);
+
+    // The three states visited are filled in, and the two connections taken have their curve, arrow head and label highlighted
+    assert_eq!(svg.matches(&format!("fill=\"{HIGHLIGHT_COLOUR}\" stroke=\"black\"")).count(), 3);
+    assert_eq!(svg.matches(&format!("stroke=\"{HIGHLIGHT_COLOUR}\"")).count(), 2);
+    assert_eq!(svg.matches("<polygon points=").count(), 4);
+    assert_eq!(svg.matches(&format!("fill=\"{HIGHLIGHT_COLOUR}\">")).count(), 2);
+  }
+
+  #[test]
+  fn test_svg_rendering_of_empty_automaton() {
+    let svg = render_automata_to_svg(&HashMap::new(), &[], &[]);
+    assert_eq!(get_element_names(&svg), vec![String::from("svg")]);
+  }
+
+  #[test]
+  fn test_svg_rendering_escapes_labels() {
+    let (mut state_positions, start_state_key) = convert_regex_to_state_positions("a").unwrap();
+    state_positions
+      .get_mut(&start_state_key)
+      .unwrap()
+      .add_connection("<", start_state_key.to_owned());
+    let connections = create_connections_from_state_positions(&state_positions);
+
+    let svg = render_automata_to_svg(&state_positions, &connections, &[]);
+    get_element_names(&svg);
+    assert!(svg.contains(">&lt;</text>"));
+  }
+
+}
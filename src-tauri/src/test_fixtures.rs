@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, Once};
+
+use app::{establish_connection, set_working_directory};
+use app::models::{Coordinate, State, WorkspaceData};
+
+use crate::registration_funcs::{is_user_registered, register_user};
+use crate::run_migrations;
+use crate::saving_automata_funcs::{delete_workspace, get_users_saved_workspaces};
+
+static SET_UP_DATABASE: Once = Once::new();
+static DATABASE: Mutex<()> = Mutex::new(());
+
+// Tests which touch the database share one beside the test binary, just as the app keeps its own beside its executable.
+// SQLite gives up on writes while another connection is writing, so the database is held for as long as the returned
+// guard lives. The user is left without any workspaces, as earlier runs of the tests leave theirs behind
+pub fn set_up_test_user(email: &str) -> MutexGuard<'static, ()> {
+
+  let database = DATABASE.lock().unwrap_or_else(|error| error.into_inner());
+
+  SET_UP_DATABASE.call_once(|| {
+    set_working_directory();
+    run_migrations(&mut establish_connection());
+  });
+
+  if !is_user_registered(email) {
+    register_user(email, "password");
+  }
+
+  for workspace_name in get_users_saved_workspaces(email) {
+    delete_workspace(&workspace_name, email);
+  }
+
+  database
+
+}
+
+// Over {a, b, 7, if}, accepts a single letter, or any other symbol followed by a digit
+pub fn create_state_positions_with_classes() -> HashMap<String, State> {
+
+  let mut q0 = State::with_id("q0", Coordinate { x: 300, y: 300 }, true, false);
+  let q1 = State::with_id("q1", Coordinate { x: 500, y: 300 }, false, true);
+  let mut q2 = State::with_id("q2", Coordinate { x: 500, y: 500 }, false, false);
+  q0.add_connection("[a-z]", "q1");
+  q0.add_connection("[*]", "q2");
+  q2.add_connection("[0-9]", "q1");
+
+  HashMap::from([
+    (String::from("q0"), q0),
+    (String::from("q1"), q1),
+    (String::from("q2"), q2)
+  ])
+
+}
+
+// The key of the first start state found, for automata which only have one
+pub fn get_start_state_key(state_positions: &HashMap<String, State>) -> String {
+
+  state_positions
+    .iter()
+    .find(|(_, state)| state.is_start())
+    .map(|(state_key, _)| state_key.to_owned())
+    .expect("Every automaton should have a start state")
+
+}
+
+// An empty workspace with the settings a new one is given, apart from any fields given here
+pub fn create_workspace_data(fields: serde_json::Value) -> WorkspaceData {
+
+  let mut workspace_data = serde_json::json!({
+    "workspace_name": "Untitled",
+    "start_state_index": null,
+    "start_state_position": null,
+    "start_state_positions": [],
+    "state_positions": {},
+    "list_of_states": [],
+    "list_of_connections": [],
+    "type_of_automata": "DFA",
+    "date_of_last_update": "2026-10-19 12:00:00",
+    "alphabet": ["a", "b"],
+    "should_strict_check": false,
+    "should_show_string_traversal": false,
+    "default_connection_character": "a",
+    "input_separator": null,
+    "max_revisions": 50
+  });
+
+  for (field, value) in fields.as_object().expect("The fields should be given as an object") {
+    workspace_data[field] = value.to_owned();
+  }
+
+  serde_json::from_value(workspace_data).expect("The fields should be those of a WorkspaceData")
+
+}
@@ -1,9 +1,72 @@
+use app::labels::{get_connected_state_keys_by_symbol, is_symbolic_label};
 use app::models::State;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+mod tests;
+
+// Input strings are split into symbols of the alphabet at every occurrence of the separator when one is given, and otherwise
+// by repeatedly taking the longest symbol the rest of the string starts with, so that with the alphabet {a, ab, b}, abb is
+// read as ab, b. A character which starts no symbol is taken on its own, so the string is rejected rather than refused.
+// Characters are always Unicode scalar values, so a letter followed by a combining accent is two characters unless
+// the alphabet has a symbol made of both
+#[tauri::command]
+pub fn tokenize_input_string(string_to_check: &str, input_alphabet: Vec<String>, input_separator: Option<String>) -> Vec<String> {
+
+  if let Some(input_separator) = input_separator.filter(|separator| !separator.is_empty()) {
+    return string_to_check
+      .split(input_separator.as_str())
+      .filter(|symbol| !symbol.is_empty())
+      .map(String::from)
+      .collect();
+  }
+
+  let mut symbols_by_length: Vec<&String> = input_alphabet
+    .iter()
+    .filter(|symbol| !symbol.is_empty())
+    .collect();
+  symbols_by_length.sort_by_key(|symbol| std::cmp::Reverse(symbol.len()));
+
+  let mut symbols = vec![];
+  let mut rest_of_string = string_to_check;
+
+  while let Some(next_character) = rest_of_string.chars().next() {
+    let symbol = symbols_by_length
+      .iter()
+      .find(|symbol| rest_of_string.starts_with(symbol.as_str()))
+      .map(|symbol| symbol.as_str())
+      .unwrap_or(&rest_of_string[..next_character.len_utf8()]);
+
+    symbols.push(symbol.to_owned());
+    rest_of_string = &rest_of_string[symbol.len()..];
+  }
+
+  symbols
+
+}
+
+// Strings given without an alphabet are read using the symbols the automaton's transitions are labelled with
+fn get_transition_symbols(state_positions: &HashMap<String, State>) -> Vec<String> {
+
+  let transition_symbols: BTreeSet<&String> = state_positions
+    .values()
+    .flat_map(|state| state.get_all_connections().keys())
+    .filter(|connection_character| connection_character.as_str() != "ϵ" && !is_symbolic_label(connection_character))
+    .collect();
+
+  transition_symbols.into_iter().cloned().collect()
+
+}
 
 #[tauri::command]
 pub fn test_string_dfa(state_positions: HashMap<String, State>, start_state_coordinates: String, string_to_check: String) -> (bool, Vec<State>) {
 
+  let symbols_to_check = tokenize_input_string(&string_to_check, get_transition_symbols(&state_positions), None);
+  test_symbols_dfa(state_positions, start_state_coordinates, symbols_to_check)
+
+}
+
+#[tauri::command]
+pub fn test_symbols_dfa(state_positions: HashMap<String, State>, start_state_coordinates: String, symbols_to_check: Vec<String>) -> (bool, Vec<State>) {
+
   let mut states_visited: Vec<State> = vec![];
   
   let start_state: &State = match state_positions.get(&start_state_coordinates){
@@ -17,9 +80,9 @@ pub fn test_string_dfa(state_positions: HashMap<String, State>, start_state_coor
 
   let mut current_state: &State = start_state;
 
-  for connection_char in string_to_check.chars() {
+  for connection_symbol in symbols_to_check {
 
-    let next_state = match dfa_delta_function(&state_positions, current_state, connection_char.to_string()) {
+    let next_state = match dfa_delta_function(&state_positions, current_state, connection_symbol) {
       Some(state) => state,
       None => return (false, states_visited)
     };
@@ -36,7 +99,7 @@ pub fn test_string_dfa(state_positions: HashMap<String, State>, start_state_coor
 
 fn dfa_delta_function<'a>(state_positions: &'a HashMap<String, State>, s: &'a State, connection_character: String) -> Option<&'a State> {
 
-  let connected_state_keys = s.get_connections_by_character(&connection_character)?;
+  let connected_state_keys = get_connected_state_keys_by_symbol(s, &connection_character);
 
   // Since in a DFA, every connection character must have exactly one connection, thus I just take the first return value
   // by iterating over the connected state keys
@@ -57,12 +120,12 @@ fn dfa_delta_function<'a>(state_positions: &'a HashMap<String, State>, s: &'a St
 fn nfa_delta_function(
   state_positions: &HashMap<String, State>, 
   current_state: &State, 
-  string_to_check: &String,
+  symbols_to_check: &[String],
   states_visited: &mut Vec<State>) -> bool {
   
-  if string_to_check.is_empty() && current_state.is_final() {
+  if symbols_to_check.is_empty() && current_state.is_final() {
     return true;
-  } else if string_to_check.is_empty() {
+  } else if symbols_to_check.is_empty() {
     let states_connected_by_epsilon = get_states_connected_by_epsilon_in_nfa(
       state_positions, 
       current_state);
@@ -71,7 +134,7 @@ fn nfa_delta_function(
       for state in states_connected_by_epsilon.unwrap() {
 
         // Checking if the bool value of states visited is true
-        if nfa_delta_function(state_positions, state, string_to_check, states_visited) {
+        if nfa_delta_function(state_positions, state, symbols_to_check, states_visited) {
           states_visited.insert(0, state.to_owned());
           return true;
         }
@@ -85,7 +148,7 @@ fn nfa_delta_function(
   let states_connected_by_character = get_states_connected_by_character_in_nfa(
     state_positions, 
     current_state,
-    &string_to_check.chars().nth(0).unwrap().to_string());
+    &symbols_to_check[0]);
   
   let states_connected_by_epsilon = get_states_connected_by_epsilon_in_nfa(
     state_positions, 
@@ -95,11 +158,10 @@ fn nfa_delta_function(
   }
   if states_connected_by_character.is_some() {
     for state in states_connected_by_character.unwrap() {
-      // The rest of the string to be checked will be everything excluding the first character
+      // The rest of the string to be checked will be everything excluding the first symbol
       // Which was consumed when retrieving states_connected_by_character
-      let string_to_check = String::from(&string_to_check[1..]);
       // Checking if the bool value of states visited is true
-      if nfa_delta_function(state_positions, state, &string_to_check, states_visited) {
+      if nfa_delta_function(state_positions, state, &symbols_to_check[1..], states_visited) {
         states_visited.insert(0, state.to_owned());
         return true;
       } 
@@ -108,7 +170,7 @@ fn nfa_delta_function(
   if states_connected_by_epsilon.is_some() {
     for state in states_connected_by_epsilon.unwrap() {
       // Checking if the bool value of states visited is true
-      if nfa_delta_function(state_positions, state, string_to_check, states_visited) {
+      if nfa_delta_function(state_positions, state, symbols_to_check, states_visited) {
         states_visited.insert(0, state.to_owned());
         return true;
       }
@@ -123,22 +185,23 @@ fn get_states_connected_by_character_in_nfa<'a>(
   connection_character: &String
   ) -> Option<HashSet<&'a State>> {
 
-  s.get_connections_by_character(connection_character)
-    .and_then(|connected_state_keys| {
+  let connected_state_keys = get_connected_state_keys_by_symbol(s, connection_character);
 
-      let mut state_references = HashSet::new();
+  if connected_state_keys.is_empty() {
+    return None;
+  }
 
-      for connected_state_key in connected_state_keys {
-        let connected_state = state_positions
-          .get(connected_state_key)?;
+  let mut state_references = HashSet::new();
 
-        state_references.insert(connected_state);
+  for connected_state_key in connected_state_keys {
+    let connected_state = state_positions
+      .get(connected_state_key)?;
 
-      }
+    state_references.insert(connected_state);
 
-      return Some(state_references);
-      
-    })
+  }
+
+  Some(state_references)
     
 }
 
@@ -176,16 +239,55 @@ pub fn test_string_nfa(
   string_to_check: String
   ) -> (bool, Vec<State>) {
 
+  let symbols_to_check = tokenize_input_string(&string_to_check, get_transition_symbols(&state_positions), None);
+  test_symbols_nfa(state_positions, start_state_coordinates, symbols_to_check)
+
+}
+
+// NFAs may have several initial states. The given start state is tried first, followed by every other state marked
+// as a start state in sorted order of their keys, and the path returned is the first accepting one found
+#[tauri::command]
+pub fn test_symbols_nfa(
+  state_positions: HashMap<String, State>, 
+  start_state_coordinates: String, 
+  symbols_to_check: Vec<String>
+  ) -> (bool, Vec<State>) {
+
+  let mut start_state_keys = get_other_start_state_keys(&state_positions, &start_state_coordinates);
+  start_state_keys.insert(0, &start_state_coordinates);
+
   let mut states_visited: Vec<State> = vec![];
 
-  let start_state = match state_positions.get(&start_state_coordinates) {
-    Some(state) => state,
-    None => return (false, states_visited)
-  };
-  
-  // Ugly syntax but whatevs
-  let result = nfa_delta_function(&state_positions, start_state, &string_to_check, &mut states_visited);
-  states_visited.insert(0, start_state.to_owned());
-  return (result, states_visited);
+  for start_state_key in start_state_keys {
+    let start_state = match state_positions.get(start_state_key) {
+      Some(state) => state,
+      None => continue
+    };
+
+    states_visited.clear();
+
+    // Ugly syntax but whatevs
+    let result = nfa_delta_function(&state_positions, start_state, &symbols_to_check, &mut states_visited);
+    states_visited.insert(0, start_state.to_owned());
+
+    if result {
+      return (result, states_visited);
+    }
+  }
 
-}
\ No newline at end of file
+  (false, states_visited)
+
+}
+
+fn get_other_start_state_keys<'a>(state_positions: &'a HashMap<String, State>, start_state_key: &str) -> Vec<&'a String> {
+
+  let mut start_state_keys: Vec<&String> = state_positions
+    .iter()
+    .filter(|(state_key, state)| state.is_start() && *state_key != start_state_key)
+    .map(|(state_key, _)| state_key)
+    .collect();
+  start_state_keys.sort();
+
+  start_state_keys
+
+}
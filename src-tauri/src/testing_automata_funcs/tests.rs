@@ -0,0 +1,152 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::testing_automata_funcs::{test_string_dfa, test_string_nfa, test_symbols_nfa, tokenize_input_string};
+  use crate::validation_automata_funcs::verify_valid_dfa;
+  use app::models::{Coordinate, State};
+  use app::{sanitize_input_alphabet, serialize_input_alphabet};
+  use std::collections::HashMap;
+
+  fn symbols(symbols: &[&str]) -> Vec<String> {
+    symbols.iter().map(|symbol| symbol.to_string()).collect()
+  }
+
+  #[test]
+  fn test_longest_match_tokenization() {
+    let alphabet = symbols(&["a", "ab", "b", "0x", "é"]);
+
+    assert_eq!(tokenize_input_string("abb", alphabet.clone(), None), symbols(&["ab", "b"]));
+    assert_eq!(tokenize_input_string("aab", alphabet.clone(), None), symbols(&["a", "ab"]));
+    assert_eq!(tokenize_input_string("0xé", alphabet.clone(), None), symbols(&["0x", "é"]));
+    // Characters which start no symbol are kept on their own
+    assert_eq!(tokenize_input_string("0yb", alphabet.clone(), None), symbols(&["0", "y", "b"]));
+    assert_eq!(tokenize_input_string("", alphabet, None), symbols(&[]));
+  }
+
+  #[test]
+  fn test_separator_tokenization() {
+    let alphabet = symbols(&["if", "then", ","]);
+
+    assert_eq!(
+      tokenize_input_string("if then , if", alphabet.clone(), Some(String::from(" "))),
+      symbols(&["if", "then", ",", "if"])
+    );
+    assert_eq!(tokenize_input_string("if  then ", alphabet.clone(), Some(String::from(" "))), symbols(&["if", "then"]));
+    // An empty separator is the same as having none
+    assert_eq!(tokenize_input_string("ifthen", alphabet, Some(String::new())), symbols(&["if", "then"]));
+  }
+
+  #[test]
+  fn test_multi_character_symbols() {
+    // A DFA over {if, then} accepting an if followed by any number of thens
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 500, y: 300 }, false, true);
+    q0.add_connection("if", "500,300");
+    q1.add_connection("then", "500,300");
+
+    let state_positions = HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("500,300"), q1)
+    ]);
+
+    for (string_to_check, should_accept) in [("if", true), ("ifthenthen", true), ("", false), ("then", false), ("ifthe", false)] {
+      let (is_accepted, _) = test_string_dfa(state_positions.clone(), String::from("300,300"), String::from(string_to_check));
+      assert_eq!(is_accepted, should_accept, "{string_to_check}");
+    }
+
+    let (is_accepted, states_visited) = test_symbols_nfa(
+      state_positions,
+      String::from("300,300"),
+      symbols(&["if", "then", "then"])
+    );
+    assert!(is_accepted);
+    assert_eq!(states_visited.len(), 4);
+  }
+
+  #[test]
+  fn test_unicode_symbols() {
+    // An NFA over {α, 🙂, é} where é is written as an e followed by a combining accent, with an ϵ transition
+    // out of the start state so that every symbol is read after a multi byte one
+    let e_with_accent = "e\u{301}";
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 500, y: 300 }, false, false);
+    let mut q2 = State::new(Coordinate { x: 700, y: 300 }, false, true);
+    q0.add_connection("ϵ", "500,300");
+    q1.add_connection("α", "500,300");
+    q1.add_connection("🙂", "700,300");
+    q2.add_connection(e_with_accent, "700,300");
+
+    let state_positions = HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("500,300"), q1),
+      (String::from("700,300"), q2)
+    ]);
+
+    for (string_to_check, should_accept) in [
+      ("🙂", true),
+      ("αα🙂", true),
+      ("α🙂e\u{301}e\u{301}", true),
+      ("α", false),
+      ("🙂e", false),
+      ("🙂\u{301}", false),
+      ("🙂é", false)
+    ] {
+      let (is_accepted, _) = test_string_nfa(state_positions.clone(), String::from("300,300"), String::from(string_to_check));
+      assert_eq!(is_accepted, should_accept, "{string_to_check}");
+    }
+
+    let alphabet = symbols(&["α", "🙂", e_with_accent]);
+    assert_eq!(tokenize_input_string("α🙂e\u{301}", alphabet.clone(), None), symbols(&["α", "🙂", e_with_accent]));
+    assert_eq!(tokenize_input_string("🙂e", alphabet, None), symbols(&["🙂", "e"]));
+  }
+
+  #[test]
+  fn test_character_classes() {
+    // A DFA for identifiers, which start with a letter or _ followed by any number of letters, digits or _,
+    // with everything else sent to a dead state
+    let mut q0 = State::new(Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 500, y: 300 }, false, true);
+    let mut q2 = State::new(Coordinate { x: 700, y: 300 }, false, false);
+    q0.add_connection("[a-zA-Z_]", "500,300");
+    q0.add_connection("[*]", "700,300");
+    q1.add_connection("[a-zA-Z0-9_]", "500,300");
+    q1.add_connection("[*]", "700,300");
+    q2.add_connection("[*]", "700,300");
+
+    let state_positions = HashMap::from([
+      (String::from("300,300"), q0),
+      (String::from("500,300"), q1),
+      (String::from("700,300"), q2)
+    ]);
+
+    for (string_to_check, should_accept) in [("x", true), ("_tmp1", true), ("camelCase", true), ("1x", false), ("a-b", false), ("", false)] {
+      let (is_accepted, _) = test_string_dfa(state_positions.clone(), String::from("300,300"), String::from(string_to_check));
+      assert_eq!(is_accepted, should_accept, "{string_to_check}");
+    }
+
+    assert!(verify_valid_dfa(state_positions.clone(), symbols(&["a", "Z", "_", "7", "-"])));
+
+    // Overlapping labels give a symbol two connections
+    let mut overlapping_state_positions = state_positions.clone();
+    overlapping_state_positions.get_mut("300,300").unwrap().add_connection("[0-9a]", "700,300");
+    assert!(!verify_valid_dfa(overlapping_state_positions, symbols(&["a", "7"])));
+
+    // Without the [*] the dead state has no connection on a
+    let mut state_positions = state_positions;
+    state_positions.get_mut("700,300").unwrap().remove_all_connections_by_character("[*]");
+    state_positions.get_mut("700,300").unwrap().add_connection("[0-9]", "700,300");
+    assert!(!verify_valid_dfa(state_positions.clone(), symbols(&["a", "7"])));
+    assert!(verify_valid_dfa(state_positions.clone(), symbols(&["7"])));
+    state_positions.get_mut("700,300").unwrap().add_connection("if", "700,300");
+    assert!(!verify_valid_dfa(state_positions, symbols(&["7"])));
+  }
+
+  #[test]
+  fn test_alphabet_sanitization() {
+    assert_eq!(
+      sanitize_input_alphabet(vec!["a", "0x", "", ",", "a", "ϵ", "é"]),
+      symbols(&["a", "0x", ",", "é"])
+    );
+    assert_eq!(serialize_input_alphabet(&symbols(&[",", "\""])), r#"[",","\""]"#);
+  }
+
+}
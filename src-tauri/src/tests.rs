@@ -0,0 +1,72 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::create_connections_from_state_positions;
+  use crate::models::{Connection, Coordinate, State};
+  use std::collections::HashMap;
+
+  fn find_connection(connections: &[Connection], start_point: Coordinate, end_point: Coordinate) -> &Connection {
+    connections
+      .iter()
+      .find(|connection| connection.curve.start_point == start_point && connection.curve.end_point == end_point)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_connection_routing() {
+    let (q0, q1, q2, q3) = (Coordinate { x: 300, y: 300 }, Coordinate { x: 500, y: 300 }, Coordinate { x: 700, y: 300 }, Coordinate { x: 500, y: 100 });
+    let mut state_positions = HashMap::from([
+      (String::from("300,300"), State::new(q0, true, false)),
+      (String::from("500,300"), State::new(q1, false, false)),
+      (String::from("700,300"), State::new(q2, false, true)),
+      (String::from("500,100"), State::new(q3, false, false))
+    ]);
+
+    for (from, connection_character, to) in [
+      ("300,300", "b", "500,300"), ("300,300", "a", "500,300"), ("500,300", "a", "300,300"), ("500,300", "b", "700,300"),
+      ("500,300", "c", "500,100"), ("500,300", "a", "500,300"), ("300,300", "b", "300,300"), ("700,300", "a", "700,300")
+    ] {
+      state_positions.get_mut(from).unwrap().add_connection(connection_character, to);
+    }
+
+    let connections = create_connections_from_state_positions(&state_positions);
+    assert_eq!(connections.len(), 7);
+
+    // Characters between the same two states share one connection, which is bent to one side as another goes back the opposite way
+    let forwards = find_connection(&connections, q0, q1);
+    let backwards = find_connection(&connections, q1, q0);
+    assert_eq!(forwards.connection_character, "a,b");
+    assert_eq!(backwards.connection_character, "a");
+    assert!(forwards.curve.control_point_one.y < 300 && forwards.curve.control_point_two.y < 300);
+    assert!(backwards.curve.control_point_one.y > 300 && backwards.curve.control_point_two.y > 300);
+
+    // Connections with nothing coming back are straight, with their control points on the states
+    let straight = find_connection(&connections, q1, q2);
+    assert_eq!((straight.curve.control_point_one, straight.curve.control_point_two), (q1, q2));
+
+    // q1 has connections to its left, right and above, so its loop hangs below it
+    let loop_below = find_connection(&connections, q1, q1);
+    assert!(loop_below.curve.control_point_one.y > 300 && loop_below.curve.control_point_two.y > 300);
+
+    // q0 has the arrow marking it as the start state to its left and q1 to its right, so its loop sits above or below.
+    // q2 only has q1 to its left, so its loop points to the right
+    let start_loop = find_connection(&connections, q0, q0);
+    assert!([start_loop.curve.control_point_one, start_loop.curve.control_point_two].iter().all(|point| (point.x - 300).abs() == 200));
+    let loop_right = find_connection(&connections, q2, q2);
+    assert!(loop_right.curve.control_point_one.x > 700 && loop_right.curve.control_point_two.x > 700);
+  }
+
+  #[test]
+  fn test_connections_between_state_ids() {
+    // Connections belong to the ids of their states, and are drawn to wherever those states have been moved
+    let mut first = State::with_id("first", Coordinate { x: 300, y: 300 }, true, false);
+    let mut second = State::with_id("second", Coordinate { x: 300, y: 300 }, false, true);
+    first.add_connection("a", "second");
+    second.set_position(Coordinate { x: 500, y: 300 });
+
+    let state_positions = HashMap::from([(String::from("first"), first), (String::from("second"), second)]);
+    let connections = create_connections_from_state_positions(&state_positions);
+    assert_eq!((connections[0].start_state_id.as_str(), connections[0].end_state_id.as_str()), ("first", "second"));
+    assert_eq!(connections[0].curve.end_point, Coordinate { x: 500, y: 300 });
+  }
+
+}
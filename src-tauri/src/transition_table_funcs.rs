@@ -0,0 +1,331 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use app::{create_connections_from_state_positions, create_state_names, create_state_numbers, create_unique_state_coordinates,
+get_alphabet_from_state_positions, get_type_of_automata_from_state_positions};
+use app::layout::layout_state_positions;
+use app::models::State;
+
+use crate::saving_automata_funcs::{create_workspace_with_automata, retrieve_workspace_data};
+mod tests;
+
+// Transition tables have a header row holding the alphabet, followed by a row for every state:
+//
+// state,a,b,ϵ
+// ->q0,q1,q0,
+// *q1,q1,q0 q1,
+//
+// The first cell of each row names the state, prefixed by -> if it is a start state and * if it is final.
+// Every other cell lists the states reached on that column's character separated by spaces, and is left empty if there are none.
+// When importing, commas (inside a quoted cell) and braces may also be used, and -, ∅ and {} are read as an empty cell
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionTableFormat {
+  CSV,
+  Markdown
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum TransitionTableError {
+  UnableToReadFile,
+  EmptyTable,
+  UnterminatedQuote { line: usize },
+  DuplicateCharacter { line: usize, character: String },
+  DuplicateState { line: usize, state_name: String },
+  UnknownState { line: usize, state_name: String },
+  WrongNumberOfCells { line: usize, expected: usize, found: usize }
+}
+
+#[tauri::command]
+pub fn export_workspace_to_transition_table(workspace_name: &str, email: &str, format: TransitionTableFormat) -> String {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+
+  convert_state_positions_to_transition_table(workspace_data.get_state_positions(), workspace_data.get_alphabet(), format)
+
+}
+
+// Returns the name the workspace was imported under, which is taken from the name of the file
+#[tauri::command]
+pub fn import_transition_table_file(email: &str, path: &str) -> Result<String, TransitionTableError> {
+
+  let csv = fs::read_to_string(path)
+    .map_err(|_| TransitionTableError::UnableToReadFile)?;
+
+  let (alphabet, state_positions) = parse_transition_table(&csv)?;
+
+  let workspace_name = Path::new(path)
+    .file_stem()
+    .and_then(|file_stem| file_stem.to_str())
+    .unwrap_or("Imported transition table");
+
+  let type_of_automata = get_type_of_automata_from_state_positions(&state_positions);
+  let connections = create_connections_from_state_positions(&state_positions);
+
+  Ok(create_workspace_with_automata(
+    email,
+    workspace_name,
+    type_of_automata,
+    alphabet.iter().map(|c| c.as_str()).collect(),
+    state_positions,
+    connections
+  ))
+
+}
+
+pub fn convert_state_positions_to_transition_table(
+  state_positions: &HashMap<String, State>,
+  input_alphabet: &[String],
+  format: TransitionTableFormat
+) -> String {
+
+  let state_numbers = create_state_numbers(state_positions);
+  let state_names = create_state_names(state_positions);
+
+  let mut states_in_name_order: Vec<(&String, &State)> = state_positions.iter().collect();
+  states_in_name_order.sort_by_key(|(state_key, _)| state_numbers[*state_key]);
+
+  // The workspace's alphabet keeps the order the user gave it, followed by any other characters which are used
+  let mut columns: Vec<String> = input_alphabet.to_vec();
+  for connection_character in get_alphabet_from_state_positions(state_positions) {
+    if !columns.contains(&connection_character) {
+      columns.push(connection_character);
+    }
+  }
+  columns.push(String::from("ϵ"));
+
+  let mut rows = vec![];
+
+  let mut header = vec![String::from("state")];
+  header.extend(columns.iter().cloned());
+  rows.push(header);
+
+  for (state_key, state) in states_in_name_order {
+    let mut row_label = state_names[state_key].to_owned();
+    if state.is_final() {
+      row_label = format!("*{row_label}");
+    }
+    if state.is_start() {
+      row_label = format!("->{row_label}");
+    }
+
+    let mut row = vec![row_label];
+
+    for connection_character in &columns {
+      let mut connected_state_keys: Vec<&String> = state
+        .get_connections_by_character(connection_character)
+        .into_iter()
+        .flatten()
+        .filter(|connected_state_key| state_names.contains_key(*connected_state_key))
+        .collect();
+      connected_state_keys.sort_by_key(|connected_state_key| state_numbers[*connected_state_key]);
+
+      row.push(connected_state_keys
+        .into_iter()
+        .map(|connected_state_key| state_names[connected_state_key].as_str())
+        .collect::<Vec<&str>>()
+        .join(" "));
+    }
+
+    rows.push(row);
+  }
+
+  match format {
+    TransitionTableFormat::CSV => rows
+      .iter()
+      .map(|row| row.iter().map(|cell| escape_csv_cell(cell)).collect::<Vec<String>>().join(",") + "\n")
+      .collect(),
+    TransitionTableFormat::Markdown => {
+      let mut markdown = String::new();
+
+      for (index, row) in rows.iter().enumerate() {
+        markdown += &format!("| {} |\n", row.iter().map(|cell| escape_markdown_cell(cell)).collect::<Vec<String>>().join(" | "));
+
+        if index == 0 {
+          markdown += &format!("|{}\n", " --- |".repeat(row.len()));
+        }
+      }
+
+      markdown
+    }
+  }
+
+}
+
+fn escape_csv_cell(cell: &str) -> String {
+  if cell.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", cell.replace('"', "\"\""))
+  } else {
+    cell.to_owned()
+  }
+}
+
+// Asterisks would otherwise start emphasis, and pipes would end the cell
+fn escape_markdown_cell(cell: &str) -> String {
+  cell
+    .replace('\\', "\\\\")
+    .replace('*', "\\*")
+    .replace('|', "\\|")
+}
+
+// Splits CSV into rows of cells, with each row paired with the line it started on.
+// Quoted cells may contain commas, newlines and quotes, which are written twice
+fn parse_csv(csv: &str) -> Result<Vec<(usize, Vec<String>)>, TransitionTableError> {
+
+  let mut rows = vec![];
+  let mut current_row = vec![];
+  let mut current_cell = String::new();
+  let mut line = 1;
+  let mut row_line = 1;
+  let mut characters = csv.chars().peekable();
+
+  while let Some(c) = characters.next() {
+    match c {
+      '"' if current_cell.trim().is_empty() => {
+        let quote_line = line;
+        current_cell.clear();
+
+        loop {
+          match characters.next() {
+            None => return Err(TransitionTableError::UnterminatedQuote { line: quote_line }),
+            Some('"') if characters.peek() == Some(&'"') => {
+              characters.next();
+              current_cell.push('"');
+            },
+            Some('"') => break,
+            Some(c) => {
+              if c == '\n' {
+                line += 1;
+              }
+              current_cell.push(c);
+            }
+          }
+        }
+      },
+      ',' => current_row.push(std::mem::take(&mut current_cell)),
+      '\r' => (),
+      '\n' => {
+        current_row.push(std::mem::take(&mut current_cell));
+        rows.push((row_line, std::mem::take(&mut current_row)));
+        line += 1;
+        row_line = line;
+      },
+      c => current_cell.push(c)
+    }
+  }
+
+  if !current_cell.is_empty() || !current_row.is_empty() {
+    current_row.push(current_cell);
+    rows.push((row_line, current_row));
+  }
+
+  // Rows which are entirely empty, such as trailing blank lines, are ignored
+  Ok(rows
+    .into_iter()
+    .filter(|(_, row)| row.iter().any(|cell| !cell.trim().is_empty()))
+    .collect())
+
+}
+
+// Returns the name of the state along with whether it is a start state and whether it is final
+fn parse_row_label(row_label: &str) -> (&str, bool, bool) {
+
+  let mut state_name = row_label.trim();
+  let mut is_start = false;
+  let mut is_final = false;
+
+  loop {
+    if let Some(remaining_name) = state_name.strip_prefix("->").or_else(|| state_name.strip_prefix('→')) {
+      is_start = true;
+      state_name = remaining_name.trim_start();
+    } else if let Some(remaining_name) = state_name.strip_prefix('*') {
+      is_final = true;
+      state_name = remaining_name.trim_start();
+    } else {
+      return (state_name, is_start, is_final);
+    }
+  }
+
+}
+
+// Returns the alphabet given in the header, excluding ϵ, alongside the parsed states, which are laid out in columns from the start state
+fn parse_transition_table(csv: &str) -> Result<(Vec<String>, HashMap<String, State>), TransitionTableError> {
+
+  let rows = parse_csv(csv)?;
+  let ((header_line, header), state_rows) = rows
+    .split_first()
+    .ok_or(TransitionTableError::EmptyTable)?;
+
+  let mut columns: Vec<String> = vec![];
+  for cell in &header[1..] {
+    let connection_character = match cell.trim() {
+      "ϵ" | "ε" | "λ" | "eps" | "epsilon" => String::from("ϵ"),
+      connection_character => connection_character.to_owned()
+    };
+
+    if columns.contains(&connection_character) {
+      return Err(TransitionTableError::DuplicateCharacter { line: *header_line, character: connection_character });
+    }
+    columns.push(connection_character);
+  }
+
+  // The first pass creates every state, so the second can connect rows to states which are declared further down
+  let mut state_positions: HashMap<String, State> = HashMap::new();
+  let mut state_keys_by_name: BTreeMap<String, String> = BTreeMap::new();
+
+  for (line, row) in state_rows {
+    if row.len() != header.len() {
+      return Err(TransitionTableError::WrongNumberOfCells { line: *line, expected: header.len(), found: row.len() });
+    }
+
+    let (state_name, is_start, is_final) = parse_row_label(&row[0]);
+
+    if state_keys_by_name.contains_key(state_name) {
+      return Err(TransitionTableError::DuplicateState { line: *line, state_name: state_name.to_owned() });
+    }
+
+    let coordinate = create_unique_state_coordinates(&state_positions.keys().cloned().collect());
+    let mut state = State::new(coordinate, is_start, is_final);
+    state.set_name(Some(state_name.to_owned()));
+    state_positions.insert(coordinate.into(), state);
+    state_keys_by_name.insert(state_name.to_owned(), coordinate.into());
+  }
+
+  for (line, row) in state_rows {
+    let (state_name, _, _) = parse_row_label(&row[0]);
+    let state_key = &state_keys_by_name[state_name];
+
+    for (connection_character, cell) in columns.iter().zip(&row[1..]) {
+      // Names such as {q0,q1} contain the separators themselves, so a cell holding exactly one name is taken as it is
+      let connected_state_names: Vec<&str> = if state_keys_by_name.contains_key(cell.trim()) {
+        vec![cell.trim()]
+      } else {
+        cell
+          .split(|c: char| c.is_whitespace() || c == ',' || c == '{' || c == '}')
+          .filter(|connected_state_name| !matches!(*connected_state_name, "" | "-" | "∅"))
+          .collect()
+      };
+
+      for connected_state_name in connected_state_names {
+        let connected_state_key = state_keys_by_name
+          .get(connected_state_name)
+          .ok_or_else(|| TransitionTableError::UnknownState { line: *line, state_name: connected_state_name.to_owned() })?;
+
+        state_positions
+          .get_mut(state_key)
+          .expect("Every state name should correspond to a created state")
+          .add_connection(connection_character, connected_state_key.to_owned());
+      }
+    }
+  }
+
+  let alphabet = columns
+    .into_iter()
+    .filter(|connection_character| connection_character != "ϵ")
+    .collect();
+
+  Ok((alphabet, layout_state_positions(&state_positions)))
+
+}
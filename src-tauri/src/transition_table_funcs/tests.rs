@@ -0,0 +1,96 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::transition_table_funcs::{convert_state_positions_to_transition_table, parse_transition_table,
+  TransitionTableError, TransitionTableFormat};
+  use crate::regular_expression_funcs::convert_regex_to_state_positions;
+  use crate::testing_automata_funcs::{test_string_dfa, test_string_nfa};
+  use crate::test_fixtures::get_start_state_key;
+  use app::models::{Coordinate, State};
+  use std::collections::HashMap;
+
+  #[test]
+  fn test_transition_table_import() {
+    // Rows may refer to states declared further down, and sets of states may be written in a few different ways
+    let csv = "δ,a,b,ε\r\n->q0,q0 q1,q0,-\r\nq1,∅,\"{q2}\",\r\n* q2,,,q0\r\n\r\n";
+    let (alphabet, state_positions) = parse_transition_table(csv).unwrap();
+    let start_state_key = get_start_state_key(&state_positions);
+
+    assert_eq!(alphabet, vec![String::from("a"), String::from("b")]);
+    assert_eq!(state_positions.len(), 3);
+
+    // States are laid out in columns by their distance from the start state
+    assert_eq!(start_state_key, "300,300");
+    assert!(state_positions["700,300"].is_final());
+    assert!(state_positions["700,300"].get_connections_by_character("ϵ").unwrap().contains("300,300"));
+
+    for (string_to_check, expected) in [("ab", true), ("abab", true), ("bab", true), ("a", false), ("", false)] {
+      let (is_accepted, _) = test_string_nfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned());
+      assert_eq!(is_accepted, expected, "Failed on {string_to_check}");
+    }
+  }
+
+  #[test]
+  fn test_transition_table_import_errors() {
+    assert_eq!(parse_transition_table("\n\n").unwrap_err(), TransitionTableError::EmptyTable);
+    assert_eq!(
+      parse_transition_table("state,a,a\n->q0,q0,q0\n").unwrap_err(),
+      TransitionTableError::DuplicateCharacter { line: 1, character: String::from("a") }
+    );
+    assert_eq!(
+      parse_transition_table("state,a\n->q0,q0\n*q0,q0\n").unwrap_err(),
+      TransitionTableError::DuplicateState { line: 3, state_name: String::from("q0") }
+    );
+    assert_eq!(
+      parse_transition_table("state,a\n->q0,q1\n").unwrap_err(),
+      TransitionTableError::UnknownState { line: 2, state_name: String::from("q1") }
+    );
+    assert_eq!(
+      parse_transition_table("state,a,b\n->q0,q0\n").unwrap_err(),
+      TransitionTableError::WrongNumberOfCells { line: 2, expected: 3, found: 2 }
+    );
+    assert_eq!(
+      parse_transition_table("state,a\n->q0,\"q0\n").unwrap_err(),
+      TransitionTableError::UnterminatedQuote { line: 2 }
+    );
+  }
+
+  #[test]
+  fn test_transition_table_export() {
+    // ab*
+    let mut q0 = State::new(Coordinate { x: 500, y: 300 }, true, false);
+    let mut q1 = State::new(Coordinate { x: 300, y: 300 }, false, true);
+    q0.add_connection("a", "300,300");
+    q1.add_connection("b", "300,300");
+    let state_positions = HashMap::from([(String::from("500,300"), q0), (String::from("300,300"), q1)]);
+
+    let csv = convert_state_positions_to_transition_table(&state_positions, &[String::from("b"), String::from("c")], TransitionTableFormat::CSV);
+    assert_eq!(csv, "state,b,c,a,ϵ\n->q0,,,q1,\n*q1,q1,,,\n");
+
+    let markdown = convert_state_positions_to_transition_table(&state_positions, &[], TransitionTableFormat::Markdown);
+    assert_eq!(markdown, "| state | a | b | ϵ |\n| --- | --- | --- | --- |\n| ->q0 | q1 |  |  |\n| \\*q1 |  | q1 |  |\n");
+  }
+
+  #[test]
+  fn test_transition_table_round_trip() {
+    for regex in ["(a+b)*abb", "ab*c+ca"] {
+      let (state_positions, start_state_key) = convert_regex_to_state_positions(regex).unwrap();
+
+      let csv = convert_state_positions_to_transition_table(&state_positions, &[], TransitionTableFormat::CSV);
+      let (_, reimported_state_positions) = parse_transition_table(&csv).unwrap();
+      let reimported_start_state_key = get_start_state_key(&reimported_state_positions);
+
+      assert_eq!(reimported_state_positions.len(), state_positions.len());
+      // States are placed in a different position, but named the same way, so exporting again gives the same table
+      assert_eq!(convert_state_positions_to_transition_table(&reimported_state_positions, &[], TransitionTableFormat::CSV), csv);
+
+      for string_to_check in ["", "a", "ab", "abb", "babb", "ac", "ca", "abbc"] {
+        assert_eq!(
+          test_string_dfa(reimported_state_positions.to_owned(), reimported_start_state_key.to_owned(), string_to_check.to_owned()).0,
+          test_string_dfa(state_positions.to_owned(), start_state_key.to_owned(), string_to_check.to_owned()).0,
+          "Failed on {string_to_check} for {regex}"
+        );
+      }
+    }
+  }
+
+}
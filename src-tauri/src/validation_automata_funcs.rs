@@ -1,38 +1,40 @@
-use crate::models::State;
+use app::labels::{get_connected_state_keys_by_symbol, TransitionLabel};
+use app::models::State;
 use std::collections::HashMap;
 
 #[tauri::command]
 pub fn verify_valid_dfa(state_positions: HashMap<String, State>, input_alphabet: Vec<String>) -> bool {
 
-  for state in state_positions.values() {
-
-    let all_connections_from_state = state.get_all_connections();
+  // Only NFAs may have several initial states
+  let number_of_start_states = state_positions
+    .values()
+    .filter(|state| state.is_start())
+    .count();
+  if number_of_start_states != 1 {
+    return false;
+  };
 
-    let connected_state_keys = all_connections_from_state.keys();
+  for state in state_positions.values() {
 
-    // If the number of keys in the state doesn't equal the alphabet, we know automatically
-    // That the dfa isn't valid
-    if &connected_state_keys.len() != &input_alphabet.len() {
-      return false;
+    // Every connection must be labelled with either a symbol of the input alphabet or a class of them,
+    // as ϵ transitions and symbols outside of the alphabet have no place in a DFA
+    for connection_label in state.get_all_connections().keys() {
+      match TransitionLabel::parse(connection_label) {
+        TransitionLabel::Epsilon => return false,
+        TransitionLabel::Symbol(symbol) if !input_alphabet.contains(&symbol) => return false,
+        _ => ()
+      };
     };
 
-    for character in connected_state_keys {
-      // Strictly speaking, in a DFA, every single state must have exactly 
-      // one connection for each character in the input alphabet
-      if !input_alphabet.contains(character){
+    // Strictly speaking, in a DFA, every single state must have exactly 
+    // one connection for each character in the input alphabet, 
+    // whichever labels those connections are written with
+    for character in &input_alphabet {
+      if get_connected_state_keys_by_symbol(state, character).len() != 1 {
         return false;
       };
-      
-      match state.get_connections_by_character(character) {
-        Some(connected_state_keys) => {
-          if connected_state_keys.len() != 1 {
-            return false
-          };
-        },
-        None => return false
-      };
     };
 
   };
   true
-}
\ No newline at end of file
+}
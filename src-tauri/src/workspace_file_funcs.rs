@@ -0,0 +1,224 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use app::{create_connections_from_state_positions, find_duplicate_state_name};
+use app::models::{split_connection_label, Connection, State, TypeOfAutomata, WorkspaceData};
+
+use crate::saving_automata_funcs::{create_workspace_with_automata, retrieve_workspace_data, update_default_connection_character,
+update_input_separator, update_max_revisions, update_showing_string_traversal, update_strict_checking};
+mod tests;
+
+// Workspaces are exported as JSON documents with the following layout:
+//
+// {
+//   "format": "inner-machinations-workspace",
+//   "version": 2,
+//   "workspace_name": "Even number of a's",
+//   "type_of_automata": "DFA",
+//   "alphabet": ["a", "b"],
+//   "should_strict_check": false,
+//   "should_show_string_traversal": true,
+//   "default_connection_character": "a",
+//   "input_separator": null,
+//   "max_revisions": 50,
+//   "state_positions": { "q0": { "id": "q0", "position": { "x": 300, "y": 300 }, "states_connected_to": { "a": ["q1"] }, ... } },
+//   "connections": [ { "curve": { "start_point": ..., "control_point_one": ..., "control_point_two": ..., "end_point": ... },
+//     "connection_character": "a", "element": "Connection", "start_state_id": "q0", "end_state_id": "q1" } ]
+// }
+//
+// state_positions and connections are serialized exactly as they are sent to the front end.
+// Any change to this layout must increment WORKSPACE_FILE_VERSION, and files of older versions must still be importable.
+// Version 1 files predate state ids, so their states are keyed by position and their connections by the points of their
+// curves, which is exactly how states and connections sent without an id are read. Files without an input separator or
+// a maximum number of revisions, which were only added later, import with the defaults a new workspace would have.
+// Only the connections of an automaton are saved, so files whose connections are missing or don't give the transitions
+// of their states have them drawn again from the states
+pub const WORKSPACE_FILE_FORMAT: &str = "inner-machinations-workspace";
+pub const WORKSPACE_FILE_VERSION: u32 = 2;
+pub const OLDEST_WORKSPACE_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkspaceFile {
+  format: String,
+  version: u32,
+  workspace_name: String,
+  type_of_automata: TypeOfAutomata,
+  alphabet: Vec<String>,
+  should_strict_check: bool,
+  should_show_string_traversal: bool,
+  default_connection_character: String,
+  #[serde(default)]
+  input_separator: Option<String>,
+  #[serde(default = "default_max_revisions")]
+  max_revisions: i32,
+  state_positions: HashMap<String, State>,
+  connections: Vec<Connection>
+}
+
+// The same as the default of the saved_workspaces column
+fn default_max_revisions() -> i32 {
+  50
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum WorkspaceFileError {
+  UnableToReadFile,
+  UnableToWriteFile,
+  InvalidFileContents,
+  UnrecognizedFormat,
+  UnsupportedVersion(u32),
+  DuplicateStateName(String)
+}
+
+impl WorkspaceFile {
+
+  pub fn new(workspace_name: &str, workspace_data: &WorkspaceData) -> Self {
+    WorkspaceFile {
+      format: WORKSPACE_FILE_FORMAT.to_owned(),
+      version: WORKSPACE_FILE_VERSION,
+      workspace_name: workspace_name.to_owned(),
+      type_of_automata: workspace_data.get_type_of_automata(),
+      alphabet: workspace_data.get_alphabet().to_owned(),
+      should_strict_check: workspace_data.should_strict_check(),
+      should_show_string_traversal: workspace_data.should_show_string_traversal(),
+      default_connection_character: workspace_data.get_default_connection_character().to_owned(),
+      input_separator: workspace_data.get_input_separator().to_owned(),
+      max_revisions: workspace_data.get_max_revisions(),
+      state_positions: workspace_data.get_state_positions().to_owned(),
+      connections: workspace_data.get_list_of_connections().to_owned()
+    }
+  }
+
+  pub fn get_type_of_automata(&self) -> TypeOfAutomata {
+    self.type_of_automata
+  }
+
+  pub fn get_alphabet(&self) -> &Vec<String> {
+    &self.alphabet
+  }
+
+  pub fn get_state_positions(&self) -> &HashMap<String, State> {
+    &self.state_positions
+  }
+
+  pub fn get_connections(&self) -> &Vec<Connection> {
+    &self.connections
+  }
+
+  pub fn to_json(&self) -> String {
+    serde_json::to_string_pretty(self)
+      .expect("A workspace file should always be serializable")
+  }
+
+  pub fn from_json(json: &str) -> Result<Self, WorkspaceFileError> {
+
+    // The format and version are checked before anything else, so that a file from a newer version of the
+    // app gives a meaningful error rather than failing on whichever field happened to change
+    let value: serde_json::Value = serde_json::from_str(json)
+      .map_err(|_| WorkspaceFileError::InvalidFileContents)?;
+
+    if value.get("format").and_then(|format| format.as_str()) != Some(WORKSPACE_FILE_FORMAT) {
+      return Err(WorkspaceFileError::UnrecognizedFormat);
+    }
+
+    let version = value
+      .get("version")
+      .and_then(|version| version.as_u64())
+      .ok_or(WorkspaceFileError::InvalidFileContents)?;
+
+    if !(OLDEST_WORKSPACE_FILE_VERSION as u64..=WORKSPACE_FILE_VERSION as u64).contains(&version) {
+      return Err(WorkspaceFileError::UnsupportedVersion(version as u32));
+    }
+
+    let mut workspace_file: WorkspaceFile = serde_json::from_value(value)
+      .map_err(|_| WorkspaceFileError::InvalidFileContents)?;
+
+    if let Some(state_name) = find_duplicate_state_name(&workspace_file.state_positions) {
+      return Err(WorkspaceFileError::DuplicateStateName(state_name));
+    }
+
+    if get_transitions_of_connections(&workspace_file.connections, &workspace_file.state_positions)
+      != get_transitions_of_states(&workspace_file.state_positions) {
+      workspace_file.connections = create_connections_from_state_positions(&workspace_file.state_positions);
+    }
+
+    Ok(workspace_file)
+
+  }
+
+}
+
+// Transitions as (start state key, character, end state key), as the connections would be saved
+fn get_transitions_of_connections(connections: &[Connection], state_positions: &HashMap<String, State>) -> BTreeSet<(String, String, String)> {
+
+  let mut transitions = BTreeSet::new();
+
+  for connection in connections {
+    let start_state_key = connection.get_start_state_key(state_positions);
+    let end_state_key = connection.get_end_state_key(state_positions);
+    for connection_character in split_connection_label(&connection.connection_character) {
+      transitions.insert((start_state_key.to_owned(), connection_character, end_state_key.to_owned()));
+    }
+  }
+
+  transitions
+
+}
+
+fn get_transitions_of_states(state_positions: &HashMap<String, State>) -> BTreeSet<(String, String, String)> {
+
+  let mut transitions = BTreeSet::new();
+
+  for (state_key, state) in state_positions {
+    for (connection_character, connected_state_keys) in state.get_all_connections() {
+      for connected_state_key in connected_state_keys {
+        transitions.insert((state_key.to_owned(), connection_character.to_owned(), connected_state_key.to_owned()));
+      }
+    }
+  }
+
+  transitions
+
+}
+
+#[tauri::command]
+pub fn export_workspace(workspace_name: &str, email: &str, path: &str) -> Result<(), WorkspaceFileError> {
+
+  let workspace_data = retrieve_workspace_data(workspace_name, email);
+  let workspace_file = WorkspaceFile::new(workspace_name, &workspace_data);
+
+  fs::write(path, workspace_file.to_json())
+    .map_err(|_| WorkspaceFileError::UnableToWriteFile)
+
+}
+
+// Returns the name the workspace was imported under, as a number is appended to the
+// name stored in the file if the user already has a workspace with that name
+#[tauri::command]
+pub fn import_workspace(email: &str, path: &str) -> Result<String, WorkspaceFileError> {
+
+  let json = fs::read_to_string(path)
+    .map_err(|_| WorkspaceFileError::UnableToReadFile)?;
+
+  let workspace_file = WorkspaceFile::from_json(&json)?;
+
+  let workspace_name = create_workspace_with_automata(
+    email,
+    &workspace_file.workspace_name,
+    workspace_file.type_of_automata,
+    workspace_file.alphabet.iter().map(|c| c.as_str()).collect(),
+    workspace_file.state_positions,
+    workspace_file.connections
+  );
+
+  update_strict_checking(&workspace_name, email, workspace_file.should_strict_check);
+  update_showing_string_traversal(&workspace_name, email, workspace_file.should_show_string_traversal);
+  update_default_connection_character(&workspace_name, email, workspace_file.default_connection_character);
+  update_input_separator(&workspace_name, email, workspace_file.input_separator);
+  update_max_revisions(&workspace_name, email, workspace_file.max_revisions);
+
+  Ok(workspace_name)
+
+}
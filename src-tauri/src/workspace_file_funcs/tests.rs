@@ -0,0 +1,152 @@
+#[cfg(test)]
+pub mod tests {
+  use crate::testing_automata_funcs::test_string_dfa;
+  use crate::test_fixtures::create_workspace_data;
+  use crate::workspace_file_funcs::{WorkspaceFile, WorkspaceFileError};
+  use app::create_connections_from_state_positions;
+  use app::models::{Coordinate, State, TypeOfAutomata};
+  use std::collections::{BTreeSet, HashMap};
+
+  // Written before states had ids, so everything is keyed by position
+  const VERSION_1_FILE: &str = r#"{
+    "format": "inner-machinations-workspace",
+    "version": 1,
+    "workspace_name": "Even number of a's",
+    "type_of_automata": "DFA",
+    "alphabet": ["a", "b"],
+    "should_strict_check": false,
+    "should_show_string_traversal": true,
+    "default_connection_character": "a",
+    "state_positions": {
+      "300,300": { "position": { "x": 300, "y": 300 }, "states_connected_to": { "a": ["500,300"], "b": ["300,300"] },
+        "is_start": true, "is_final": true, "element": "State" },
+      "500,300": { "position": { "x": 500, "y": 300 }, "states_connected_to": { "a": ["300,300"], "b": ["500,300"] },
+        "is_start": false, "is_final": false, "element": "State" }
+    },
+    "connections": [
+      { "curve": { "start_point": { "x": 300, "y": 300 }, "control_point_one": { "x": 400, "y": 250 },
+        "control_point_two": { "x": 400, "y": 250 }, "end_point": { "x": 500, "y": 300 } },
+        "connection_character": "a", "element": "Connection" },
+      { "curve": { "start_point": { "x": 500, "y": 300 }, "control_point_one": { "x": 400, "y": 350 },
+        "control_point_two": { "x": 400, "y": 350 }, "end_point": { "x": 300, "y": 300 } },
+        "connection_character": "a", "element": "Connection" },
+      { "curve": { "start_point": { "x": 300, "y": 300 }, "control_point_one": { "x": 250, "y": 150 },
+        "control_point_two": { "x": 350, "y": 150 }, "end_point": { "x": 300, "y": 300 } },
+        "connection_character": "b", "element": "Connection" },
+      { "curve": { "start_point": { "x": 500, "y": 300 }, "control_point_one": { "x": 450, "y": 150 },
+        "control_point_two": { "x": 550, "y": 150 }, "end_point": { "x": 500, "y": 300 } },
+        "connection_character": "b", "element": "Connection" }
+    ]
+  }"#;
+
+  #[test]
+  fn test_importing_version_1_files() {
+    let workspace_file = WorkspaceFile::from_json(VERSION_1_FILE).unwrap();
+    let state_positions = workspace_file.get_state_positions();
+
+    assert_eq!(workspace_file.get_type_of_automata(), TypeOfAutomata::DFA);
+    assert_eq!(state_positions["300,300"].get_id(), "300,300");
+    assert_eq!(state_positions["500,300"].get_id(), "500,300");
+
+    let connection = &workspace_file.get_connections()[0];
+    assert_eq!(connection.get_start_state_key(state_positions), "300,300");
+    assert_eq!(connection.get_end_state_key(state_positions), "500,300");
+
+    assert!(test_string_dfa(state_positions.to_owned(), String::from("300,300"), String::from("abab")).0);
+    assert!(!test_string_dfa(state_positions.to_owned(), String::from("300,300"), String::from("ab")).0);
+
+    // Options added after version 1 take the defaults of a new workspace
+    assert_eq!(workspace_file.input_separator, None);
+    assert_eq!(workspace_file.max_revisions, 50);
+  }
+
+  #[test]
+  fn test_rebuilding_connections() {
+    // Connections that give the transitions of the states are kept as they were drawn
+    let workspace_file = WorkspaceFile::from_json(VERSION_1_FILE).unwrap();
+    assert_eq!(workspace_file.get_connections().len(), 4);
+    assert_eq!(workspace_file.get_connections()[0].curve.control_point_one, Coordinate { x: 400, y: 250 });
+
+    // Files missing connections, or whose connections disagree with their states, have them drawn again from the states
+    let without_connections = VERSION_1_FILE.split("\"connections\"").next().unwrap().to_owned() + "\"connections\": [] }";
+    let with_wrong_character = VERSION_1_FILE.replacen("\"connection_character\": \"a\"", "\"connection_character\": \"b\"", 1);
+    let with_merged_characters = VERSION_1_FILE.replacen("\"connection_character\": \"a\"", "\"connection_character\": \"a,b\"", 1);
+
+    for json in [without_connections, with_wrong_character, with_merged_characters] {
+      let workspace_file = WorkspaceFile::from_json(&json).unwrap();
+      let state_positions = workspace_file.get_state_positions();
+      assert_eq!(workspace_file.get_connections().len(), create_connections_from_state_positions(state_positions).len());
+
+      let transitions: BTreeSet<(String, &str, String)> = workspace_file
+        .get_connections()
+        .iter()
+        .map(|connection| {
+          (connection.get_start_state_key(state_positions), connection.connection_character.as_str(), connection.get_end_state_key(state_positions))
+        })
+        .collect();
+      assert_eq!(transitions, BTreeSet::from([
+        (String::from("300,300"), "a", String::from("500,300")),
+        (String::from("300,300"), "b", String::from("300,300")),
+        (String::from("500,300"), "a", String::from("300,300")),
+        (String::from("500,300"), "b", String::from("500,300"))
+      ]));
+    }
+  }
+
+  #[test]
+  fn test_workspace_file_round_trip() {
+    let mut q0 = State::with_id("q0", Coordinate { x: 300, y: 300 }, true, false);
+    let mut q1 = State::with_id("q1", Coordinate { x: 500, y: 300 }, false, true);
+    q0.set_name(Some(String::from("start")));
+    q0.add_connection(",", "q1");
+    q0.add_connection("if", "q1");
+    q1.add_connection("[0-9]", "q1");
+
+    let state_positions = HashMap::from([
+      (String::from("q0"), q0),
+      (String::from("q1"), q1)
+    ]);
+    let connections = create_connections_from_state_positions(&state_positions);
+
+    let empty_workspace_data = create_workspace_data(serde_json::json!({
+      "workspace_name": "Keywords",
+      "should_strict_check": true,
+      "default_connection_character": "if",
+      "input_separator": " ",
+      "max_revisions": 7
+    }));
+    let workspace_data = empty_workspace_data.with_automata(
+      state_positions.clone(),
+      connections,
+      TypeOfAutomata::NFA,
+      vec![String::from(","), String::from("if"), String::from("7")]
+    );
+
+    let workspace_file = WorkspaceFile::from_json(&WorkspaceFile::new("Keywords", &workspace_data).to_json()).unwrap();
+
+    assert_eq!(workspace_file.workspace_name, "Keywords");
+    assert_eq!(workspace_file.get_type_of_automata(), TypeOfAutomata::NFA);
+    assert_eq!(workspace_file.get_alphabet(), &[String::from(","), String::from("if"), String::from("7")]);
+    assert!(workspace_file.should_strict_check);
+    assert!(!workspace_file.should_show_string_traversal);
+    assert_eq!(workspace_file.default_connection_character, "if");
+    assert_eq!(workspace_file.input_separator.as_deref(), Some(" "));
+    assert_eq!(workspace_file.max_revisions, 7);
+    for (state_key, state) in &state_positions {
+      assert_eq!(workspace_file.get_state_positions()[state_key].get_all_connections(), state.get_all_connections());
+    }
+    assert_eq!(workspace_file.get_state_positions()["q0"].get_name(), Some("start"));
+
+    let labels: BTreeSet<&str> = workspace_file.get_connections().iter().map(|connection| connection.connection_character.as_str()).collect();
+    assert_eq!(labels, BTreeSet::from(["\\,,if", "[0-9]"]));
+  }
+
+  #[test]
+  fn test_unsupported_versions() {
+    for version in ["0", "3"] {
+      let json = VERSION_1_FILE.replacen("\"version\": 1", &format!("\"version\": {version}"), 1);
+      assert_eq!(WorkspaceFile::from_json(&json).unwrap_err(), WorkspaceFileError::UnsupportedVersion(version.parse().unwrap()));
+    }
+  }
+
+}
@@ -5,7 +5,11 @@ import type { Token } from "./types";
 
 // In both drawing states to the canvas and connecting data in the rust
 export interface State {
-  // Specifying coordinates of node as an identification for a node
+  // Identifies the node in state_positions and in transitions. States from before ids existed, and ones placed
+  // but not yet saved by older versions, are identified by their position instead
+  id?: string;
+  // Shown to the user in place of the id if given
+  name?: string | null;
   position: Coordinate;
   // CANNOT use Sets as SERDE cannot deserialize javascript Set into rust HashSet
   states_connected_to: Map<String, Array<String>>;
@@ -18,6 +22,9 @@ export interface Connection {
   curve: BezierCurve;
   connection_character: string;
   element: "Connection";
+  // The ids of the states either end, found from the curve when missing
+  start_state_id?: string;
+  end_state_id?: string;
 }
 
 export interface BezierCurve {
@@ -67,8 +74,10 @@ export interface Literal {
 }
 
 export interface WorkspaceData {
+  workspace_name: string,
   start_state_index: number | null,
   start_state_position: string | null,
+  start_state_positions: Array<string>,
   state_positions: { [key: string]: State; },
   list_of_states: Array<State>,
   list_of_connections: Array<Connection>,
@@ -77,6 +86,8 @@ export interface WorkspaceData {
   alphabet: Array<string>,
   should_strict_check: boolean,
   should_show_string_traversal: boolean,
-  default_connection_character: string
+  default_connection_character: string,
+  input_separator: string | null,
+  max_revisions: number
 
 }
@@ -28,5 +28,9 @@ export const selected_connection_index: Writable<number | null> = writable(null)
 export const should_strict_check: Writable<boolean> = writable(false);
 export const should_show_string_traversal: Writable<boolean> = writable(false);
 export const default_connection_character: Writable<string> = writable("a");
+// Tested strings are split into symbols at the separator, or by the longest symbol of the alphabet when it is empty
+export const input_separator: Writable<string> = writable("");
+// How many of the most recent saves are kept as revisions which can be restored
+export const max_revisions: Writable<number> = writable(50);
 export const workspace_name: Writable<string> = writable(getCookie("workspace_name"));
 export const email: Writable<string> = writable(getCookie("email"));
\ No newline at end of file
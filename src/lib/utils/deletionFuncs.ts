@@ -1,20 +1,28 @@
 import { get } from "svelte/store";
 import { list_of_all_elements, list_of_connections, list_of_states, start_state_index, start_state_position, state_positions } from "./automataStores";
 import type { Connection, State } from "../types/interfaces";
-import { convertCoordinateToString, removeFirstElementFromArray } from "./miscUtils";
+import { findStateByIdOrPosition, getStateKey, removeFirstElementFromArray, splitConnectionLabel } from "./miscUtils";
 
-export const removeStateFromConnections = (given_state: State, state_to_remove: State, connection_character: string): State => {
+// A connection's label may hold several characters, such as "a,b", each of which is its own transition to remove
+export const removeStateFromConnections = (given_state: State, state_to_remove: State, connection_label: string): State => {
 
-  let states_connected_by_char = given_state.states_connected_to.get(connection_character);
-  if(!states_connected_by_char) {
-    // If there are no connections by the given char, we just return the original state
-    return given_state;
+  let has_removed_any_connection = false;
+
+  for(const connection_character of splitConnectionLabel(connection_label)) {
+    const states_connected_by_char = given_state.states_connected_to.get(connection_character);
+    if(states_connected_by_char) {
+      states_connected_by_char.delete(getStateKey(state_to_remove));
+      has_removed_any_connection = true;
+    }
   }
 
-  states_connected_by_char.delete(convertCoordinateToString(state_to_remove.position));
+  if(!has_removed_any_connection) {
+    // If there are no connections by the given chars, we just return the original state
+    return given_state;
+  }
 
   state_positions.update((positions)=>{
-    positions.set(convertCoordinateToString(given_state.position), given_state);
+    positions.set(getStateKey(given_state), given_state);
     return positions;
   });
 
@@ -65,7 +73,7 @@ export const undo = (): void => {
       // Will have to look into this later, but for some reason type checking fails to work properly
       // inside an update callback even though we have properly type checked that removed_element
       // must be of type state
-      positions.delete(convertCoordinateToString((removed_element as State).position))
+      positions.delete(getStateKey(removed_element as State))
       return positions;
     })
     return;
@@ -76,12 +84,16 @@ export const undo = (): void => {
     return connections.slice(0, connections.length - 1);
   });
 
-  const starting_state: State | undefined = get(state_positions).get(
-    convertCoordinateToString(removed_element.curve.start_point),
+  const starting_state: State | undefined = findStateByIdOrPosition(
+    get(state_positions),
+    removed_element.start_state_id,
+    removed_element.curve.start_point
   );
 
-  const ending_state: State | undefined = get(state_positions).get(
-    convertCoordinateToString(removed_element.curve.end_point)
+  const ending_state: State | undefined = findStateByIdOrPosition(
+    get(state_positions),
+    removed_element.end_state_id,
+    removed_element.curve.end_point
   );
   
   if (!starting_state || !ending_state) {
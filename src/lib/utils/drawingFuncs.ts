@@ -173,6 +173,16 @@ const drawNode = (
     context.closePath();
     context.fillStyle = "rgb(22, 163, 74)";
   }
+
+  // Named states show their name inside them, shrunk along with the rest of the canvas
+  if (node.name) {
+    context.font = "20px Arial";
+    if (scale > 1) {
+      context.font = "10px Arial";
+    }
+    context.fillStyle = "black";
+    context.fillText(node.name, node.position.x / scale, node.position.y / scale, 60 / scale);
+  }
 };
 
 export function drawParseTree(parse_tree: Token, context: CanvasRenderingContext2D, start_position: Coordinate) {
@@ -1,9 +1,31 @@
-import type { Coordinate } from "../types/interfaces";
+import type { Coordinate, State } from "../types/interfaces";
 
 export const convertCoordinateToString = (coordinate: Coordinate): string => {
   return coordinate.x + "," + coordinate.y;
 };
 
+export const getStateKey = (state: State): string => {
+  return state.id ?? convertCoordinateToString(state.position);
+};
+
+// Positions are no longer keys, so states under the cursor or at the end of a connection have to be searched for
+export const findStateAtPosition = (state_positions: Map<string, State>, position: Coordinate): State | undefined => {
+  for (const state of state_positions.values()) {
+    if (state.position.x === position.x && state.position.y === position.y) {
+      return state;
+    }
+  }
+  return undefined;
+};
+
+export const findStateByIdOrPosition = (
+  state_positions: Map<string, State>,
+  state_id: string | undefined,
+  position: Coordinate
+): State | undefined => {
+  return (state_id && state_positions.get(state_id)) || findStateAtPosition(state_positions, position);
+};
+
 export const removeFirstElementFromArray = <T>(list: Array<T>, element_to_remove: T): Array<T> => {
 
   const index_of_element_to_remove = list.indexOf(element_to_remove);
@@ -41,4 +63,45 @@ export function convertFormDataEntriesToStringArray(form_data: Array<FormDataEnt
   })
 
   return stringified_array;
-}
\ No newline at end of file
+}
+// Character classes like [0-9] and the [*] wildcard stand for many symbols, so they don't belong in the alphabet.
+// This is only a quick check, the backend decides what a label actually matches
+export const isSymbolicLabel = (label: string): boolean => {
+  return label.length > 2 && label.startsWith("[") && label.endsWith("]");
+};
+
+// Mirrors join_connection_characters and split_connection_label in the backend. Labels list their characters separated
+// by commas, with commas and backslashes within a character escaped with a backslash, so "x\\,y,a" is the characters
+// "x,y" and "a" and classes such as [,;] keep their commas
+export const joinConnectionCharacters = (connection_characters: Array<string>): string => {
+  return connection_characters
+    .map((connection_character) => connection_character.split("\\").join("\\\\").split(",").join("\\,"))
+    .join(",");
+};
+
+export const splitConnectionLabel = (label: string): Array<string> => {
+  let connection_characters: Array<string> = [];
+  let connection_character = "";
+  const characters = Array.from(label);
+
+  for(let index = 0; index < characters.length; index++) {
+    if(characters[index] === "\\") {
+      // A backslash at the very end escapes nothing, so is kept as it is
+      index++;
+      connection_character += characters[index] ?? "\\";
+    } else if(characters[index] === ",") {
+      connection_characters.push(connection_character);
+      connection_character = "";
+    } else {
+      connection_character += characters[index];
+    }
+  }
+  connection_characters.push(connection_character);
+
+  // A label with nothing in it, as connections have while they are being drawn, is kept as a single empty character
+  if(connection_characters.length > 1) {
+    connection_characters = connection_characters.filter((connection_character) => connection_character !== "");
+  }
+
+  return connection_characters;
+};
@@ -1,5 +1,5 @@
 import { get } from "svelte/store";
-import { default_connection_character, input_alphabet, list_of_connections, list_of_states, should_show_string_traversal, should_strict_check, start_state_index, start_state_position, state_positions, type_of_automata } from "$lib/utils/automataStores";
+import { default_connection_character, input_alphabet, list_of_connections, list_of_states, should_show_string_traversal, should_strict_check, input_separator, max_revisions, start_state_index, start_state_position, state_positions, type_of_automata } from "$lib/utils/automataStores";
 import type { State, WorkspaceData } from "$lib/types/interfaces";
 import { convertCoordinateToString } from "$lib/utils/miscUtils";
 import { Automata } from "../types/enums";
@@ -80,5 +80,13 @@ export const setTauriResponses = (tauri_response: WorkspaceData): void => {
     tauri_response.default_connection_character
   );
 
+  input_separator.set(
+    tauri_response.input_separator ?? ""
+  );
+
+  max_revisions.set(
+    tauri_response.max_revisions
+  );
+
 
 }
\ No newline at end of file
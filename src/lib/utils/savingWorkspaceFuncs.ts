@@ -1,7 +1,7 @@
 import { invoke } from "@tauri-apps/api";
 import { dialogue_to_user, input_alphabet, list_of_connections, 
 state_positions, email, workspace_name, should_strict_check, should_show_string_traversal,
-default_connection_character } from "./automataStores";
+default_connection_character, input_separator, max_revisions } from "./automataStores";
 import { get } from "svelte/store";
 import { convertFormDataEntriesToStringArray } from "./miscUtils";
 
@@ -15,6 +15,13 @@ export const saveWorkspace = async () => {
     states: get(state_positions),
     connections: get(list_of_connections),
     alphabet: get(input_alphabet)
+  }).catch((err) => {
+    // Nothing is saved while two Nodes share a name
+    if(err?.DuplicateName !== undefined) {
+      dialogue_to_user.set(`More than one Node is named ${err.DuplicateName}, so the workspace was not saved.`);
+    } else {
+      dialogue_to_user.set("The workspace could not be saved.");
+    }
   });
 };
 
@@ -42,6 +49,21 @@ export async function saveOptions(form: HTMLFormElement | undefined) {
   await invoke("update_showing_string_traversal", 
     {email: get(email), workspaceName: get(workspace_name), shouldShowTraversal: get(should_show_string_traversal)});
 
+  const new_input_separator = data.get("separator")?.toString() ?? "";
+
+  await invoke("update_input_separator",
+    {email: get(email), workspaceName: get(workspace_name), inputSeparator: new_input_separator});
+
+  input_separator.set(new_input_separator);
+
+  const new_max_revisions = Number(data.get("max_revisions")?.toString() || NaN);
+
+  if(Number.isInteger(new_max_revisions)) {
+    await invoke("update_max_revisions",
+      {email: get(email), workspaceName: get(workspace_name), maxRevisions: new_max_revisions});
+    max_revisions.set(Math.max(new_max_revisions, 0));
+  }
+
   const new_default_connection_character = data.get("default_character")?.toString();
   
   if(!new_default_connection_character) {
@@ -8,7 +8,8 @@ export const checkInputtedString = async (
   state_positions: Map<string, State>,
   string_to_check: string | null,
   should_strict_check: boolean,
-  input_alphabet: Array<string>
+  input_alphabet: Array<string>,
+  input_separator: string
   ): Promise<CheckedStringResponse> => {
 
   const is_dfa_valid: boolean = await checkValidityOfDFA(should_strict_check, type_of_automata, state_positions, input_alphabet);
@@ -27,6 +28,12 @@ export const checkInputtedString = async (
       states_traversed: []
     };
   }
+  // Symbols may be longer than a single character, so the string is split into them before being tested
+  const symbols_to_check: Array<string> = await invoke("tokenize_input_string", {
+    stringToCheck: string_to_check,
+    inputAlphabet: input_alphabet,
+    inputSeparator: input_separator,
+  });
   // No feedback message needs to be displayed if the automata in question is valid
   let is_string_accepted_after_test: boolean;
   let states_traversed_after_test: Array<State>;
@@ -34,10 +41,10 @@ export const checkInputtedString = async (
     // Setting the states traversed when checking 
     // the string and displaying to the user whether the string was accepted
     case Automata.DFA:
-      [is_string_accepted_after_test, states_traversed_after_test] = await invoke("test_string_dfa", {
+      [is_string_accepted_after_test, states_traversed_after_test] = await invoke("test_symbols_dfa", {
         statePositions: state_positions,
         startStateCoordinates: start_state_coordinates,
-        stringToCheck: string_to_check,
+        symbolsToCheck: symbols_to_check,
       });
 
       return {
@@ -47,10 +54,10 @@ export const checkInputtedString = async (
       };
 
     case Automata.NFA:
-      [is_string_accepted_after_test, states_traversed_after_test] = await invoke("test_string_nfa", {
+      [is_string_accepted_after_test, states_traversed_after_test] = await invoke("test_symbols_nfa", {
         statePositions: state_positions,
         startStateCoordinates: start_state_coordinates,
-        stringToCheck: string_to_check,
+        symbolsToCheck: symbols_to_check,
       });
       
       return {
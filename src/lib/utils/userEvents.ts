@@ -1,14 +1,13 @@
 import { get } from "svelte/store";
-import { current_action, dialogue_to_user, input_alphabet, list_of_all_elements, list_of_connections, list_of_states, selected_connection_index, start_state_index, start_state_position, state_positions } from "./automataStores";
+import { current_action, dialogue_to_user, input_alphabet, list_of_all_elements, list_of_connections, list_of_states, selected_connection_index, start_state_index, start_state_position, state_positions, type_of_automata } from "./automataStores";
 import type { BezierCurve, Connection, Coordinate, State } from "../types/interfaces";
-import { convertCoordinateToString } from "./miscUtils";
-import { Action } from "../types/enums";
+import { findStateAtPosition, findStateByIdOrPosition, getStateKey, isSymbolicLabel, joinConnectionCharacters, splitConnectionLabel } from "./miscUtils";
+import { Action, Automata } from "../types/enums";
 
 export const handleUserClickingCanvas = (cursor_x_pos: number, cursor_y_pos: number, default_connection_character: string) => {
   dialogue_to_user.set(null);
   const cursor_coords: Coordinate = { x: cursor_x_pos, y: cursor_y_pos };
-  const cursor_coords_as_string: string = convertCoordinateToString(cursor_coords);
-  const selected_state: State | undefined = get(state_positions).get(cursor_coords_as_string);
+  const selected_state: State | undefined = findStateAtPosition(get(state_positions), cursor_coords);
   const current_user_action = get(current_action);
 
   if(current_user_action === Action.ADDING_REGULAR_STATE) {
@@ -16,13 +15,13 @@ export const handleUserClickingCanvas = (cursor_x_pos: number, cursor_y_pos: num
       dialogue_to_user.set("You cannot place a Node on top of another Node.")
       return;
     }
-    addState(cursor_coords, cursor_coords_as_string, false);
+    addState(cursor_coords, false);
   }else if(current_user_action === Action.ADDING_START_STATE){
     if(selected_state !== undefined) {
       dialogue_to_user.set("You cannot place a Node on top of another Node.")
       return;
     }
-    addState(cursor_coords, cursor_coords_as_string, true);
+    addState(cursor_coords, true);
     current_action.set(Action.ADDING_REGULAR_STATE);
   }else if(current_user_action === Action.ADDING_FINAL_STATE) {
     if(selected_state === undefined) {
@@ -33,14 +32,14 @@ export const handleUserClickingCanvas = (cursor_x_pos: number, cursor_y_pos: num
       return;
     }
     selected_state.is_final = true;
-    make_final_state(selected_state, cursor_x_pos, cursor_y_pos, cursor_coords_as_string);
+    make_final_state(selected_state, cursor_x_pos, cursor_y_pos);
   } else if(current_user_action === Action.PLACING_START_OF_LINE) {
     if(selected_state === undefined) {
       dialogue_to_user.set("You must place an arrow on top of a Node.");
       return;
     }
     addConnection(cursor_coords, default_connection_character);
-    if(!get(input_alphabet).includes(default_connection_character)) {
+    if(!isSymbolicLabel(default_connection_character) && !get(input_alphabet).includes(default_connection_character)) {
       input_alphabet.update((previous_input_alphabet) => {
         previous_input_alphabet.push(default_connection_character);
         return previous_input_alphabet;
@@ -70,7 +69,7 @@ export const handleUserClickingCanvas = (cursor_x_pos: number, cursor_y_pos: num
       return;
     }
 
-    placeEndOfLine(connection, selected_state, cursor_coords, cursor_coords_as_string);
+    placeEndOfLine(connection, selected_state, cursor_coords);
     current_action.set(Action.PLACING_START_OF_LINE);
     
   } else if(current_user_action === Action.DRAGGING_LINE) {
@@ -85,7 +84,7 @@ export const handleUserClickingCanvas = (cursor_x_pos: number, cursor_y_pos: num
 
 }
 
-const placeEndOfLine = (connection: Connection, selected_state: State, cursor_coords: Coordinate, cursor_coords_as_string: string) => {
+const placeEndOfLine = (connection: Connection, selected_state: State, cursor_coords: Coordinate) => {
 
   connection.curve.end_point = selected_state.position;
   if(
@@ -104,28 +103,34 @@ const placeEndOfLine = (connection: Connection, selected_state: State, cursor_co
     connection.curve.control_point_two = selected_state.position;
   }
 
-  const starting_state_of_connection: State | undefined = get(state_positions)
-    .get(convertCoordinateToString(connection.curve.start_point));
+  const starting_state_of_connection: State | undefined = findStateByIdOrPosition(
+    get(state_positions),
+    connection.start_state_id,
+    connection.curve.start_point
+  );
   
   if(starting_state_of_connection === undefined) {
     return;
   }
 
-  const connection_character = connection.connection_character;
+  connection.start_state_id = getStateKey(starting_state_of_connection);
+  connection.end_state_id = getStateKey(selected_state);
 
-  let states_connected_to_start_state_by_character = starting_state_of_connection
-    .states_connected_to
-    .get(connection_character);
+  for(const connection_character of splitConnectionLabel(connection.connection_character)) {
+    let states_connected_to_start_state_by_character = starting_state_of_connection
+      .states_connected_to
+      .get(connection_character);
 
-  if(states_connected_to_start_state_by_character === undefined){
-    states_connected_to_start_state_by_character = new Array();
-  }
+    if(states_connected_to_start_state_by_character === undefined){
+      states_connected_to_start_state_by_character = new Array();
+    }
 
-  states_connected_to_start_state_by_character.push(cursor_coords_as_string);
-  starting_state_of_connection.states_connected_to.set(
-    connection_character,
-    states_connected_to_start_state_by_character
-  )
+    states_connected_to_start_state_by_character.push(getStateKey(selected_state));
+    starting_state_of_connection.states_connected_to.set(
+      connection_character,
+      states_connected_to_start_state_by_character
+    )
+  }
 
   list_of_connections.update((connections)=> {
     // Once again for some reason the typing of connection isn't accurate hence the use of 'as'
@@ -140,7 +145,7 @@ const placeEndOfLine = (connection: Connection, selected_state: State, cursor_co
 
   state_positions.update((positions)=>{
     positions.set(
-      convertCoordinateToString(starting_state_of_connection.position),
+      getStateKey(starting_state_of_connection),
       starting_state_of_connection
     )
     return positions;
@@ -160,7 +165,7 @@ const addConnection = (cursor_coords: Coordinate, connection_char: string) => {
   const connection: Connection = {
     curve: curve,
     element: "Connection",
-    connection_character: connection_char,
+    connection_character: joinConnectionCharacters([connection_char]),
   };
 
   list_of_connections.update((connections)=>{
@@ -171,9 +176,11 @@ const addConnection = (cursor_coords: Coordinate, connection_char: string) => {
 
 }
 
-const addState = (cursor_coords: Coordinate, cursor_coords_as_string: string, make_start: boolean) => {
+const addState = (cursor_coords: Coordinate, make_start: boolean) => {
 
+  // The position is only where the state was first placed, so it cannot be relied on to be unused as an id
   const new_state: State = {
+    id: crypto.randomUUID(),
     position: cursor_coords,
     states_connected_to: new Map(),
     is_start: make_start,
@@ -196,7 +203,7 @@ const addState = (cursor_coords: Coordinate, cursor_coords_as_string: string, ma
   });
 
   state_positions.update((positions)=>{
-    positions.set(cursor_coords_as_string, new_state);
+    positions.set(getStateKey(new_state), new_state);
     return positions;
   });
 
@@ -204,7 +211,11 @@ const addState = (cursor_coords: Coordinate, cursor_coords_as_string: string, ma
 
 const update_start_state_information = (new_start_state: State): void => {
 
+  // NFAs may have several start states, in which case the first one placed is kept as the one the backend is given
   const previous_start_state_index = get(start_state_index);
+  if(get(type_of_automata) === Automata.NFA && previous_start_state_index !== null) {
+    return;
+  }
   if(previous_start_state_index !== null) {
     list_of_states.update((states)=>{
       const previous_start_state = states[previous_start_state_index];
@@ -214,11 +225,11 @@ const update_start_state_information = (new_start_state: State): void => {
     });
   }
   start_state_index.set(get(list_of_states).length);
-  start_state_position.set(convertCoordinateToString(new_start_state.position));
+  start_state_position.set(getStateKey(new_start_state));
 
 }
 
-const make_final_state = (state_to_update: State, cursor_x_pos: number, cursor_y_pos: number, cursor_coords_as_string: string) => {
+const make_final_state = (state_to_update: State, cursor_x_pos: number, cursor_y_pos: number) => {
 
   list_of_states.update((states)=>{
     states.forEach((state, index)=> {
@@ -230,7 +241,7 @@ const make_final_state = (state_to_update: State, cursor_x_pos: number, cursor_y
   });
 
   state_positions.update((positions)=>{
-    positions.set(cursor_coords_as_string, state_to_update);
+    positions.set(getStateKey(state_to_update), state_to_update);
     return positions;
   });
 